@@ -0,0 +1,79 @@
+use clap::Args;
+use std::path::PathBuf;
+
+use crate::coverage::compute_coverage;
+use crate::languages::get_adapter;
+use crate::traverser::detect_language;
+use crate::verify::parse_language;
+
+#[derive(Args)]
+pub struct CoverageArgs {
+    /// Source file to analyze
+    pub file: String,
+    /// Override language detection (by default, inferred from the file extension)
+    #[arg(long)]
+    pub language: Option<String>,
+    /// Emit a JSON report instead of a human-readable one
+    #[arg(long)]
+    pub json: bool,
+}
+
+pub fn run(args: CoverageArgs) {
+    let path = PathBuf::from(&args.file);
+
+    let lang = match &args.language {
+        Some(name) => match parse_language(name) {
+            Some(l) => l,
+            None => {
+                eprintln!("Error: unknown language '{}'", name);
+                std::process::exit(1);
+            }
+        },
+        None => match detect_language(&path) {
+            Some(l) => l,
+            None => {
+                eprintln!("Error: cannot detect language for '{}', pass --language", path.display());
+                std::process::exit(1);
+            }
+        },
+    };
+
+    let source = match std::fs::read(&path) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Error: failed to read '{}': {}", path.display(), e);
+            std::process::exit(1);
+        }
+    };
+
+    let adapter = get_adapter(lang);
+    let mut parser = tree_sitter::Parser::new();
+    if parser.set_language(&adapter.language()).is_err() {
+        eprintln!("Error: failed to load grammar for '{}'", lang.as_str());
+        std::process::exit(1);
+    }
+    let Some(tree) = parser.parse(&source, None) else {
+        eprintln!("Error: failed to parse '{}'", path.display());
+        std::process::exit(1);
+    };
+
+    let report = compute_coverage(lang, &tree, &source);
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+        return;
+    }
+
+    println!("coverage for {} ({})", path.display(), report.language);
+    for kind in &report.by_kind {
+        println!("  {:<12} {}/{}", kind.label, kind.hits, kind.total);
+    }
+    if report.missed.is_empty() {
+        println!("  no unparsed declarations found");
+    } else {
+        println!("  missed:");
+        for m in &report.missed {
+            println!("    {} at line {} (bytes {}..{})", m.label, m.start_line, m.start_byte, m.end_byte);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use clap::Args;
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct ExportArgs {
+    /// Output format
+    #[arg(long, value_parser = ["dot", "json-graph"], default_value = "dot")]
+    pub format: String,
+    /// Write to this file instead of stdout
+    #[arg(long)]
+    pub out: Option<String>,
+    /// Project directory
+    #[arg(long, default_value = ".")]
+    pub dir: String,
+}
+
+pub fn run(args: ExportArgs) {
+    let root = PathBuf::from(&args.dir);
+    let root = match root.canonicalize() {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("Error: cannot resolve directory '{}': {}", args.dir, e);
+            std::process::exit(1);
+        }
+    };
+
+    let output_dir = root.join(".codemap");
+    let graph = match crate::graph::load_graph(&output_dir) {
+        Ok(g) => g,
+        Err(e) => {
+            eprintln!(
+                "Error: failed to load code graph from '{}/.codemap/': {}",
+                root.display(),
+                e
+            );
+            eprintln!("Hint: run 'codegraph scan {}' first.", root.display());
+            std::process::exit(1);
+        }
+    };
+
+    let rendered = match args.format.as_str() {
+        "dot" => crate::graph::export_dot(&graph),
+        "json-graph" => match serde_json::to_string_pretty(&crate::graph::export_json_graph(&graph)) {
+            Ok(json) => json,
+            Err(e) => {
+                eprintln!("Serialization error: {}", e);
+                std::process::exit(1);
+            }
+        },
+        other => {
+            eprintln!("Error: unknown export format '{}' (expected 'dot' or 'json-graph')", other);
+            std::process::exit(1);
+        }
+    };
+
+    match &args.out {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, &rendered) {
+                eprintln!("Error: failed to write '{}': {}", path, e);
+                std::process::exit(1);
+            }
+            println!("Exported {} graph to {}", args.format, path);
+        }
+        None => println!("{}", rendered),
+    }
+}
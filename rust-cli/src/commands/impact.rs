@@ -39,6 +39,9 @@ pub fn run(args: ImpactArgs) {
     println!("Impact analysis for: {}", args.target);
     println!("  Target type: {}", result.target_type.as_str());
     println!("  Target module: {}", result.target_module);
+    if let Some(function) = &result.target_function {
+        println!("  Target function: {function}");
+    }
 
     let direct_str = if result.direct_dependants.is_empty() {
         "(none)".to_string()
@@ -63,4 +66,15 @@ pub fn run(args: ImpactArgs) {
     for file in &result.impacted_files {
         println!("    - {file}");
     }
+
+    if let Some(cycle) = &result.cycle_warning {
+        println!(
+            "  Warning: target is part of a circular dependency ({}); transitive dependants above may be incomplete",
+            cycle.join(" -> ")
+        );
+    }
+
+    if !result.suggestions.is_empty() {
+        println!("  Did you mean: {}?", result.suggestions.join(", "));
+    }
 }
@@ -0,0 +1,257 @@
+use clap::Args;
+use serde_json::{json, Value};
+use std::io::{BufRead, Read, Write};
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct LspArgs {
+    /// Project directory
+    pub dir: Option<String>,
+}
+
+/// 以 stdio 启动一个最小化的 LSP 服务器
+///
+/// 支持 `initialize`、`textDocument/documentSymbol`、`workspace/symbol`、
+/// `textDocument/definition`、`textDocument/didSave`（触发增量重扫）、`shutdown`/`exit`。
+/// 协议细节（纯逻辑部分）见 `crate::lsp`；这里只负责 JSON-RPC 的帧读写与调度。
+pub fn run(args: LspArgs) {
+    let dir = args.dir.unwrap_or_else(|| ".".to_string());
+    let root = match PathBuf::from(&dir).canonicalize() {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("Error: cannot resolve directory '{}': {}", dir, e);
+            std::process::exit(1);
+        }
+    };
+    let codemap_dir = root.join(".codemap");
+
+    let mut graph = match crate::graph::load_graph(&codemap_dir) {
+        Ok(g) => g,
+        Err(e) => {
+            eprintln!("Error: could not load graph from {}/.codemap/: {}", root.display(), e);
+            eprintln!("Run 'codegraph scan {}' first.", root.display());
+            std::process::exit(1);
+        }
+    };
+
+    let stdin = std::io::stdin();
+    let mut reader = stdin.lock();
+    let stdout = std::io::stdout();
+    let mut writer = stdout.lock();
+
+    log_message(&mut writer, &format!("codegraph-lsp ready, project: {}", graph.project.name));
+
+    loop {
+        let message = match read_message(&mut reader) {
+            Some(m) => m,
+            None => break,
+        };
+
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        let id = message.get("id").cloned();
+
+        match method {
+            "initialize" => {
+                let result = json!({
+                    "capabilities": {
+                        "documentSymbolProvider": true,
+                        "workspaceSymbolProvider": true,
+                        "definitionProvider": true,
+                        "textDocumentSync": { "openClose": true, "save": true },
+                    }
+                });
+                respond(&mut writer, id, Ok(result));
+            }
+            "textDocument/documentSymbol" => {
+                let uri = message
+                    .pointer("/params/textDocument/uri")
+                    .and_then(Value::as_str)
+                    .unwrap_or("");
+                let rel_path = rel_path_from_uri(&root, uri);
+                let result = rel_path
+                    .and_then(|p| graph.files.get(&p))
+                    .map(|f| serde_json::to_value(crate::lsp::document_symbols(f)).unwrap_or(Value::Null))
+                    .unwrap_or(Value::Null);
+                respond(&mut writer, id, Ok(result));
+            }
+            "workspace/symbol" => {
+                let query = message.pointer("/params/query").and_then(Value::as_str).unwrap_or("");
+                let result = crate::lsp::workspace_symbols(&graph, query);
+                respond(&mut writer, id, Ok(serde_json::to_value(result).unwrap_or(Value::Null)));
+            }
+            "textDocument/definition" => {
+                let uri = message
+                    .pointer("/params/textDocument/uri")
+                    .and_then(Value::as_str)
+                    .unwrap_or("");
+                let line = message.pointer("/params/position/line").and_then(Value::as_u64).unwrap_or(0) as u32;
+                let character = message.pointer("/params/position/character").and_then(Value::as_u64).unwrap_or(0) as u32;
+
+                let result = rel_path_from_uri(&root, uri)
+                    .and_then(|rel_path| {
+                        let abs_path = root.join(&rel_path);
+                        let content = std::fs::read_to_string(&abs_path).ok()?;
+                        let line_text = content.lines().nth(line as usize)?;
+                        let identifier = crate::lsp::identifier_at(line_text, character)?;
+                        crate::lsp::find_definition(&graph, &rel_path, &identifier)
+                    })
+                    .map(|loc| serde_json::to_value(loc).unwrap_or(Value::Null))
+                    .unwrap_or(Value::Null);
+                respond(&mut writer, id, Ok(result));
+            }
+            "textDocument/didSave" => {
+                let uri = message
+                    .pointer("/params/textDocument/uri")
+                    .and_then(Value::as_str)
+                    .unwrap_or("");
+                if let Some(rel_path) = rel_path_from_uri(&root, uri) {
+                    match rescan_file(&root, &mut graph, &rel_path) {
+                        Ok(()) => log_message(&mut writer, &format!("reindexed {}", rel_path)),
+                        Err(e) => log_message(&mut writer, &format!("failed to reindex {}: {}", rel_path, e)),
+                    }
+                }
+            }
+            "shutdown" => {
+                respond(&mut writer, id, Ok(Value::Null));
+            }
+            "exit" => {
+                break;
+            }
+            _ => {
+                if id.is_some() {
+                    respond(&mut writer, id, Err("method not found".to_string()));
+                }
+            }
+        }
+    }
+}
+
+/// 保存时重新解析单个文件并把结果合并回内存中的图谱（逻辑与 `commands::update` 一致，
+/// 但只针对这一个文件，不重新遍历整个项目）
+fn rescan_file(root: &std::path::Path, graph: &mut crate::graph::CodeGraph, rel_path: &str) -> anyhow::Result<()> {
+    let abs_path = root.join(rel_path.replace('/', std::path::MAIN_SEPARATOR_STR));
+    let content = std::fs::read(&abs_path)?;
+
+    let base_lang = crate::traverser::detect_language(&abs_path)
+        .ok_or_else(|| anyhow::anyhow!("unrecognized language for {}", rel_path))?;
+    let project_files: Vec<PathBuf> = graph
+        .files
+        .keys()
+        .map(|p| root.join(p.replace('/', std::path::MAIN_SEPARATOR_STR)))
+        .collect();
+    let has_cpp = crate::traverser::has_cpp_source_files(&project_files);
+    let lang = crate::traverser::effective_language(&abs_path, base_lang, has_cpp);
+
+    let adapter = crate::languages::get_adapter(lang);
+    let mut ts_parser = tree_sitter::Parser::new();
+    ts_parser.set_language(&adapter.language())?;
+    let tree = ts_parser
+        .parse(&content, None)
+        .ok_or_else(|| anyhow::anyhow!("failed to parse {}", rel_path))?;
+
+    let lang_functions = adapter.extract_functions(&tree, &content);
+    let lang_imports = adapter.extract_imports(&tree, &content);
+    let lang_exports = adapter.extract_exports(&tree, &content);
+    let lang_classes = adapter.extract_classes(&tree, &content);
+    let lang_calls = adapter.extract_calls(&tree, &content);
+    let lines = content.iter().filter(|&&b| b == b'\n').count() as u32 + 1;
+    let (code_lines, comment_lines, blank_lines) = crate::scanner::classify_lines(&tree, &content);
+    let parse_diagnostics = crate::scanner::collect_parse_diagnostics(&tree, &content);
+
+    let functions = crate::scanner::convert_functions(&lang_functions);
+    let (exports, reexports) = crate::scanner::convert_exports(&lang_exports);
+
+    let mut file_entry = crate::graph::FileEntry {
+        language: lang.as_str().to_string(),
+        module: crate::scanner::detect_module_name(&abs_path, root),
+        hash: crate::graph::compute_file_hash(&content),
+        lines,
+        code_lines,
+        comment_lines,
+        blank_lines,
+        functions,
+        classes: crate::scanner::convert_classes(&lang_classes),
+        types: crate::scanner::convert_types(&lang_classes, lang),
+        imports: crate::scanner::convert_imports(
+            &lang_imports,
+            lang,
+            crate::languages::go_lang::read_module_path(root).as_deref(),
+        ),
+        exports,
+        reexports,
+        resolved_reexports: vec![],
+        calls: crate::scanner::convert_calls(&lang_calls),
+        is_entry_point: false,
+        entry_point_reason: None,
+        resolved_imports: vec![],
+        imported_by: vec![],
+        parse_diagnostics,
+    };
+    let manifest_hints = crate::graph::read_manifest_hints(root);
+    let reason = crate::graph::detect_entry_point(&file_entry, &abs_path, &manifest_hints);
+    file_entry.is_entry_point = reason.is_some();
+    file_entry.entry_point_reason = reason.map(|r| r.as_str().to_string());
+
+    let mut updated = std::collections::HashMap::new();
+    updated.insert(rel_path.to_string(), file_entry);
+
+    let module_mapping = crate::module_mapping::ModuleMapping::load(root);
+    crate::differ::merge_graph_update(graph, updated, &[], &module_mapping);
+    crate::graph::save_graph(&root.join(".codemap"), graph)?;
+    Ok(())
+}
+
+/// 把 `file://` URI 转换为相对于项目根目录的路径
+fn rel_path_from_uri(root: &std::path::Path, uri: &str) -> Option<String> {
+    let path = uri.strip_prefix("file://")?;
+    let abs = PathBuf::from(path);
+    let rel = abs.strip_prefix(root).ok()?;
+    Some(rel.to_string_lossy().replace('\\', "/"))
+}
+
+// ── JSON-RPC 帧读写（`Content-Length: N\r\n\r\n<N 字节 JSON>`）──────────────────
+
+fn read_message<R: BufRead>(reader: &mut R) -> Option<Value> {
+    let mut content_length: Option<usize> = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).ok()? == 0 {
+            return None;
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(value) = header.strip_prefix("Content-Length:") {
+            content_length = value.trim().parse().ok();
+        }
+    }
+
+    let len = content_length?;
+    let mut buf = vec![0u8; len];
+    reader.read_exact(&mut buf).ok()?;
+    serde_json::from_slice(&buf).ok()
+}
+
+fn write_message<W: Write>(writer: &mut W, value: &Value) {
+    let body = serde_json::to_string(value).unwrap_or_default();
+    let _ = write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body);
+    let _ = writer.flush();
+}
+
+fn respond<W: Write>(writer: &mut W, id: Option<Value>, result: Result<Value, String>) {
+    let id = id.unwrap_or(Value::Null);
+    let message = match result {
+        Ok(value) => json!({ "jsonrpc": "2.0", "id": id, "result": value }),
+        Err(message) => json!({ "jsonrpc": "2.0", "id": id, "error": { "code": -32601, "message": message } }),
+    };
+    write_message(writer, &message);
+}
+
+fn log_message<W: Write>(writer: &mut W, text: &str) {
+    write_message(writer, &json!({
+        "jsonrpc": "2.0",
+        "method": "window/logMessage",
+        "params": { "type": 3, "message": text },
+    }));
+}
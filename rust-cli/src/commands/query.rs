@@ -8,12 +8,23 @@ pub struct QueryArgs {
     /// Filter by type: function, class, or type
     #[arg(long)]
     pub r#type: Option<String>,
+    /// Symbol name match mode
+    #[arg(long, value_parser = ["exact", "prefix", "substring", "fuzzy"], default_value = "substring")]
+    pub r#match: String,
     /// Project directory
     #[arg(long, default_value = ".")]
     pub dir: String,
     /// Query a module instead of a symbol
     #[arg(long)]
     pub module: bool,
+    /// With --module, show full transitive reachability (forward/reverse closures
+    /// with hop distances) instead of just direct dependsOn/dependedBy
+    #[arg(long)]
+    pub transitive: bool,
+    /// Maximum graph age in seconds before refusing to query (defaults to just
+    /// warning at graph::DEFAULT_STALE_TTL_SECS); prompts a re-scan either way
+    #[arg(long)]
+    pub max_age: Option<u64>,
 }
 
 pub fn run(args: QueryArgs) {
@@ -40,7 +51,24 @@ pub fn run(args: QueryArgs) {
         }
     };
 
-    if args.module {
+    check_graph_staleness(&output_dir, args.max_age);
+
+    if args.module && args.transitive {
+        // 模块传递可达性查询模式
+        match crate::query::query_module_transitive(&graph, &args.symbol) {
+            Some(result) => println!("{}", crate::query::format_transitive_result(&result)),
+            None => {
+                eprintln!("Module '{}' not found.", args.symbol);
+                // 列出可用模块
+                let mut mods: Vec<&str> = graph.modules.keys().map(|s| s.as_str()).collect();
+                mods.sort();
+                if !mods.is_empty() {
+                    eprintln!("Available modules: {}", mods.join(", "));
+                }
+                std::process::exit(1);
+            }
+        }
+    } else if args.module {
         // 模块查询模式
         match crate::query::query_module(&graph, &args.symbol) {
             Some(result) => println!("{}", crate::query::format_module_result(&result)),
@@ -59,8 +87,44 @@ pub fn run(args: QueryArgs) {
         // 符号查询模式
         let opts = crate::query::QueryOptions {
             type_filter: args.r#type.clone(),
+            match_mode: parse_match_mode(&args.r#match),
         };
         let results = crate::query::query_symbol(&graph, &args.symbol, &opts);
         println!("{}", crate::query::format_symbol_results(&results));
     }
 }
+
+/// 把 `--match` 的字符串值转成 [`crate::query::MatchMode`]；clap 的 `value_parser`
+/// 已经把取值限制在这四个之一，所以这里直接兜底到 `Substring`，不会真的走到
+fn parse_match_mode(value: &str) -> crate::query::MatchMode {
+    match value {
+        "exact" => crate::query::MatchMode::Exact,
+        "prefix" => crate::query::MatchMode::Prefix,
+        "fuzzy" => crate::query::MatchMode::Fuzzy,
+        _ => crate::query::MatchMode::Substring,
+    }
+}
+
+/// 根据 meta.json 里的 `lastScanAt` 检查图谱是否过期：没传 `--max-age` 时只警告
+/// （阈值取 `graph::DEFAULT_STALE_TTL_SECS`），传了就把它当硬性上限直接拒绝查询。
+/// 读不到 meta 或时间戳解不出来时静默放行——宁可信任可能过期的图谱，也不要因为
+/// 陈旧检测本身的问题挡住正常查询。
+fn check_graph_staleness(output_dir: &std::path::Path, max_age: Option<u64>) {
+    let Ok(meta) = crate::graph::load_meta(output_dir) else { return };
+    let Some(age) = crate::graph::graph_age_secs(&meta) else { return };
+
+    let ttl = max_age.unwrap_or(crate::graph::DEFAULT_STALE_TTL_SECS);
+    if age <= ttl {
+        return;
+    }
+
+    let msg = format!(
+        "code graph is {}s old (last scanned at {}), exceeding {}s; run 'codegraph scan' or 'codegraph update' to refresh",
+        age, meta.last_scan_at, ttl
+    );
+    if max_age.is_some() {
+        eprintln!("Error: {}", msg);
+        std::process::exit(1);
+    }
+    eprintln!("Warning: {}", msg);
+}
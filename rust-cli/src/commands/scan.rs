@@ -8,6 +8,15 @@ pub struct ScanArgs {
     /// Additional glob patterns to exclude
     #[arg(long, num_args = 1..)]
     pub exclude: Vec<String>,
+    /// Only scan files matching these glob patterns (relative to `dir`)
+    #[arg(long, num_args = 1..)]
+    pub include: Vec<String>,
+    /// Stream newline-delimited JSON progress events to stderr while scanning
+    #[arg(long)]
+    pub progress: bool,
+    /// Exit with a non-zero status if any file has parse diagnostics (ERROR/MISSING nodes)
+    #[arg(long)]
+    pub strict: bool,
 }
 
 pub fn run(args: ScanArgs) {
@@ -23,22 +32,73 @@ pub fn run(args: ScanArgs) {
 
     println!("Scanning {}...", root.display());
 
-    match crate::scanner::scan_and_save(&root, &args.exclude) {
-        Ok(graph) => {
-            let codemap_dir = root.join(".codemap");
-            // 生成 slices/（与 Node.js scan 行为一致）
-            if let Err(e) = crate::slicer::save_slices(&codemap_dir, &graph) {
-                eprintln!("Warning: failed to save slices: {}", e);
+    let codemap_dir = root.join(".codemap");
+    let filter = crate::traverser::ScanFilter::new(args.include.clone(), args.exclude.clone());
+
+    let graph = if args.progress {
+        let mut sink = crate::progress::NdjsonSink::new(std::io::stderr());
+        let result = crate::scanner::scan_project_with_progress(&root, &filter, &mut sink)
+            .and_then(|graph| {
+                crate::graph::save_graph(&codemap_dir, &graph)?;
+                Ok(graph)
+            });
+        match result {
+            Ok(graph) => {
+                if let Err(e) = crate::slicer::save_slices_with_progress(&codemap_dir, &graph, &mut sink) {
+                    eprintln!("Warning: failed to save slices: {}", e);
+                }
+                if let Err(e) = crate::metrics::append_metrics(&codemap_dir.join("metrics.json"), &graph) {
+                    eprintln!("Warning: failed to append metrics: {}", e);
+                }
+                graph
+            }
+            Err(e) => {
+                eprintln!("Scan failed: {}", e);
+                std::process::exit(1);
             }
-            println!("Scan complete.");
-            println!("  Files:     {}", graph.summary.total_files);
-            println!("  Functions: {}", graph.summary.total_functions);
-            println!("  Modules:   {}", graph.summary.modules.join(", "));
-            println!("  Output:    {}", codemap_dir.display());
         }
-        Err(e) => {
-            eprintln!("Scan failed: {}", e);
-            std::process::exit(1);
+    } else {
+        match crate::scanner::scan_and_save_with_filter(&root, &filter) {
+            Ok(graph) => {
+                // 生成 slices/（与 Node.js scan 行为一致）
+                if let Err(e) = crate::slicer::save_slices(&codemap_dir, &graph) {
+                    eprintln!("Warning: failed to save slices: {}", e);
+                }
+                if let Err(e) = crate::metrics::append_metrics(&codemap_dir.join("metrics.json"), &graph) {
+                    eprintln!("Warning: failed to append metrics: {}", e);
+                }
+                graph
+            }
+            Err(e) => {
+                eprintln!("Scan failed: {}", e);
+                std::process::exit(1);
+            }
         }
+    };
+
+    println!("Scan complete.");
+    println!("  Files:     {}", graph.summary.total_files);
+    println!("  Functions: {}", graph.summary.total_functions);
+    println!("  Modules:   {}", graph.summary.modules.join(", "));
+    println!("  Output:    {}", codemap_dir.display());
+
+    print_parse_diagnostics(&graph);
+    if args.strict && graph.summary.total_parse_diagnostics > 0 {
+        std::process::exit(1);
+    }
+}
+
+/// 打印每个存在语法错误的文件及其 `ERROR`/`MISSING` 节点数，格式与 `update` 命令一致
+fn print_parse_diagnostics(graph: &crate::graph::CodeGraph) {
+    let mut paths: Vec<&String> = graph
+        .files
+        .iter()
+        .filter(|(_, f)| !f.parse_diagnostics.is_empty())
+        .map(|(p, _)| p)
+        .collect();
+    paths.sort();
+    for path in paths {
+        let count = graph.files[path].parse_diagnostics.len();
+        println!("⚠ {}: {} parse errors", path, count);
     }
 }
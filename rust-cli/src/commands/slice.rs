@@ -1,5 +1,9 @@
 use clap::Args;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+use notify::{RecursiveMode, Watcher};
 
 #[derive(Args)]
 pub struct SliceArgs {
@@ -8,9 +12,20 @@ pub struct SliceArgs {
     /// Include dependency info in module slice
     #[arg(long)]
     pub with_deps: bool,
+    /// With --with-deps, how many levels of transitive dependencies to include (default: 1, direct only)
+    #[arg(long)]
+    pub max_depth: Option<u32>,
     /// Project directory
     #[arg(long, default_value = ".")]
     pub dir: String,
+    /// Keep running, watch the project tree for changes and re-emit the slice on every
+    /// debounced batch of edits (like deno's `--watch`)
+    #[arg(long)]
+    pub watch: bool,
+    /// With --watch, how long (in ms) to wait for a burst of filesystem events to settle
+    /// before re-scanning and re-emitting
+    #[arg(long, default_value = "300")]
+    pub debounce_ms: u64,
 }
 
 pub fn run(args: SliceArgs) {
@@ -37,52 +52,125 @@ pub fn run(args: SliceArgs) {
         }
     };
 
-    match args.module {
+    if !print_slice(&graph, args.module.as_deref(), args.with_deps, args.max_depth) {
+        std::process::exit(1);
+    }
+
+    if args.watch {
+        watch_and_reslice(&root, args.module.as_deref(), args.with_deps, args.max_depth, args.debounce_ms);
+    }
+}
+
+/// 渲染一次 overview 或 module slice 到 stdout；成功返回 `true`，失败（模块不存在/序列化
+/// 出错）返回 `false`，由调用方决定是退出进程还是（watch 模式下）继续等下一批事件
+fn print_slice(
+    graph: &crate::graph::CodeGraph,
+    module: Option<&str>,
+    with_deps: bool,
+    max_depth: Option<u32>,
+) -> bool {
+    match module {
         None => {
             // 输出 overview
-            let overview = crate::slicer::generate_overview(&graph);
+            let overview = crate::slicer::generate_overview(graph);
             match serde_json::to_string_pretty(&overview) {
-                Ok(json) => println!("{}", json),
+                Ok(json) => {
+                    println!("{}", json);
+                    true
+                }
                 Err(e) => {
                     eprintln!("Serialization error: {}", e);
-                    std::process::exit(1);
+                    false
                 }
             }
         }
         Some(mod_name) => {
-            if args.with_deps {
-                match crate::slicer::get_module_slice_with_deps(&graph, &mod_name) {
+            if with_deps {
+                match crate::slicer::get_module_slice_with_deps(graph, mod_name, max_depth) {
                     Ok(slice) => match serde_json::to_string_pretty(&slice) {
-                        Ok(json) => println!("{}", json),
+                        Ok(json) => {
+                            println!("{}", json);
+                            true
+                        }
                         Err(e) => {
                             eprintln!("Serialization error: {}", e);
-                            std::process::exit(1);
+                            false
                         }
                     },
                     Err(e) => {
                         eprintln!("Error: {}", e);
-                        std::process::exit(1);
+                        false
                     }
                 }
             } else {
-                match graph.modules.get(&mod_name) {
+                match graph.modules.get(mod_name) {
                     Some(mod_data) => {
-                        let slice =
-                            crate::slicer::build_module_slice(&graph, &mod_name, mod_data);
+                        let slice = crate::slicer::build_module_slice(graph, mod_name, mod_data);
                         match serde_json::to_string_pretty(&slice) {
-                            Ok(json) => println!("{}", json),
+                            Ok(json) => {
+                                println!("{}", json);
+                                true
+                            }
                             Err(e) => {
                                 eprintln!("Serialization error: {}", e);
-                                std::process::exit(1);
+                                false
                             }
                         }
                     }
                     None => {
                         eprintln!("Error: module \"{}\" not found in graph.", mod_name);
-                        std::process::exit(1);
+                        false
                     }
                 }
             }
         }
     }
 }
+
+/// 监听 `root`（已在启动时 canonicalize 并固定下来，后续不会因为进程 cwd 变化而失效）下的
+/// 文件系统事件，在 `debounce_ms` 的窗口内吸收突发的连续变更，稳定下来后对受影响文件做
+/// 增量重扫（复用 `codegraph update` 背后的 [`crate::differ::update_graph_incremental`]），
+/// 再重新打印一次 slice/overview。
+fn watch_and_reslice(root: &Path, module: Option<&str>, with_deps: bool, max_depth: Option<u32>, debounce_ms: u64) {
+    let (tx, rx) = channel();
+    let mut watcher = match notify::recommended_watcher(move |res| {
+        let _ = tx.send(res);
+    }) {
+        Ok(w) => w,
+        Err(e) => {
+            eprintln!("Error: failed to start filesystem watcher: {}", e);
+            std::process::exit(1);
+        }
+    };
+    if let Err(e) = watcher.watch(root, RecursiveMode::Recursive) {
+        eprintln!("Error: failed to watch '{}': {}", root.display(), e);
+        std::process::exit(1);
+    }
+
+    eprintln!("Watching {} for changes (Ctrl+C to stop)...", root.display());
+
+    let debounce = Duration::from_millis(debounce_ms);
+    loop {
+        // 阻塞等第一个事件；拿到后在 debounce 窗口内持续吸收后续事件，超时即认为这一批变更已稳定
+        if rx.recv().is_err() {
+            break;
+        }
+        while rx.recv_timeout(debounce).is_ok() {}
+
+        match crate::differ::update_graph_incremental(root, &[]) {
+            Ok((graph, changes)) => {
+                if changes.is_empty() {
+                    continue;
+                }
+                eprintln!(
+                    "Reindexed +{} ~{} -{}",
+                    changes.added.len(),
+                    changes.modified.len(),
+                    changes.removed.len()
+                );
+                print_slice(&graph, module, with_deps, max_depth);
+            }
+            Err(e) => eprintln!("Error: failed to re-scan after change: {}", e),
+        }
+    }
+}
@@ -65,4 +65,12 @@ pub fn run(args: StatusArgs) {
         .map(|m| m.file_hashes.len())
         .unwrap_or(0);
     println!("Tracked files: {tracked}");
+
+    // 模块间循环依赖（强连通分量，见 impact::detect_cycles）
+    if !graph.summary.circular_dependencies.is_empty() {
+        println!("Circular dependencies:");
+        for cycle in &graph.summary.circular_dependencies {
+            println!("  - {}", cycle.join(" -> "));
+        }
+    }
 }
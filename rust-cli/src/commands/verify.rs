@@ -0,0 +1,118 @@
+use clap::Args;
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use crate::verify::{load_manifest, run_fixture, FixtureExpectation, Outcome, VerifyEvent};
+
+#[derive(Args)]
+pub struct VerifyArgs {
+    /// Path to the golden expectations manifest
+    #[arg(long, default_value = "tests/fixtures/expectations.json")]
+    pub manifest: String,
+    /// Only run fixtures whose language or path contains this substring
+    #[arg(long)]
+    pub filter: Option<String>,
+    /// Run only the named fixture(s) (repeatable), skipping the rest of the manifest
+    #[arg(long = "only")]
+    pub only: Vec<String>,
+    /// Emit newline-delimited JSON events instead of a human-readable report
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// 跑一遍 golden fixture 清单，按 deno 测试运行器的风格发 Plan/Wait/Result 事件
+pub fn run(args: VerifyArgs) {
+    let manifest_path = PathBuf::from(&args.manifest);
+    let manifest = match load_manifest(&manifest_path) {
+        Ok(m) => m,
+        Err(e) => {
+            eprintln!("Error: failed to load manifest '{}': {}", manifest_path.display(), e);
+            std::process::exit(1);
+        }
+    };
+
+    let base_dir = resolve_base_dir(&manifest_path, &manifest.fixture_base);
+    let only_set: HashSet<&str> = args.only.iter().map(String::as_str).collect();
+
+    let selected: Vec<&FixtureExpectation> = manifest
+        .fixtures
+        .iter()
+        .filter(|f| matches_filter(f, args.filter.as_deref()))
+        .filter(|f| only_set.is_empty() || only_set.contains(f.name.as_str()))
+        .collect();
+    let filtered = manifest.fixtures.len() - selected.len();
+
+    emit(args.json, &VerifyEvent::Plan { pending: selected.len(), filtered, only: !only_set.is_empty() });
+
+    let mut failed = 0usize;
+    for expectation in &selected {
+        emit(args.json, &VerifyEvent::Wait { name: expectation.name.clone() });
+        let start = Instant::now();
+        let outcome = run_fixture(&base_dir, expectation);
+        let duration_ms = start.elapsed().as_millis() as u64;
+        if matches!(outcome, Outcome::Failed { .. }) {
+            failed += 1;
+        }
+        emit(args.json, &VerifyEvent::Result { name: expectation.name.clone(), duration_ms, outcome });
+    }
+
+    if failed > 0 {
+        eprintln!("{} of {} fixtures failed", failed, selected.len());
+        std::process::exit(1);
+    }
+}
+
+fn matches_filter(expectation: &FixtureExpectation, filter: Option<&str>) -> bool {
+    match filter {
+        None => true,
+        Some(f) => expectation.language.contains(f) || expectation.path.contains(f),
+    }
+}
+
+/// manifest 里的 `fixtureBase` 是绝对路径就直接用，否则当作相对 manifest 文件所在目录解析
+fn resolve_base_dir(manifest_path: &std::path::Path, fixture_base: &str) -> PathBuf {
+    let base = PathBuf::from(fixture_base);
+    if base.is_absolute() {
+        base
+    } else {
+        manifest_path.parent().unwrap_or_else(|| std::path::Path::new(".")).join(base)
+    }
+}
+
+fn emit(json: bool, event: &VerifyEvent) {
+    if json {
+        println!("{}", serde_json::to_string(event).unwrap_or_default());
+    } else {
+        print_human(event);
+    }
+}
+
+fn print_human(event: &VerifyEvent) {
+    match event {
+        VerifyEvent::Plan { pending, filtered, only } => {
+            let suffix = if *filtered > 0 { format!(" ({} filtered out)", filtered) } else { String::new() };
+            println!("running {} fixtures{}{}", pending, suffix, if *only { " (--only)" } else { "" });
+        }
+        VerifyEvent::Wait { name } => {
+            print!("{} ... ", name);
+            let _ = std::io::stdout().flush();
+        }
+        VerifyEvent::Result { duration_ms, outcome, .. } => match outcome {
+            Outcome::Ok => println!("ok ({}ms)", duration_ms),
+            Outcome::Ignored => println!("ignored ({}ms)", duration_ms),
+            Outcome::Failed { diff } => {
+                println!("FAILED ({}ms)", duration_ms);
+                for d in diff {
+                    if !d.missing.is_empty() {
+                        println!("    {}: missing {:?}", d.field, d.missing);
+                    }
+                    if !d.extra.is_empty() {
+                        println!("    {}: extra {:?}", d.field, d.extra);
+                    }
+                }
+            }
+        },
+    }
+}
@@ -0,0 +1,212 @@
+/// 语言适配器抽取覆盖率统计
+///
+/// 每加一种 tree-sitter 语法，适配器的 `extract_*` 只认识当初写下的那几种节点
+/// kind；语法升级或新写的适配器很容易漏掉一整类声明却没有任何报错。这里反过来
+/// 走一遍语法树，把每种语言里"看起来像声明"的节点 kind（函数、类/结构体/枚举/
+/// trait/接口、命名空间、import/include）收集起来，跟 `extract_functions`/
+/// `extract_classes`/`extract_imports` 的实际产出按行号/文本做比对，报出每种
+/// kind 的命中率，以及具体漏掉了哪些字节范围——给适配器补齐覆盖面一个可检查的
+/// 量化指标。
+use crate::languages::{get_adapter, node_text};
+use crate::traverser::Language;
+use serde::Serialize;
+
+/// 一种被统计的声明节点该去跟哪类 `extract_*` 产出比对
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeclCategory {
+    Function,
+    Class,
+    Import,
+}
+
+/// 识别一个节点是不是某种语言里的"声明"，是的话分类到对应的 [`DeclCategory`]
+/// 并给出人类可读的 kind 标签（用于分组统计，不必是 tree-sitter 的原始 kind 名）。
+/// 不认识的节点种类返回 `None`，调用方直接跳过，不计入统计。
+fn classify(lang: Language, node: tree_sitter::Node) -> Option<(DeclCategory, &'static str)> {
+    match lang {
+        Language::Rust => match node.kind() {
+            "function_item" => Some((DeclCategory::Function, "function")),
+            "struct_item" => Some((DeclCategory::Class, "struct")),
+            "enum_item" => Some((DeclCategory::Class, "enum")),
+            "trait_item" => Some((DeclCategory::Class, "trait")),
+            "mod_item" => Some((DeclCategory::Class, "namespace")),
+            "use_declaration" => Some((DeclCategory::Import, "import")),
+            _ => None,
+        },
+        Language::Go => match node.kind() {
+            "function_declaration" => Some((DeclCategory::Function, "function")),
+            "type_spec" => match node.child_by_field_name("type")?.kind() {
+                "struct_type" => Some((DeclCategory::Class, "struct")),
+                "interface_type" => Some((DeclCategory::Class, "interface")),
+                _ => None,
+            },
+            "import_spec" => Some((DeclCategory::Import, "import")),
+            _ => None,
+        },
+        Language::Java => match node.kind() {
+            "method_declaration" | "constructor_declaration" => Some((DeclCategory::Function, "function")),
+            "class_declaration" => Some((DeclCategory::Class, "class")),
+            "interface_declaration" => Some((DeclCategory::Class, "interface")),
+            "enum_declaration" => Some((DeclCategory::Class, "enum")),
+            "import_declaration" => Some((DeclCategory::Import, "import")),
+            _ => None,
+        },
+        Language::Python => match node.kind() {
+            "function_definition" => Some((DeclCategory::Function, "function")),
+            "class_definition" => Some((DeclCategory::Class, "class")),
+            "import_statement" | "import_from_statement" => Some((DeclCategory::Import, "import")),
+            _ => None,
+        },
+        Language::C | Language::Cpp => match node.kind() {
+            "function_definition" => Some((DeclCategory::Function, "function")),
+            "struct_specifier" => Some((DeclCategory::Class, "struct")),
+            "class_specifier" => Some((DeclCategory::Class, "class")),
+            "enum_specifier" => Some((DeclCategory::Class, "enum")),
+            "preproc_include" => Some((DeclCategory::Import, "include")),
+            _ => None,
+        },
+        Language::TypeScript | Language::JavaScript => match node.kind() {
+            "function_declaration" => Some((DeclCategory::Function, "function")),
+            "class_declaration" => Some((DeclCategory::Class, "class")),
+            "interface_declaration" => Some((DeclCategory::Class, "interface")),
+            "type_alias_declaration" => Some((DeclCategory::Class, "type")),
+            "import_statement" => Some((DeclCategory::Import, "import")),
+            _ => None,
+        },
+    }
+}
+
+/// 一种 kind 标签下的命中/总数统计，如 `{ label: "struct", hits: 3, total: 4 }`
+#[derive(Debug, Clone, Serialize)]
+pub struct KindCoverage {
+    pub label: String,
+    pub hits: u32,
+    pub total: u32,
+}
+
+/// 一处被适配器跳过的声明：标签 + 字节范围 + 起始行号，方便定位到源码
+#[derive(Debug, Clone, Serialize)]
+pub struct MissedDecl {
+    pub label: String,
+    #[serde(rename = "startByte")]
+    pub start_byte: usize,
+    #[serde(rename = "endByte")]
+    pub end_byte: usize,
+    #[serde(rename = "startLine")]
+    pub start_line: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CoverageReport {
+    pub language: String,
+    #[serde(rename = "byKind")]
+    pub by_kind: Vec<KindCoverage>,
+    pub missed: Vec<MissedDecl>,
+}
+
+/// 对一份已解析的语法树做一遍覆盖率统计：跑该语言适配器的三个 `extract_*`，
+/// 再走一遍语法树给每个"声明样子"的节点打命中/未命中标记
+///
+/// 命中判定：函数/类按 `start_line` 是否出现在对应 `extract_*` 的结果里；
+/// import/include 没有行号字段，退化成看该节点的原始文本里是否包含某条
+/// `ImportInfo::source`（它本就是从同一个节点的子串里取出来的，包含关系足够可靠）。
+pub fn compute_coverage(lang: Language, tree: &tree_sitter::Tree, source: &[u8]) -> CoverageReport {
+    let adapter = get_adapter(lang);
+    let functions = adapter.extract_functions(tree, source);
+    let classes = adapter.extract_classes(tree, source);
+    let imports = adapter.extract_imports(tree, source);
+
+    let mut by_kind: Vec<KindCoverage> = Vec::new();
+    let mut missed = Vec::new();
+
+    crate::languages::walk_nodes(tree.root_node(), &mut |node| {
+        let Some((category, label)) = classify(lang, node) else {
+            return;
+        };
+        let start_line = node.start_position().row + 1;
+        let covered = match category {
+            DeclCategory::Function => functions.iter().any(|f| f.start_line == start_line),
+            DeclCategory::Class => classes.iter().any(|c| c.start_line == start_line),
+            DeclCategory::Import => {
+                let text = node_text(node, source);
+                imports.iter().any(|i| !i.source.is_empty() && text.contains(i.source.as_str()))
+            }
+        };
+
+        match by_kind.iter_mut().find(|k| k.label == label) {
+            Some(k) => {
+                k.total += 1;
+                if covered {
+                    k.hits += 1;
+                }
+            }
+            None => by_kind.push(KindCoverage {
+                label: label.to_string(),
+                hits: if covered { 1 } else { 0 },
+                total: 1,
+            }),
+        }
+
+        if !covered {
+            missed.push(MissedDecl {
+                label: label.to_string(),
+                start_byte: node.start_byte(),
+                end_byte: node.end_byte(),
+                start_line,
+            });
+        }
+    });
+
+    CoverageReport { language: lang.as_str().to_string(), by_kind, missed }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(lang: Language, source: &str) -> tree_sitter::Tree {
+        let adapter = get_adapter(lang);
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&adapter.language()).unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    #[test]
+    fn rust_mod_item_is_reported_as_unparsed_namespace() {
+        let src = "mod utils {\n    pub fn helper() {}\n}\n";
+        let tree = parse(Language::Rust, src);
+        let report = compute_coverage(Language::Rust, &tree, src.as_bytes());
+
+        let namespace = report.by_kind.iter().find(|k| k.label == "namespace").unwrap();
+        assert_eq!(namespace.total, 1);
+        assert_eq!(namespace.hits, 0);
+        assert!(report.missed.iter().any(|m| m.label == "namespace"));
+
+        let function = report.by_kind.iter().find(|k| k.label == "function").unwrap();
+        assert_eq!(function.hits, 1);
+    }
+
+    #[test]
+    fn go_interface_type_spec_is_covered_by_class_extraction() {
+        let src = "package main\n\ntype Greeter interface {\n    Greet() string\n}\n";
+        let tree = parse(Language::Go, src);
+        let report = compute_coverage(Language::Go, &tree, src.as_bytes());
+
+        let interface = report.by_kind.iter().find(|k| k.label == "interface").unwrap();
+        assert_eq!(interface.hits, 1);
+        assert_eq!(interface.total, 1);
+        assert!(report.missed.is_empty());
+    }
+
+    #[test]
+    fn python_nested_function_is_missed_since_only_top_level_is_extracted() {
+        let src = "def outer():\n    def inner():\n        pass\n    return inner\n";
+        let tree = parse(Language::Python, src);
+        let report = compute_coverage(Language::Python, &tree, src.as_bytes());
+
+        let function = report.by_kind.iter().find(|k| k.label == "function").unwrap();
+        assert_eq!(function.total, 2);
+        assert_eq!(function.hits, 1);
+        assert_eq!(report.missed.iter().filter(|m| m.label == "function").count(), 1);
+    }
+}
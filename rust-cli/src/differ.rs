@@ -1,6 +1,9 @@
 use crate::graph::{CodeGraph, FileEntry, ModuleEntry};
+use crate::module_mapping::ModuleMapping;
 use crate::path_utils::{posix_dirname, posix_normalize, strip_extension};
 use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::process::Command;
 
 // ── 变更检测结果 ──────────────────────────────────────────────────────────────
 
@@ -58,9 +61,171 @@ pub fn detect_changed_files(
     ChangeSet { added, modified, removed, unchanged }
 }
 
+/// 读取 `repo_root` 当前 HEAD 提交哈希；不在 git 仓库中或 git 不可用时返回 None
+pub fn git_head_commit(repo_root: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("rev-parse")
+        .arg("HEAD")
+        .current_dir(repo_root)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if hash.is_empty() {
+        None
+    } else {
+        Some(hash)
+    }
+}
+
+/// 基于 `git diff --name-status` 检测两个提交之间变更的文件
+///
+/// 相比 `detect_changed_files` 逐文件重新哈希，这里直接复用 git 自身已经算好的差异，
+/// 在大仓库上避免重复读盘哈希。状态字母含义：`A` 新增、`M`/`T` 修改、`D` 删除，
+/// `R###`/`C###`（重命名/拷贝，git 输出为 `old_path\tnew_path`）则将旧路径记为删除、新路径记为新增。
+/// 路径本身就是相对于 `repo_root` 的（通过 `current_dir` 固定工作目录），直接对应
+/// `graph.files` 使用的 key，无需再做归一化。
+pub fn detect_changed_files_from_git(
+    old_commit: &str,
+    new_commit: &str,
+    repo_root: &Path,
+) -> anyhow::Result<ChangeSet> {
+    let output = Command::new("git")
+        .arg("diff")
+        .arg("--name-status")
+        .arg(format!("{}..{}", old_commit, new_commit))
+        .current_dir(repo_root)
+        .output()
+        .map_err(|e| anyhow::anyhow!("failed to run git: {}", e))?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "git diff --name-status {}..{} failed: {}",
+            old_commit,
+            new_commit,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut added = Vec::new();
+    let mut modified = Vec::new();
+    let mut removed = Vec::new();
+
+    for line in stdout.lines() {
+        let mut parts = line.split('\t');
+        let status = match parts.next() {
+            Some(s) if !s.is_empty() => s,
+            _ => continue,
+        };
+        let status_letter = status.chars().next().unwrap_or(' ');
+
+        match status_letter {
+            'A' => {
+                if let Some(path) = parts.next() {
+                    added.push(path.to_string());
+                }
+            }
+            'M' | 'T' => {
+                if let Some(path) = parts.next() {
+                    modified.push(path.to_string());
+                }
+            }
+            'D' => {
+                if let Some(path) = parts.next() {
+                    removed.push(path.to_string());
+                }
+            }
+            'R' | 'C' => {
+                let old_path = parts.next();
+                let new_path = parts.next();
+                if let (Some(old_path), Some(new_path)) = (old_path, new_path) {
+                    removed.push(old_path.to_string());
+                    added.push(new_path.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    added.sort();
+    modified.sort();
+    removed.sort();
+
+    Ok(ChangeSet {
+        added,
+        modified,
+        removed,
+        unchanged: Vec::new(),
+    })
+}
+
+/// 基于 `(size, mtime_nanos)` 的快速路径文件变更分类，跳过对未变更文件的重新哈希
+///
+/// 分类规则（`old_hashes` 只用来判断一个路径是否是"已知但尚无 stat 记录"的旧文件，
+/// 例如从只存哈希的旧版 meta.json 升级上来）：
+/// - `new_stat.mtime_nanos == scan_timestamp_nanos`：文件 mtime 恰好落在本次扫描时刻，
+///   同一秒/同一次扫描内的时间戳精度不足以分辨谁先谁后，归入 `unsure`，调用方必须
+///   重新哈希后再与旧哈希比对来确定真伪
+/// - 否则若 `old_stats` 里没有这个路径：
+///   - `old_hashes` 里也没有 → 真正的新文件，归入 `ChangeSet.added`
+///   - `old_hashes` 里有 → 升级路径上的旧文件缺 stat 记录，无法只凭 stat 下结论，
+///     同样归入 `unsure`
+/// - 否则若 size/mtime 与 `old_stats` 记录一致 → 未变更，不出现在返回值的任何集合里
+///   （mtime 为 0，即取不到 mtime 的退化值时除外，这种文件归入 `modified`）
+/// - 否则（size 或 mtime 任一不同）→ 归入 `ChangeSet.modified`；调用方仍需重新哈希
+///   以获得新的内容哈希存回 `fileHashes`，但不必为了"判断是否变了"这件事再读一遍内容
+///
+/// `removed` 以 `old_hashes` 的 key 集合为准（而非 `old_stats`），与 `detect_changed_files`
+/// 对"已知文件"的定义保持一致。
+pub fn detect_changed_files_stat(
+    old_stats: &HashMap<String, crate::graph::FileStat>,
+    new_stats: &HashMap<String, crate::graph::FileStat>,
+    old_hashes: &HashMap<String, String>,
+    scan_timestamp_nanos: i64,
+) -> (ChangeSet, Vec<String>) {
+    let mut added = Vec::new();
+    let mut modified = Vec::new();
+    let mut removed = Vec::new();
+    let mut unsure = Vec::new();
+
+    for (path, new_stat) in new_stats {
+        if new_stat.mtime_nanos == scan_timestamp_nanos {
+            unsure.push(path.clone());
+            continue;
+        }
+
+        match old_stats.get(path) {
+            None if old_hashes.contains_key(path) => unsure.push(path.clone()),
+            None => added.push(path.clone()),
+            Some(old_stat) if old_stat == new_stat && new_stat.mtime_nanos != 0 => {}
+            Some(_) => modified.push(path.clone()),
+        }
+    }
+
+    for path in old_hashes.keys() {
+        if !new_stats.contains_key(path) {
+            removed.push(path.clone());
+        }
+    }
+
+    added.sort();
+    modified.sort();
+    removed.sort();
+    unsure.sort();
+
+    (
+        ChangeSet { added, modified, removed, unchanged: Vec::new() },
+        unsure,
+    )
+}
+
 /// 将变更合并到现有图谱（原地修改）
 ///
 /// - 删除 `removed_files` 中的文件条目及其模块引用
+/// - 按 `module_mapping` 重新指派 `updated_files` 中文件的所属模块（覆盖目录推断的默认值）
 /// - 添加/更新 `updated_files` 中的文件条目
 /// - 清理空模块
 /// - 重新计算 summary 和模块依赖
@@ -68,6 +233,7 @@ pub fn merge_graph_update(
     graph: &mut CodeGraph,
     updated_files: HashMap<String, FileEntry>,
     removed_files: &[String],
+    module_mapping: &ModuleMapping,
 ) {
     // Step 1: 删除已移除的文件
     for file_path in removed_files {
@@ -79,7 +245,13 @@ pub fn merge_graph_update(
     }
 
     // Step 2: 添加/更新变更文件
-    for (file_path, file_data) in updated_files {
+    for (file_path, mut file_data) in updated_files {
+        // 应用用户的模块映射覆盖（在写回 graph.files/graph.modules 之前，
+        // 这样后续的 recalculate_summary/rebuild_dependencies 都基于最终模块边界运行）
+        if let Some(overridden) = module_mapping.resolve(&file_path) {
+            file_data.module = overridden;
+        }
+
         // 若文件已存在且模块发生变化，从旧模块移除
         if let Some(existing) = graph.files.get(&file_path) {
             if existing.module != file_data.module {
@@ -95,6 +267,9 @@ pub fn merge_graph_update(
             files: vec![],
             depends_on: vec![],
             depended_by: vec![],
+            code_lines: 0,
+            comment_lines: 0,
+            blank_lines: 0,
         });
 
         // 将文件加入模块（避免重复）
@@ -113,6 +288,279 @@ pub fn merge_graph_update(
     // Step 4: 重新计算 summary 和依赖
     recalculate_summary(graph);
     rebuild_dependencies(graph);
+    // 依赖重建完之后再查环——dependsOn/dependedBy 在这一步之前还是上一次扫描的旧值
+    graph.summary.circular_dependencies = crate::impact::detect_cycles(graph);
+    // 文件级导入解析同样要对合并后的全量 graph.files 重跑：哪怕这次没改动的文件，
+    // 也可能因为别的文件新增/删除而多出或失去一条 resolved_imports/imported_by 边
+    let alias_map = crate::scanner::load_alias_map(std::path::Path::new(&graph.project.root));
+    crate::scanner::resolve_file_imports(graph, &alias_map);
+    // 同理，C/C++ include 诊断也要对合并后的全量文件重新生成
+    let root_path = std::path::PathBuf::from(&graph.project.root);
+    let c_search_paths = crate::scanner::default_c_search_paths(&root_path);
+    graph.include_diagnostics = crate::scanner::resolve_c_includes(graph, &root_path, &c_search_paths);
+    // 调用边同样要在合并后的全量文件集合上重新判断 resolved
+    crate::scanner::resolve_calls(graph);
+}
+
+/// 增量更新：加载 `.codemap/` 下已有的 graph.json + meta.json，用 mtime+size 做快速
+/// 预筛选、哈希作为最终判定，只重新解析真正变化的文件，合并进图谱并落盘
+/// （graph.json / meta.json fileHashes+fileStats / slices/ / sources/）
+///
+/// 真正变化的文件不再无条件全量解析：`.codemap/sources/` 里存着上一次的源码快照
+/// （见 `source_cache`），有快照就走 `parse_cache::reparse_incremental` 的
+/// `Tree::edit` + `Parser::parse(new, Some(&old_tree))` 路径，tree-sitter 据此
+/// 复用编辑区间之外未变化的子树加速解析。但 `changed_ranges` 只是"结构性重解析过的
+/// 子树"，不是"行号发生变化的每一行"——文件靠前处的一次编辑会让后面所有声明的行号
+/// 整体偏移，而那些声明并不落在任何一个 changed range 里。所以函数/类/调用边一律
+/// 对重解析出的完整新树整份重新抽取，不按 changed_ranges 去拼接新旧数据。
+/// 没有快照（新文件）时照常全量解析。
+///
+/// 这是 `codegraph update` 命令的核心逻辑，抽成库函数以便非 CLI 调用方复用；
+/// 调用方负责处理 I/O 错误的展示方式，这里只返回 `anyhow::Result`。
+/// 返回更新后的 `CodeGraph` 以及这次检测到的 `ChangeSet`（`is_empty()` 为 true 时
+/// 图谱未发生任何改动，调用方可以据此跳过落盘后的提示）。
+pub fn update_graph_incremental(
+    root: &Path,
+    exclude: &[String],
+) -> anyhow::Result<(CodeGraph, ChangeSet)> {
+    let codemap_dir = root.join(".codemap");
+    let mut graph = crate::graph::load_graph(&codemap_dir)?;
+
+    // 遍历磁盘当前文件；先只读 (size, mtime) 做快速路径分类，
+    // 只有进了 changed/unsure 集合的文件才需要读内容重新哈希
+    let files = crate::traverser::traverse_files(root, exclude);
+    let has_cpp = crate::traverser::has_cpp_source_files(&files);
+
+    let mut new_stats: HashMap<String, crate::graph::FileStat> = HashMap::new();
+    let mut rel_to_abs: HashMap<String, std::path::PathBuf> = HashMap::new();
+
+    for abs_path in &files {
+        if crate::traverser::detect_language(abs_path).is_none() {
+            continue;
+        }
+        let metadata = match std::fs::metadata(abs_path) {
+            Ok(m) => m,
+            Err(_) => continue,
+        };
+
+        let rel_path = abs_path
+            .strip_prefix(root)
+            .unwrap_or(abs_path)
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        new_stats.insert(rel_path.clone(), crate::graph::file_stat_from_metadata(&metadata));
+        rel_to_abs.insert(rel_path, abs_path.clone());
+    }
+
+    // 从 meta.fileHashes/fileStats 读取旧状态；若 meta.json 不存在或无 fileHashes，
+    // 回退到从 graph.files 提取
+    let (old_hashes, old_stats): (HashMap<String, String>, HashMap<String, crate::graph::FileStat>) =
+        match crate::graph::load_meta(&codemap_dir) {
+            Ok(meta) if !meta.file_hashes.is_empty() => {
+                (meta.file_hashes.into_iter().collect(), meta.file_stats.into_iter().collect())
+            }
+            _ => (
+                graph.files.iter().map(|(p, f)| (p.clone(), f.hash.clone())).collect(),
+                HashMap::new(),
+            ),
+        };
+
+    // 快速路径：凭 stat 就能确定"没变"的文件直接跳过哈希；只对 changed/unsure 读内容哈希
+    let scan_timestamp_nanos = crate::graph::now_nanos();
+    let (stat_changes, unsure) =
+        detect_changed_files_stat(&old_stats, &new_stats, &old_hashes, scan_timestamp_nanos);
+
+    let mut new_hashes: HashMap<String, String> = HashMap::new();
+    let mut file_contents: HashMap<String, Vec<u8>> = HashMap::new();
+    let to_hash = stat_changes
+        .added
+        .iter()
+        .chain(stat_changes.modified.iter())
+        .chain(unsure.iter());
+
+    for rel_path in to_hash {
+        let Some(abs_path) = rel_to_abs.get(rel_path) else {
+            continue;
+        };
+        let content = match std::fs::read(abs_path) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+        let hash = crate::graph::compute_file_hash(&content);
+        new_hashes.insert(rel_path.clone(), hash);
+        file_contents.insert(rel_path.clone(), content);
+    }
+    // 未变更的文件沿用旧哈希，保证 new_hashes 仍覆盖磁盘上的全部已知文件
+    for rel_path in new_stats.keys() {
+        if let Some(old_hash) = old_hashes.get(rel_path) {
+            new_hashes.entry(rel_path.clone()).or_insert_with(|| old_hash.clone());
+        }
+    }
+
+    // 检测变更：优先使用 git diff（避免对未变更文件重新哈希），
+    // 仅当新旧 commit 均可用且不同时才尝试；否则回退到 stat 快速路径 + 哈希表对比
+    let current_commit = git_head_commit(root);
+    let changes = match (&graph.commit_hash, &current_commit) {
+        (Some(old_commit), Some(new_commit)) if old_commit != new_commit => {
+            detect_changed_files_from_git(old_commit, new_commit, root)
+                .unwrap_or_else(|_| detect_changed_files(&old_hashes, &new_hashes))
+        }
+        _ => detect_changed_files(&old_hashes, &new_hashes),
+    };
+    graph.commit_hash = current_commit.or_else(|| graph.commit_hash.clone());
+
+    // `changes` 才是下面真正要应用到图上的变更集合，但它可能来自 git diff —— 一套
+    // 完全独立于上面 `stat_changes`/`unsure`（只看 mtime+size）的数据源。git 认定
+    // 某个文件 added/modified，不代表它也进了 `to_hash`：粗粒度 mtime、签出后
+    // size 和 mtime 恰好跟记录的 `FileStat` 对上等情况都会让 stat 快速路径判定
+    // "没变"，`file_contents`/`new_hashes` 里就没有这个文件的新内容/新哈希。这里把
+    // `changes.added`/`changes.modified` 里还没读到内容的文件补读一遍，保证下面
+    // 重新抽取用的数据跟 `changes` 实际覆盖的文件集合完全一致，不会把过期的
+    // `FileEntry` 当成"没变"留在图里、还顺带把 `commit_hash` 往前推过这个文件
+    // 真正的变更，导致它以后也追不回来
+    for rel_path in changes.added.iter().chain(changes.modified.iter()) {
+        if file_contents.contains_key(rel_path) {
+            continue;
+        }
+        let Some(abs_path) = rel_to_abs.get(rel_path) else {
+            continue;
+        };
+        let Ok(content) = std::fs::read(abs_path) else {
+            continue;
+        };
+        let hash = crate::graph::compute_file_hash(&content);
+        new_hashes.insert(rel_path.clone(), hash);
+        file_contents.insert(rel_path.clone(), content);
+    }
+
+    if changes.is_empty() {
+        return Ok((graph, changes));
+    }
+
+    // 解析变更文件（新增 + 修改），哈希相同的文件不会落到这里，原 FileEntry 保持不变
+    let mut updated_files: HashMap<String, FileEntry> = HashMap::new();
+    let go_module_path = crate::languages::go_lang::read_module_path(root);
+    let manifest_hints = crate::graph::read_manifest_hints(root);
+
+    for rel_path in changes.added.iter().chain(changes.modified.iter()) {
+        let Some(content) = file_contents.get(rel_path) else {
+            continue;
+        };
+
+        let abs_path = root.join(rel_path.replace('/', std::path::MAIN_SEPARATOR_STR));
+        let Some(base_lang) = crate::traverser::detect_language(&abs_path) else {
+            continue;
+        };
+        let lang = crate::traverser::effective_language(&abs_path, base_lang, has_cpp);
+
+        let adapter = crate::languages::get_adapter(lang);
+
+        // 增量重解析：有上一次的源码快照才能走 tree-sitter 的 Tree::edit 路径，
+        // 没有（新文件，或者 .codemap/sources/ 还没来得及为它建过基线）就照常全量解析。
+        let prev_source = crate::source_cache::load(&codemap_dir, rel_path);
+        // `Tree::edit` + 增量 parse 仍然用来加速解析（复用未受影响的子树），但返回的
+        // `changed_ranges` 不再用于合并函数/类/调用边——见下方的说明
+        let (tree, _changed_ranges) = match prev_source
+            .as_deref()
+            .and_then(|old| crate::parse_cache::reparse_incremental(lang, old, content).ok())
+        {
+            Some(result) => result,
+            None => {
+                let Some(tree) = full_parse(adapter.as_ref(), content) else {
+                    continue;
+                };
+                (tree, Vec::new())
+            }
+        };
+
+        let lang_functions = adapter.extract_functions(&tree, content);
+        let lang_imports = adapter.extract_imports(&tree, content);
+        let lang_exports = adapter.extract_exports(&tree, content);
+        let lang_classes = adapter.extract_classes(&tree, content);
+        let lang_calls = adapter.extract_calls(&tree, content);
+        let lines = content.iter().filter(|&&b| b == b'\n').count() as u32 + 1;
+        let (code_lines, comment_lines, blank_lines) = crate::scanner::classify_lines(&tree, content);
+        // 重解析本来就是拿到一棵完整的新树，诊断直接对这棵树整份重算即可
+        let parse_diagnostics = crate::scanner::collect_parse_diagnostics(&tree, content);
+
+        // 函数/类/调用边同样整份替换，不按 `changed_ranges` 去拼接新旧数据：
+        // `changed_ranges` 只覆盖 tree-sitter 认为结构性重解析过的子树，不是"行号
+        // 发生变化的每一行"——文件中更早处的一次编辑会让后面所有声明的行号整体偏移，
+        // 而那些声明并不落在任何一个 changed range 里。`lang_functions`/`lang_classes`/
+        // `lang_calls` 已经是对完整新树重新抽取的结果，行号天然正确，跟 imports/exports
+        // 的处理方式一致，直接整份覆盖
+        let functions = crate::scanner::convert_functions(&lang_functions);
+        let classes = crate::scanner::convert_classes(&lang_classes);
+        let calls = crate::scanner::convert_calls(&lang_calls);
+        let types = crate::scanner::convert_types(&lang_classes, lang);
+        let imports = crate::scanner::convert_imports(&lang_imports, lang, go_module_path.as_deref());
+        let (exports, reexports) = crate::scanner::convert_exports(&lang_exports);
+
+        let module_name = crate::scanner::detect_module_name(&abs_path, root);
+        let hash = new_hashes[rel_path].clone();
+
+        let mut file_entry = FileEntry {
+            language: lang.as_str().to_string(),
+            module: module_name,
+            hash,
+            lines,
+            code_lines,
+            comment_lines,
+            blank_lines,
+            functions,
+            classes,
+            types,
+            imports,
+            exports,
+            reexports,
+            resolved_reexports: vec![],
+            calls,
+            is_entry_point: false,
+            entry_point_reason: None,
+            resolved_imports: vec![],
+            imported_by: vec![],
+            parse_diagnostics,
+        };
+        let reason = crate::graph::detect_entry_point(&file_entry, &abs_path, &manifest_hints);
+        file_entry.is_entry_point = reason.is_some();
+        file_entry.entry_point_reason = reason.map(|r| r.as_str().to_string());
+
+        updated_files.insert(rel_path.clone(), file_entry);
+    }
+
+    // 把这一批变更文件的源码存成快照，供下一次 update 当增量重解析的基线
+    for (rel_path, content) in &file_contents {
+        let _ = crate::source_cache::save(&codemap_dir, rel_path, content);
+    }
+    for rel_path in &changes.removed {
+        crate::source_cache::remove(&codemap_dir, rel_path);
+    }
+
+    // 合并变更到图谱（按项目根下 codemap.modules 里的覆盖规则重新指派模块），
+    // 这一步内部会重新计算 summary 和跨模块依赖
+    let module_mapping = ModuleMapping::load(root);
+    merge_graph_update(&mut graph, updated_files, &changes.removed, &module_mapping);
+
+    graph.scanned_at = crate::graph::chrono_now();
+    crate::graph::save_graph(&codemap_dir, &graph)?;
+
+    let file_stats: std::collections::BTreeMap<String, crate::graph::FileStat> =
+        new_stats.into_iter().collect();
+    crate::graph::save_file_stats(&codemap_dir, &file_stats)?;
+
+    crate::slicer::save_slices_incremental(&codemap_dir, &graph)?;
+    crate::metrics::append_metrics(&codemap_dir.join("metrics.json"), &graph)?;
+
+    Ok((graph, changes))
+}
+
+/// 对 `content` 做一次全量解析：没有可用的旧源码快照时的兜底路径
+/// （新文件，或者上一次 update 之前从未给它建立过 source_cache 基线）
+fn full_parse(adapter: &dyn crate::languages::LanguageAdapter, content: &[u8]) -> Option<tree_sitter::Tree> {
+    let mut parser = tree_sitter::Parser::new();
+    parser.set_language(&adapter.language()).ok()?;
+    parser.parse(content, None)
 }
 
 // ── 内部函数 ──────────────────────────────────────────────────────────────────
@@ -122,19 +570,32 @@ fn recalculate_summary(graph: &mut CodeGraph) {
     let mut total_files = 0u32;
     let mut total_functions = 0u32;
     let mut total_classes = 0u32;
+    let mut total_code_lines = 0u32;
+    let mut total_comment_lines = 0u32;
+    let mut total_blank_lines = 0u32;
+    let mut total_parse_diagnostics = 0u32;
     let mut languages: HashMap<String, u32> = HashMap::new();
 
     for file_data in graph.files.values() {
         total_files += 1;
         total_functions += file_data.functions.len() as u32;
         total_classes += file_data.classes.len() as u32;
+        total_code_lines += file_data.code_lines;
+        total_comment_lines += file_data.comment_lines;
+        total_blank_lines += file_data.blank_lines;
+        total_parse_diagnostics += file_data.parse_diagnostics.len() as u32;
         *languages.entry(file_data.language.clone()).or_insert(0) += 1;
     }
 
     graph.summary.total_files = total_files;
     graph.summary.total_functions = total_functions;
     graph.summary.total_classes = total_classes;
+    graph.summary.total_code_lines = total_code_lines;
+    graph.summary.total_comment_lines = total_comment_lines;
+    graph.summary.total_blank_lines = total_blank_lines;
+    graph.summary.total_parse_diagnostics = total_parse_diagnostics;
     graph.summary.languages = languages.clone();
+    crate::graph::recalculate_module_line_stats(graph);
 
     let mut mod_list: Vec<String> = graph.modules.keys().cloned().collect();
     mod_list.sort();
@@ -152,13 +613,234 @@ fn recalculate_summary(graph: &mut CodeGraph) {
     let mut lang_list: Vec<String> = languages.into_keys().collect();
     lang_list.sort();
     graph.config.languages = lang_list;
+
+    graph.summary.complexity_hotspots =
+        crate::graph::top_complexity_hotspots(&graph.files, crate::graph::COMPLEXITY_HOTSPOT_LIMIT);
+}
+
+// ── 按语言解析 import ─────────────────────────────────────────────────────────
+//
+// 不同语言的 import 语法差异很大（JS/TS 的相对路径、Python 的点号计数、
+// Rust 的 crate/self/super 前缀、Go 的模块路径、Java 的包名+类名），`ImportResolver`
+// 把“原始 import → 候选文件路径”这一步按语言拆开，候选路径最终都交给同一份
+// `path_lookup` 做查找；查不到就当作外部依赖，不记录边。
+
+/// 把一条 import 解析为候选相对路径（posix 风格、不含扩展名）
+pub(crate) trait ImportResolver {
+    /// `importer_dir`：发起 import 的文件所在目录；`raw_import`：`ImportInfo.source` 原始值
+    fn resolve_candidates(&self, importer_dir: &str, raw_import: &str) -> Vec<String>;
+
+    /// 候选路径若命中一个目录，还应尝试的“目录即模块”文件名（如 `index`、`__init__`）
+    fn index_stems(&self) -> &'static [&'static str];
+
+    /// 大多数语言的 import 目标完全由 `ImportInfo.source` 决定，默认转发给
+    /// `resolve_candidates`；Java 的 import 被拆成了包名（`source`）和类名
+    /// （`symbols[0]`）两部分，两者拼起来才是文件路径，因此单独覆盖这个入口
+    fn resolve_import_candidates(&self, importer_dir: &str, imp: &crate::graph::ImportInfo) -> Vec<String> {
+        self.resolve_candidates(importer_dir, &imp.source)
+    }
+}
+
+struct JsImportResolver;
+
+impl ImportResolver for JsImportResolver {
+    fn resolve_candidates(&self, importer_dir: &str, raw_import: &str) -> Vec<String> {
+        if !raw_import.starts_with('.') {
+            return Vec::new();
+        }
+        vec![posix_normalize(&format!("{}/{}", importer_dir, raw_import))]
+    }
+
+    fn index_stems(&self) -> &'static [&'static str] {
+        &["index"]
+    }
+}
+
+struct PythonImportResolver;
+
+impl ImportResolver for PythonImportResolver {
+    fn resolve_candidates(&self, importer_dir: &str, raw_import: &str) -> Vec<String> {
+        let dots = raw_import.chars().take_while(|&c| c == '.').count();
+        let rest_path = raw_import[dots..].replace('.', "/");
+
+        if dots > 0 {
+            // 相对 import：一个 `.` 表示当前包（import 所在目录），
+            // 之后每多一个 `.` 再往上一级目录
+            let mut base = importer_dir.to_string();
+            for _ in 0..dots.saturating_sub(1) {
+                base = posix_dirname(&base).to_string();
+            }
+            let candidate = if rest_path.is_empty() {
+                base
+            } else {
+                format!("{}/{}", base, rest_path)
+            };
+            vec![posix_normalize(&candidate)]
+        } else if rest_path.is_empty() {
+            Vec::new()
+        } else {
+            // 绝对 import（`import pkg.mod` / `from pkg.mod import x`）：
+            // 按点号拆出的包路径直接相对项目根匹配
+            vec![rest_path]
+        }
+    }
+
+    fn index_stems(&self) -> &'static [&'static str] {
+        &["__init__"]
+    }
+}
+
+struct RustImportResolver;
+
+impl ImportResolver for RustImportResolver {
+    fn resolve_candidates(&self, importer_dir: &str, raw_import: &str) -> Vec<String> {
+        let mut segments: Vec<&str> = raw_import.split("::").filter(|s| !s.is_empty()).collect();
+        if segments.is_empty() {
+            return Vec::new();
+        }
+
+        let base = match segments[0] {
+            "crate" => {
+                segments.remove(0);
+                "src".to_string()
+            }
+            "self" => {
+                segments.remove(0);
+                importer_dir.to_string()
+            }
+            "super" => {
+                let mut dir = importer_dir.to_string();
+                while segments.first() == Some(&"super") {
+                    segments.remove(0);
+                    dir = posix_dirname(&dir).to_string();
+                }
+                dir
+            }
+            // 其余一律视为外部 crate（`use serde::...` 等），不产生候选路径
+            _ => return Vec::new(),
+        };
+
+        let tail = segments.join("/");
+        let candidate = if tail.is_empty() { base } else { format!("{}/{}", base, tail) };
+        vec![posix_normalize(&candidate)]
+    }
+
+    fn index_stems(&self) -> &'static [&'static str] {
+        &["mod"]
+    }
+}
+
+/// Go 的 import 路径是模块内的绝对路径（如 `example.com/app/internal/util`）。
+/// 有 `go.mod` 的 module 路径时精确剥离前缀，只把 Internal 来源的 import 当作候选
+/// （Stdlib/External 直接跳过，不再误当本地文件猜测）；没有 module 路径可用时，退回
+/// 旧的启发式猜测（完整路径 + 去掉第一段，通常是仓库/组织名）
+struct GoImportResolver {
+    module_path: Option<String>,
+}
+
+impl ImportResolver for GoImportResolver {
+    fn resolve_candidates(&self, _importer_dir: &str, raw_import: &str) -> Vec<String> {
+        if raw_import.is_empty() {
+            return Vec::new();
+        }
+        match &self.module_path {
+            Some(module_path) => {
+                use crate::languages::go_lang::{classify_go_import, GoImportOrigin};
+                if classify_go_import(raw_import, Some(module_path)) != GoImportOrigin::Internal {
+                    return Vec::new();
+                }
+                let stripped = raw_import
+                    .strip_prefix(module_path.as_str())
+                    .map(|rest| rest.trim_start_matches('/'))
+                    .unwrap_or(raw_import);
+                if stripped.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![stripped.to_string()]
+                }
+            }
+            None => {
+                let mut candidates = vec![raw_import.to_string()];
+                if let Some(idx) = raw_import.find('/') {
+                    candidates.push(raw_import[idx + 1..].to_string());
+                }
+                candidates
+            }
+        }
+    }
+
+    fn index_stems(&self) -> &'static [&'static str] {
+        &[]
+    }
+}
+
+/// Java 的 import 在 `languages::java::JavaAdapter` 里被拆成了包名（`ImportInfo.source`，
+/// 如 `com.acme.util`）和类名（`ImportInfo.symbols[0]`，如 `Helper`）两段；按约定把
+/// 包名的点号换成斜杠、拼上类名就是该类源文件的相对路径（不含扩展名），即
+/// `com/acme/util/Helper`。通配符 import（`import com.acme.util.*;`）没有具体类名
+/// 可拼，不产生候选路径。
+struct JavaImportResolver;
+
+impl ImportResolver for JavaImportResolver {
+    fn resolve_candidates(&self, _importer_dir: &str, _raw_import: &str) -> Vec<String> {
+        // 只靠 source 定位不到具体类文件，真正的逻辑在 resolve_import_candidates 里
+        Vec::new()
+    }
+
+    fn resolve_import_candidates(&self, _importer_dir: &str, imp: &crate::graph::ImportInfo) -> Vec<String> {
+        let Some(class_name) = imp.symbols.first() else {
+            return Vec::new();
+        };
+        if class_name == "*" {
+            return Vec::new();
+        }
+        let candidate = if imp.source.is_empty() {
+            class_name.clone()
+        } else {
+            format!("{}/{}", imp.source.replace('.', "/"), class_name)
+        };
+        vec![candidate]
+    }
+
+    fn index_stems(&self) -> &'static [&'static str] {
+        &[]
+    }
+}
+
+pub(crate) fn resolver_for_language(language: &str, go_module_path: Option<&str>) -> Option<Box<dyn ImportResolver>> {
+    match language {
+        "typescript" | "javascript" => Some(Box::new(JsImportResolver)),
+        "python" => Some(Box::new(PythonImportResolver)),
+        "rust" => Some(Box::new(RustImportResolver)),
+        "go" => Some(Box::new(GoImportResolver { module_path: go_module_path.map(str::to_string) })),
+        "java" => Some(Box::new(JavaImportResolver)),
+        _ => None,
+    }
+}
+
+/// 在 `path_lookup` 里查找一个候选路径，命中目录时依次尝试该语言的 index 文件名
+pub(crate) fn lookup_module(
+    path_lookup: &HashMap<String, String>,
+    candidate: &str,
+    index_stems: &[&str],
+) -> Option<String> {
+    if let Some(m) = path_lookup.get(candidate) {
+        return Some(m.clone());
+    }
+    index_stems
+        .iter()
+        .find_map(|stem| path_lookup.get(&format!("{}/{}", candidate, stem)))
+        .cloned()
 }
 
 /// 从文件级 import 数据重建模块级 dependsOn / dependedBy
 ///
-/// 注意：当前仅解析以 `.` 开头的相对路径导入（JS/TS），
-/// 非 JS/TS 语言的 import 被标记为 external 而跳过。
+/// 按 `FileEntry.language` 派发到对应的 `ImportResolver`（JS/TS、Python、Rust、Go、Java），
+/// 没有解析器的语言（如 C/C++）保持跳过，和之前一致。
 fn rebuild_dependencies(graph: &mut CodeGraph) {
+    let go_module_path =
+        crate::languages::go_lang::read_module_path(std::path::Path::new(&graph.project.root));
+
     // 构建 relPath → moduleName 查找表
     let mut path_lookup: HashMap<String, String> = HashMap::new();
     for (rel_path, file_data) in &graph.files {
@@ -178,22 +860,19 @@ fn rebuild_dependencies(graph: &mut CodeGraph) {
     }
 
     for (rel_path, file_data) in &graph.files {
+        let Some(resolver) = resolver_for_language(&file_data.language, go_module_path.as_deref())
+        else {
+            continue;
+        };
         let module_name = &file_data.module;
         let norm_path = rel_path.replace('\\', "/");
+        let importer_dir = posix_dirname(&norm_path);
 
         for imp in &file_data.imports {
-            if imp.is_external || !imp.source.starts_with('.') {
-                continue;
-            }
-
-            // 解析相对 import 路径（posix 风格）
-            let importer_dir = posix_dirname(&norm_path);
-            let resolved = posix_normalize(&format!("{}/{}", importer_dir, imp.source));
-
-            let target = path_lookup
-                .get(&resolved)
-                .or_else(|| path_lookup.get(&format!("{}/index", resolved)))
-                .cloned();
+            let target = resolver
+                .resolve_import_candidates(importer_dir, imp)
+                .iter()
+                .find_map(|candidate| lookup_module(&path_lookup, candidate, resolver.index_stems()));
 
             if let Some(target_mod) = target {
                 if &target_mod != module_name {
@@ -235,12 +914,22 @@ mod tests {
             module: module.to_string(),
             hash: "sha256:aabbccdd11223344".to_string(),
             lines: 10,
+            code_lines: 8,
+            comment_lines: 0,
+            blank_lines: 2,
             functions: vec![],
             classes: vec![],
             types: vec![],
             imports: vec![],
             exports: vec![],
+            reexports: vec![],
+            resolved_reexports: vec![],
+            calls: vec![],
             is_entry_point: false,
+            entry_point_reason: None,
+            resolved_imports: vec![],
+            imported_by: vec![],
+            parse_diagnostics: vec![],
         }
     }
 
@@ -312,6 +1001,94 @@ mod tests {
         assert_eq!(cs.added, vec!["a.ts", "m.ts", "z.ts"]);
     }
 
+    // ── detect_changed_files_from_git ─────────────────────────────────────────
+
+    #[test]
+    fn test_git_diff_unknown_commit_errors() {
+        let dir = std::env::temp_dir();
+        let result = detect_changed_files_from_git("deadbeef1", "deadbeef2", &dir);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_git_diff_against_self_empty() {
+        // 仓库自身 HEAD..HEAD 应该没有任何变更
+        let repo_root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        if let Ok(cs) = detect_changed_files_from_git("HEAD", "HEAD", repo_root) {
+            assert!(cs.is_empty());
+        }
+    }
+
+    // ── detect_changed_files_stat ─────────────────────────────────────────────
+
+    fn stat(size: u64, mtime_nanos: i64) -> crate::graph::FileStat {
+        crate::graph::FileStat { size, mtime_nanos }
+    }
+
+    #[test]
+    fn test_stat_unchanged_file_is_not_classified() {
+        let old: HashMap<String, crate::graph::FileStat> = [("a.ts".to_string(), stat(10, 100))].into();
+        let new = old.clone();
+        let hashes: HashMap<String, String> = [("a.ts".to_string(), "h1".to_string())].into();
+        let (cs, unsure) = detect_changed_files_stat(&old, &new, &hashes, 9999);
+        assert!(cs.added.is_empty());
+        assert!(cs.modified.is_empty());
+        assert!(cs.unchanged.is_empty()); // 未变更的文件不出现在任何集合里
+        assert!(unsure.is_empty());
+    }
+
+    #[test]
+    fn test_stat_size_or_mtime_differing_is_changed() {
+        let old: HashMap<String, crate::graph::FileStat> = [("a.ts".to_string(), stat(10, 100))].into();
+        let new: HashMap<String, crate::graph::FileStat> = [("a.ts".to_string(), stat(20, 200))].into();
+        let hashes: HashMap<String, String> = [("a.ts".to_string(), "h1".to_string())].into();
+        let (cs, unsure) = detect_changed_files_stat(&old, &new, &hashes, 9999);
+        assert_eq!(cs.modified, vec!["a.ts"]);
+        assert!(unsure.is_empty());
+    }
+
+    #[test]
+    fn test_stat_new_file_not_in_old_hashes_is_added() {
+        let old: HashMap<String, crate::graph::FileStat> = HashMap::new();
+        let new: HashMap<String, crate::graph::FileStat> = [("a.ts".to_string(), stat(10, 100))].into();
+        let hashes: HashMap<String, String> = HashMap::new();
+        let (cs, unsure) = detect_changed_files_stat(&old, &new, &hashes, 9999);
+        assert_eq!(cs.added, vec!["a.ts"]);
+        assert!(unsure.is_empty());
+    }
+
+    #[test]
+    fn test_stat_missing_prior_stat_but_known_hash_is_unsure() {
+        // 从只存哈希的旧 meta.json 升级上来：没有 stat 记录，但文件本身不是新的
+        let old: HashMap<String, crate::graph::FileStat> = HashMap::new();
+        let new: HashMap<String, crate::graph::FileStat> = [("a.ts".to_string(), stat(10, 100))].into();
+        let hashes: HashMap<String, String> = [("a.ts".to_string(), "h1".to_string())].into();
+        let (cs, unsure) = detect_changed_files_stat(&old, &new, &hashes, 9999);
+        assert!(cs.added.is_empty());
+        assert!(cs.modified.is_empty());
+        assert_eq!(unsure, vec!["a.ts"]);
+    }
+
+    #[test]
+    fn test_stat_mtime_matching_scan_timestamp_is_unsure_even_if_otherwise_unchanged() {
+        let old: HashMap<String, crate::graph::FileStat> = [("a.ts".to_string(), stat(10, 100))].into();
+        let new: HashMap<String, crate::graph::FileStat> = [("a.ts".to_string(), stat(10, 100))].into();
+        let hashes: HashMap<String, String> = [("a.ts".to_string(), "h1".to_string())].into();
+        let (cs, unsure) = detect_changed_files_stat(&old, &new, &hashes, 100);
+        assert!(cs.added.is_empty());
+        assert!(cs.modified.is_empty());
+        assert_eq!(unsure, vec!["a.ts"]);
+    }
+
+    #[test]
+    fn test_stat_removed_file_uses_old_hashes_as_known_set() {
+        let old: HashMap<String, crate::graph::FileStat> = [("a.ts".to_string(), stat(10, 100))].into();
+        let new: HashMap<String, crate::graph::FileStat> = HashMap::new();
+        let hashes: HashMap<String, String> = [("a.ts".to_string(), "h1".to_string())].into();
+        let (cs, _unsure) = detect_changed_files_stat(&old, &new, &hashes, 9999);
+        assert_eq!(cs.removed, vec!["a.ts"]);
+    }
+
     // ── merge_graph_update ────────────────────────────────────────────────────
 
     #[test]
@@ -324,11 +1101,14 @@ mod tests {
                 files: vec!["src/a.ts".to_string()],
                 depends_on: vec![],
                 depended_by: vec![],
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
             },
         );
         graph.summary.total_files = 1;
 
-        merge_graph_update(&mut graph, HashMap::new(), &["src/a.ts".to_string()]);
+        merge_graph_update(&mut graph, HashMap::new(), &["src/a.ts".to_string()], &ModuleMapping::default());
 
         assert!(!graph.files.contains_key("src/a.ts"));
         // 空模块应被清理
@@ -343,7 +1123,7 @@ mod tests {
         let mut updated = HashMap::new();
         updated.insert("src/b.ts".to_string(), make_file_entry("utils"));
 
-        merge_graph_update(&mut graph, updated, &[]);
+        merge_graph_update(&mut graph, updated, &[], &ModuleMapping::default());
 
         assert!(graph.files.contains_key("src/b.ts"));
         assert!(graph.modules.contains_key("utils"));
@@ -361,12 +1141,15 @@ mod tests {
                 files: vec!["src/a.ts".to_string()],
                 depends_on: vec![],
                 depended_by: vec![],
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
             },
         );
 
         let mut updated = HashMap::new();
         updated.insert("src/a.ts".to_string(), make_file_entry("new_mod"));
-        merge_graph_update(&mut graph, updated, &[]);
+        merge_graph_update(&mut graph, updated, &[], &ModuleMapping::default());
 
         // 旧模块应被清理（空了）
         assert!(!graph.modules.contains_key("old_mod"));
@@ -375,6 +1158,51 @@ mod tests {
         assert_eq!(graph.files["src/a.ts"].module, "new_mod");
     }
 
+    #[test]
+    fn test_merge_graph_update_refreshes_circular_dependencies() {
+        use crate::graph::ImportInfo;
+
+        let mut graph = create_empty_graph("test", "/tmp/test");
+
+        let mut a_file = make_file_entry("a");
+        a_file.imports = vec![ImportInfo {
+            source: "../b/index".to_string(),
+            symbols: vec![],
+            is_external: false,
+            dynamic: false,
+        }];
+        graph.files.insert("src/a/index.ts".to_string(), a_file);
+        graph.modules.insert(
+            "a".to_string(),
+            ModuleEntry {
+                files: vec!["src/a/index.ts".to_string()],
+                depends_on: vec![],
+                depended_by: vec![],
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
+            },
+        );
+
+        let mut b_file = make_file_entry("b");
+        b_file.imports = vec![ImportInfo {
+            source: "../a/index".to_string(),
+            symbols: vec![],
+            is_external: false,
+            dynamic: false,
+        }];
+
+        let mut updated = HashMap::new();
+        updated.insert("src/b/index.ts".to_string(), b_file);
+        merge_graph_update(&mut graph, updated, &[], &ModuleMapping::default());
+
+        // a ↔ b 互相依赖，应被识别为一个循环并写回 summary
+        assert_eq!(graph.summary.circular_dependencies.len(), 1);
+        let cycle = &graph.summary.circular_dependencies[0];
+        assert!(cycle.contains(&"a".to_string()));
+        assert!(cycle.contains(&"b".to_string()));
+    }
+
     #[test]
     fn test_rebuild_dependencies() {
         use crate::graph::ImportInfo;
@@ -386,6 +1214,7 @@ mod tests {
             source: "../utils/helper".to_string(),
             symbols: vec![],
             is_external: false,
+            dynamic: false,
         }];
         graph.files.insert("src/auth/login.ts".to_string(), auth_file);
 
@@ -398,6 +1227,9 @@ mod tests {
                 files: vec!["src/auth/login.ts".to_string()],
                 depends_on: vec![],
                 depended_by: vec![],
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
             },
         );
         graph.modules.insert(
@@ -406,6 +1238,9 @@ mod tests {
                 files: vec!["src/utils/helper.ts".to_string()],
                 depends_on: vec![],
                 depended_by: vec![],
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
             },
         );
 
@@ -414,4 +1249,262 @@ mod tests {
         assert_eq!(graph.modules["auth"].depends_on, vec!["utils"]);
         assert_eq!(graph.modules["utils"].depended_by, vec!["auth"]);
     }
+
+    // ── rebuild_dependencies：多语言 import 解析 ──────────────────────────────
+
+    fn make_graph_with_modules(files: Vec<(&str, FileEntry)>) -> CodeGraph {
+        let mut graph = create_empty_graph("test", "/tmp/test");
+        for (path, file) in files {
+            let module = file.module.clone();
+            graph.files.insert(path.to_string(), file);
+            graph.modules.entry(module).or_insert_with(|| ModuleEntry {
+                files: vec![path.to_string()],
+                depends_on: vec![],
+                depended_by: vec![],
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
+            });
+        }
+        graph
+    }
+
+    #[test]
+    fn test_rebuild_dependencies_python_relative_import() {
+        use crate::graph::ImportInfo;
+
+        let mut app_file = make_file_entry("app");
+        app_file.language = "python".to_string();
+        app_file.imports = vec![ImportInfo {
+            source: "..pkg.mod".to_string(),
+            symbols: vec![],
+            is_external: true,
+            dynamic: false,
+        }];
+        let mut pkg_mod_file = make_file_entry("pkg_mod");
+        pkg_mod_file.language = "python".to_string();
+
+        let mut graph = make_graph_with_modules(vec![
+            ("app/sub/main.py", app_file),
+            ("app/pkg/mod.py", pkg_mod_file),
+        ]);
+        rebuild_dependencies(&mut graph);
+
+        assert_eq!(graph.modules["app"].depends_on, vec!["pkg_mod"]);
+        assert_eq!(graph.modules["pkg_mod"].depended_by, vec!["app"]);
+    }
+
+    #[test]
+    fn test_rebuild_dependencies_python_absolute_import() {
+        use crate::graph::ImportInfo;
+
+        let mut app_file = make_file_entry("app");
+        app_file.language = "python".to_string();
+        app_file.imports =
+            vec![ImportInfo { source: "pkg.mod".to_string(), symbols: vec![], is_external: true, dynamic: false }];
+        let mut pkg_mod_file = make_file_entry("pkg_mod");
+        pkg_mod_file.language = "python".to_string();
+
+        let mut graph =
+            make_graph_with_modules(vec![("src/app.py", app_file), ("pkg/mod.py", pkg_mod_file)]);
+        rebuild_dependencies(&mut graph);
+
+        assert_eq!(graph.modules["app"].depends_on, vec!["pkg_mod"]);
+    }
+
+    #[test]
+    fn test_rebuild_dependencies_python_package_init() {
+        use crate::graph::ImportInfo;
+
+        let mut app_file = make_file_entry("app");
+        app_file.language = "python".to_string();
+        app_file.imports =
+            vec![ImportInfo { source: "pkg".to_string(), symbols: vec![], is_external: true, dynamic: false }];
+        let mut pkg_file = make_file_entry("pkg");
+        pkg_file.language = "python".to_string();
+
+        let mut graph =
+            make_graph_with_modules(vec![("src/app.py", app_file), ("pkg/__init__.py", pkg_file)]);
+        rebuild_dependencies(&mut graph);
+
+        assert_eq!(graph.modules["app"].depends_on, vec!["pkg"]);
+    }
+
+    #[test]
+    fn test_rebuild_dependencies_rust_crate_path() {
+        use crate::graph::ImportInfo;
+
+        let mut main_file = make_file_entry("main");
+        main_file.language = "rust".to_string();
+        main_file.imports = vec![ImportInfo {
+            source: "crate::utils::helper".to_string(),
+            symbols: vec!["format_thing".to_string()],
+            is_external: true,
+            dynamic: false,
+        }];
+        let mut helper_file = make_file_entry("utils_helper");
+        helper_file.language = "rust".to_string();
+
+        let mut graph = make_graph_with_modules(vec![
+            ("src/main.rs", main_file),
+            ("src/utils/helper.rs", helper_file),
+        ]);
+        rebuild_dependencies(&mut graph);
+
+        assert_eq!(graph.modules["main"].depends_on, vec!["utils_helper"]);
+    }
+
+    #[test]
+    fn test_rebuild_dependencies_rust_mod_rs_and_external_crate() {
+        use crate::graph::ImportInfo;
+
+        let mut main_file = make_file_entry("main");
+        main_file.language = "rust".to_string();
+        main_file.imports = vec![
+            ImportInfo {
+                source: "crate::auth".to_string(),
+                symbols: vec!["login".to_string()],
+                is_external: true,
+                dynamic: false,
+            },
+            ImportInfo {
+                source: "serde::Serialize".to_string(),
+                symbols: vec!["Serialize".to_string()],
+                is_external: true,
+                dynamic: false,
+            },
+        ];
+        let mut auth_file = make_file_entry("auth");
+        auth_file.language = "rust".to_string();
+
+        let mut graph = make_graph_with_modules(vec![
+            ("src/main.rs", main_file),
+            ("src/auth/mod.rs", auth_file),
+        ]);
+        rebuild_dependencies(&mut graph);
+
+        assert_eq!(graph.modules["main"].depends_on, vec!["auth"]);
+    }
+
+    #[test]
+    fn test_rebuild_dependencies_go_module_path() {
+        use crate::graph::ImportInfo;
+
+        let mut main_file = make_file_entry("main");
+        main_file.language = "go".to_string();
+        main_file.imports = vec![ImportInfo {
+            source: "example.com/app/internal/util".to_string(),
+            symbols: vec!["util".to_string()],
+            is_external: true,
+            dynamic: false,
+        }];
+        let mut util_file = make_file_entry("util");
+        util_file.language = "go".to_string();
+
+        let mut graph = make_graph_with_modules(vec![
+            ("cmd/main.go", main_file),
+            ("app/internal/util.go", util_file),
+        ]);
+        rebuild_dependencies(&mut graph);
+
+        assert_eq!(graph.modules["main"].depends_on, vec!["util"]);
+    }
+
+    #[test]
+    fn test_go_import_resolver_internal_strips_module_prefix() {
+        let resolver = GoImportResolver { module_path: Some("example.com/app".to_string()) };
+        assert_eq!(
+            resolver.resolve_candidates("cmd", "example.com/app/internal/util"),
+            vec!["internal/util".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_go_import_resolver_skips_stdlib_and_external_with_known_module_path() {
+        let resolver = GoImportResolver { module_path: Some("example.com/app".to_string()) };
+        assert!(resolver.resolve_candidates("cmd", "fmt").is_empty());
+        assert!(resolver.resolve_candidates("cmd", "github.com/foo/bar").is_empty());
+    }
+
+    #[test]
+    fn test_go_import_resolver_without_module_path_uses_heuristic_fallback() {
+        let resolver = GoImportResolver { module_path: None };
+        assert_eq!(
+            resolver.resolve_candidates("cmd", "example.com/app/internal/util"),
+            vec![
+                "example.com/app/internal/util".to_string(),
+                "app/internal/util".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rebuild_dependencies_java_import() {
+        use crate::graph::ImportInfo;
+
+        let mut app_file = make_file_entry("app");
+        app_file.language = "java".to_string();
+        app_file.imports = vec![ImportInfo {
+            source: "com.acme.util".to_string(),
+            symbols: vec!["Helper".to_string()],
+            is_external: true,
+            dynamic: false,
+        }];
+        let mut helper_file = make_file_entry("util");
+        helper_file.language = "java".to_string();
+
+        let mut graph = make_graph_with_modules(vec![
+            ("src/app/Main.java", app_file),
+            ("com/acme/util/Helper.java", helper_file),
+        ]);
+        rebuild_dependencies(&mut graph);
+
+        assert_eq!(graph.modules["app"].depends_on, vec!["util"]);
+    }
+
+    #[test]
+    fn test_java_import_resolver_joins_package_and_class() {
+        use crate::graph::ImportInfo;
+
+        let resolver = JavaImportResolver;
+        let imp = ImportInfo {
+            source: "com.acme.util".to_string(),
+            symbols: vec!["Helper".to_string()],
+            is_external: true,
+            dynamic: false,
+        };
+        assert_eq!(
+            resolver.resolve_import_candidates("src/app", &imp),
+            vec!["com/acme/util/Helper".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_java_import_resolver_skips_wildcard_import() {
+        use crate::graph::ImportInfo;
+
+        let resolver = JavaImportResolver;
+        let imp = ImportInfo {
+            source: "com.acme.util".to_string(),
+            symbols: vec!["*".to_string()],
+            is_external: true,
+            dynamic: false,
+        };
+        assert!(resolver.resolve_import_candidates("src/app", &imp).is_empty());
+    }
+
+    #[test]
+    fn test_rebuild_dependencies_unresolvable_import_is_skipped() {
+        use crate::graph::ImportInfo;
+
+        let mut app_file = make_file_entry("app");
+        app_file.language = "python".to_string();
+        app_file.imports =
+            vec![ImportInfo { source: "numpy".to_string(), symbols: vec![], is_external: true, dynamic: false }];
+
+        let mut graph = make_graph_with_modules(vec![("src/app.py", app_file)]);
+        rebuild_dependencies(&mut graph);
+
+        assert!(graph.modules["app"].depends_on.is_empty());
+    }
 }
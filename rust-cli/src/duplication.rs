@@ -0,0 +1,348 @@
+//! 跨语言克隆检测（结构子树哈希）
+//!
+//! 思路借鉴 clippy 的 `SpanlessHash`/`SpanlessEq`：对每个已提取的 `FunctionInfo`，
+//! 在它对应的 tree-sitter 子树上递归构造一棵"结构外形"（[`Shape`]）——分支节点记
+//! `kind()`，叶子节点按 identifier/type_identifier/字面量这类节点一视同仁替换成该
+//! kind 的规范 token（这样改名字、换常量值的复制粘贴函数仍然落在同一个外形里），
+//! 其余叶子节点保留 `kind()`。[`Shape`] 本身可哈希、可比较——既用来做分桶（相同哈希
+//! 才可能是克隆），也直接用 `==` 充当 spanless_eq（结构外形相等，自然要求 kind 序列
+//! 和子节点数都一致）。
+//!
+//! `CodeGraph` 只保存 `extract_functions` 的抽取结果，不保存 AST，所以这里需要
+//! `root_dir` 按文件重新解析（做法与 `commands::lsp::rescan_file` 按需重解析单文件
+//! 一致），再按 `FunctionInfo` 的起止行在树里定位函数节点。只依赖 `walk_nodes` 已经
+//! 用到的通用遍历，因此对 C/C++/Python/Go/Java/Rust/TS/JS 所有适配器一视同仁。
+
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+use tree_sitter::Node;
+
+use crate::graph::CodeGraph;
+
+/// 克隆匹配的严格度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchMode {
+    /// 叶子节点（identifier/type_identifier/字面量）也要求文本完全相同
+    Exact,
+    /// 叶子节点按 kind 统一替换成规范 token 再比较——重命名变量、更换字面量值的克隆也算数
+    Normalized,
+}
+
+#[derive(Debug, Clone)]
+pub struct DetectOptions {
+    /// 结构外形的节点数低于这个阈值的函数不参与检测，过滤掉单行 getter 这类琐碎匹配
+    pub min_node_count: usize,
+    pub mode: MatchMode,
+}
+
+impl Default for DetectOptions {
+    fn default() -> Self {
+        Self { min_node_count: 20, mode: MatchMode::Normalized }
+    }
+}
+
+/// 一个函数在克隆类里的定位信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionFingerprint {
+    pub file: String,
+    pub function: String,
+    pub start_line: u32,
+    pub end_line: u32,
+}
+
+/// 一组疑似克隆的函数（成员数 >= 2）
+#[derive(Debug, Clone)]
+pub struct CloneClass {
+    pub members: Vec<FunctionFingerprint>,
+    /// 克隆体的结构规模（外形节点数），同一克隆类内的成员该值相等，供调用方按体量过滤展示
+    pub node_count: usize,
+}
+
+/// 把一个 tree-sitter 子树折叠成的结构外形：叶子记规范化后的 token，分支记 kind 和子节点外形
+///
+/// 派生的 `Hash`/`Eq` 天然就是 SpanlessHash/SpanlessEq：外形相等要求 kind 序列和子节点数
+/// （arity）都递归一致，且叶子 token 也相等。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Shape {
+    Leaf(String),
+    Branch(String, Vec<Shape>),
+}
+
+impl Shape {
+    fn node_count(&self) -> usize {
+        match self {
+            Shape::Leaf(_) => 1,
+            Shape::Branch(_, children) => 1 + children.iter().map(Shape::node_count).sum::<usize>(),
+        }
+    }
+
+    fn structural_hash(&self) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// identifier/type_identifier/字面量一类的叶子节点：文本内容对"是不是复制粘贴"没有意义，
+/// 按名字而非按值参与比较。用 kind 名称的宽松包含判断而不是为每种语言单独列举，覆盖所有适配器。
+fn is_text_leaf_kind(kind: &str) -> bool {
+    let k = kind.to_ascii_lowercase();
+    k.contains("identifier") || k.contains("literal")
+}
+
+fn build_shape(node: Node, source: &[u8], mode: MatchMode) -> Shape {
+    let kind = node.kind();
+    if node.child_count() == 0 {
+        let token = if mode == MatchMode::Normalized && is_text_leaf_kind(kind) {
+            format!("<{}>", kind)
+        } else if is_text_leaf_kind(kind) {
+            format!("{}:{}", kind, crate::languages::node_text(node, source))
+        } else {
+            kind.to_string()
+        };
+        return Shape::Leaf(token);
+    }
+    let mut cursor = node.walk();
+    let children = node.children(&mut cursor).map(|c| build_shape(c, source, mode)).collect();
+    Shape::Branch(kind.to_string(), children)
+}
+
+/// 在树里找到和 `FunctionInfo` 的 `[start_line, end_line]`（1-based，闭区间）精确对应的节点
+///
+/// 多个节点可能共享同一行区间（比如单行函数体里的表达式），优先挑 kind 里带
+/// function/method/constructor 字样的节点；找不到这样的候选就退而求其次，取第一个完全匹配的节点。
+fn find_function_node(root: Node, start_line: u32, end_line: u32) -> Option<Node> {
+    let mut fallback = None;
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        let node_start = node.start_position().row as u32 + 1;
+        let node_end = node.end_position().row as u32 + 1;
+        if node_start == start_line && node_end == end_line {
+            let kind = node.kind().to_ascii_lowercase();
+            if kind.contains("function") || kind.contains("method") || kind.contains("constructor") {
+                return Some(node);
+            }
+            if fallback.is_none() {
+                fallback = Some(node);
+            }
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            stack.push(child);
+        }
+    }
+    fallback
+}
+
+struct Candidate {
+    fingerprint: FunctionFingerprint,
+    shape: Shape,
+}
+
+/// 对单个文件重新解析、定位每个已知函数的子树，算出它的结构外形
+///
+/// `project_has_cpp` 由调用方对整个项目的文件列表算好一次再传进来，不能在这里
+/// 对 `[abs_path]` 这种单元素切片重新调用 `has_cpp_source_files`——那只会在这一个
+/// 文件自己带 `.cpp`/`.cc` 等后缀时才返回 true，导致 `.h`/`.c` 文件在克隆检测里被
+/// 判成 C，却在 `scan_project` 里因为看到了全量文件列表而判成 C++，两边 `effective_language`
+/// 对不上
+fn fingerprint_file(
+    rel_path: &str,
+    root_dir: &Path,
+    functions: &[crate::graph::FunctionInfo],
+    mode: MatchMode,
+    project_has_cpp: bool,
+) -> Vec<Candidate> {
+    let abs_path = root_dir.join(rel_path.replace('/', std::path::MAIN_SEPARATOR_STR));
+    let Ok(content) = std::fs::read(&abs_path) else { return Vec::new() };
+    let Some(base_lang) = crate::traverser::detect_language(&abs_path) else { return Vec::new() };
+    let lang = crate::traverser::effective_language(&abs_path, base_lang, project_has_cpp);
+    let adapter = crate::languages::get_adapter(lang);
+
+    let mut parser = tree_sitter::Parser::new();
+    if parser.set_language(&adapter.language()).is_err() {
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(&content, None) else { return Vec::new() };
+
+    functions
+        .iter()
+        .filter_map(|f| {
+            let node = find_function_node(tree.root_node(), f.start_line, f.end_line)?;
+            let shape = build_shape(node, &content, mode);
+            Some(Candidate {
+                fingerprint: FunctionFingerprint {
+                    file: rel_path.to_string(),
+                    function: f.name.clone(),
+                    start_line: f.start_line,
+                    end_line: f.end_line,
+                },
+                shape,
+            })
+        })
+        .collect()
+}
+
+/// 扫描整张图里的所有函数，找出结构相同（按 `options.mode` 定义）的克隆类
+///
+/// 输出按克隆类大小降序、类内按 `(file, start_line)` 排序，保证同一张图多次调用结果一致。
+pub fn detect_clones(graph: &CodeGraph, root_dir: &Path, options: &DetectOptions) -> Vec<CloneClass> {
+    let mut rel_paths: Vec<&String> = graph.files.keys().collect();
+    rel_paths.sort();
+
+    let project_abs_paths: Vec<std::path::PathBuf> = rel_paths
+        .iter()
+        .map(|rel_path| root_dir.join(rel_path.replace('/', std::path::MAIN_SEPARATOR_STR)))
+        .collect();
+    let project_has_cpp = crate::traverser::has_cpp_source_files(&project_abs_paths);
+
+    let mut buckets: HashMap<u64, Vec<Candidate>> = HashMap::new();
+    for rel_path in rel_paths {
+        let entry = &graph.files[rel_path];
+        for candidate in fingerprint_file(rel_path, root_dir, &entry.functions, options.mode, project_has_cpp) {
+            if candidate.shape.node_count() < options.min_node_count {
+                continue;
+            }
+            buckets.entry(candidate.shape.structural_hash()).or_default().push(candidate);
+        }
+    }
+
+    let mut classes = Vec::new();
+    for (_, candidates) in buckets {
+        // 同一个哈希桶里按外形分组——哈希碰撞（外形不同但哈希相同）在这里被 spanless_eq（Shape 的 Eq）拆开
+        let mut groups: Vec<(Shape, Vec<FunctionFingerprint>)> = Vec::new();
+        for candidate in candidates {
+            if let Some(group) = groups.iter_mut().find(|(shape, _)| *shape == candidate.shape) {
+                group.1.push(candidate.fingerprint);
+            } else {
+                groups.push((candidate.shape, vec![candidate.fingerprint]));
+            }
+        }
+        for (shape, mut members) in groups {
+            if members.len() < 2 {
+                continue;
+            }
+            members.sort_by(|a, b| (a.file.clone(), a.start_line).cmp(&(b.file.clone(), b.start_line)));
+            classes.push(CloneClass { node_count: shape.node_count(), members });
+        }
+    }
+
+    classes.sort_by(|a, b| {
+        b.members
+            .len()
+            .cmp(&a.members.len())
+            .then_with(|| a.members[0].file.cmp(&b.members[0].file))
+            .then_with(|| a.members[0].start_line.cmp(&b.members[0].start_line))
+    });
+    classes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{create_empty_graph, FileEntry, FunctionInfo};
+
+    fn temp_project(name: &str, files: &[(&str, &str)]) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("codemap-duplication-test-{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        for (rel, content) in files {
+            let path = dir.join(rel);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).unwrap();
+            }
+            std::fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    fn make_entry(functions: Vec<FunctionInfo>) -> FileEntry {
+        FileEntry {
+            language: "rust".to_string(),
+            module: "_root".to_string(),
+            hash: "sha256:x".to_string(),
+            lines: 1,
+            code_lines: 1,
+            comment_lines: 0,
+            blank_lines: 0,
+            functions,
+            classes: vec![],
+            types: vec![],
+            imports: vec![],
+            exports: vec![],
+            reexports: vec![],
+            resolved_reexports: vec![],
+            calls: vec![],
+            is_entry_point: false,
+            entry_point_reason: None,
+            resolved_imports: vec![],
+            imported_by: vec![],
+            parse_diagnostics: vec![],
+        }
+    }
+
+    fn func(name: &str, start: u32, end: u32) -> FunctionInfo {
+        FunctionInfo { name: name.to_string(), signature: String::new(), start_line: start, end_line: end, complexity: 1 }
+    }
+
+    const ADD_A: &str = "fn add_totals(items: &[i32]) -> i32 {\n    let mut sum = 0;\n    for x in items {\n        sum += x;\n    }\n    sum\n}\n";
+    const ADD_B: &str = "fn sum_values(values: &[i32]) -> i32 {\n    let mut total = 0;\n    for v in values {\n        total += v;\n    }\n    total\n}\n";
+    const UNRELATED: &str = "fn greet(name: &str) -> String {\n    format!(\"hello {}\", name)\n}\n";
+
+    #[test]
+    fn test_detect_clones_finds_renamed_duplicate_with_normalized_mode() {
+        let dir = temp_project("normalized", &[("a.rs", ADD_A), ("b.rs", ADD_B)]);
+        let mut graph = create_empty_graph("test", dir.to_str().unwrap());
+        graph.files.insert("a.rs".to_string(), make_entry(vec![func("add_totals", 1, 7)]));
+        graph.files.insert("b.rs".to_string(), make_entry(vec![func("sum_values", 1, 7)]));
+
+        let options = DetectOptions { min_node_count: 1, mode: MatchMode::Normalized };
+        let classes = detect_clones(&graph, &dir, &options);
+
+        assert_eq!(classes.len(), 1);
+        assert_eq!(classes[0].members.len(), 2);
+        assert!(classes[0].members.iter().any(|m| m.function == "add_totals"));
+        assert!(classes[0].members.iter().any(|m| m.function == "sum_values"));
+    }
+
+    #[test]
+    fn test_detect_clones_exact_mode_rejects_renamed_duplicate() {
+        let dir = temp_project("exact", &[("a.rs", ADD_A), ("b.rs", ADD_B)]);
+        let mut graph = create_empty_graph("test", dir.to_str().unwrap());
+        graph.files.insert("a.rs".to_string(), make_entry(vec![func("add_totals", 1, 7)]));
+        graph.files.insert("b.rs".to_string(), make_entry(vec![func("sum_values", 1, 7)]));
+
+        let options = DetectOptions { min_node_count: 1, mode: MatchMode::Exact };
+        let classes = detect_clones(&graph, &dir, &options);
+
+        assert!(classes.is_empty());
+    }
+
+    #[test]
+    fn test_detect_clones_ignores_unrelated_functions() {
+        let dir = temp_project("unrelated", &[("a.rs", ADD_A), ("c.rs", UNRELATED)]);
+        let mut graph = create_empty_graph("test", dir.to_str().unwrap());
+        graph.files.insert("a.rs".to_string(), make_entry(vec![func("add_totals", 1, 7)]));
+        graph.files.insert("c.rs".to_string(), make_entry(vec![func("greet", 1, 3)]));
+
+        let options = DetectOptions { min_node_count: 1, mode: MatchMode::Normalized };
+        let classes = detect_clones(&graph, &dir, &options);
+
+        assert!(classes.is_empty());
+    }
+
+    #[test]
+    fn test_detect_clones_respects_min_node_count_threshold() {
+        let dir = temp_project("threshold", &[("a.rs", ADD_A), ("b.rs", ADD_B)]);
+        let mut graph = create_empty_graph("test", dir.to_str().unwrap());
+        graph.files.insert("a.rs".to_string(), make_entry(vec![func("add_totals", 1, 7)]));
+        graph.files.insert("b.rs".to_string(), make_entry(vec![func("sum_values", 1, 7)]));
+
+        let options = DetectOptions { min_node_count: 10_000, mode: MatchMode::Normalized };
+        let classes = detect_clones(&graph, &dir, &options);
+
+        assert!(classes.is_empty());
+    }
+}
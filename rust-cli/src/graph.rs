@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::path::Path;
 
 // ── 数据结构（与 Node.js JSON schema 完全兼容）────────────────────────────────
@@ -14,6 +15,7 @@ pub struct FunctionInfo {
     pub start_line: u32,
     #[serde(rename = "endLine")]
     pub end_line: u32,
+    pub complexity: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +24,51 @@ pub struct ImportInfo {
     pub symbols: Vec<String>,
     #[serde(rename = "isExternal")]
     pub is_external: bool,
+    /// `require(...)`/动态 `import(...)` 调用识别出的导入，区别于静态 `import`/`use`
+    /// 声明。是 Rust 版独有的新增字段，Node.js 版没有对应概念
+    #[serde(default)]
+    pub dynamic: bool,
+}
+
+/// barrel 文件里的一条 re-export：`export { login } from '../auth/login'`
+/// 或 `export * from './routes'`。`star` 为真时 `name` 留空，真正的符号列表
+/// 要等 [`crate::slicer`] 的解析阶段把来源模块的导出折叠进来。是 Rust 版独有的
+/// 新增字段，Node.js 版没有对应概念
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReexportInfo {
+    pub name: String,
+    pub source: String,
+    pub star: bool,
+}
+
+/// [`ReexportInfo::source`] 解析到具体文件之后的结果，供 [`crate::slicer`] 的
+/// re-export 解析阶段直接按文件键查表，不用重新跑一遍相对路径解析
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedReexport {
+    pub name: String,
+    #[serde(rename = "targetFile")]
+    pub target_file: String,
+    pub star: bool,
+}
+
+/// 一条函数调用边：`caller` 调用了 `callee`，在源文件第 `line` 行
+///
+/// 目前 Rust/JavaScript/TypeScript/Python/Go/Java/C/C++ 八个内置适配器都会产出
+/// （`languages::LanguageAdapter::extract_calls` 的默认空实现只有 `QueryAdapter` 沿用），使
+/// [`crate::impact::analyze_impact`] 能在函数粒度而不只是模块粒度回答"谁依赖谁"。
+/// `caller`/`callee` 是函数名（Rust 方法为 `Type::method` 形式），不是全限定路径——
+/// 跨文件同名函数目前无法区分。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallInfo {
+    pub caller: String,
+    pub callee: String,
+    pub line: u32,
+    /// `callee` 是否已经匹配上了某个已知的本地函数（同文件内，或者本文件
+    /// `resolved_imports` 指向的文件里）。见 [`crate::scanner::resolve_calls`]。
+    /// Rust 版独有的新增字段，`#[serde(default)]` 保证缺省时落回 `false`，不影响
+    /// 与 Node.js 版的 JSON 兼容性。
+    #[serde(default)]
+    pub resolved: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +80,16 @@ pub struct ClassInfo {
     pub end_line: u32,
 }
 
+/// interface/type 字面量里的一个成员，见 [`crate::languages::Member`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypeMember {
+    pub name: String,
+    pub kind: String, // "field" | "method"
+    pub optional: bool,
+    #[serde(rename = "typeAnnotation", skip_serializing_if = "Option::is_none", default)]
+    pub type_annotation: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TypeInfo {
     pub name: String,
@@ -41,6 +98,11 @@ pub struct TypeInfo {
     pub start_line: u32,
     #[serde(rename = "endLine")]
     pub end_line: u32,
+    /// `interface` 声明、或值是对象字面量的 `type` 别名的成员列表，让消费方能看到
+    /// 接口的形状而不只是声明名。Rust 版独有的新增字段，`#[serde(default)]` 保证
+    /// 缺省时落回空表，不影响与 Node.js 版的 JSON 兼容性。
+    #[serde(default)]
+    pub members: Vec<TypeMember>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,13 +111,68 @@ pub struct FileEntry {
     pub module: String,
     pub hash: String,
     pub lines: u32,
+    #[serde(rename = "codeLines")]
+    pub code_lines: u32,
+    #[serde(rename = "commentLines")]
+    pub comment_lines: u32,
+    #[serde(rename = "blankLines")]
+    pub blank_lines: u32,
     pub functions: Vec<FunctionInfo>,
     pub classes: Vec<ClassInfo>,
     pub types: Vec<TypeInfo>,
     pub imports: Vec<ImportInfo>,
     pub exports: Vec<String>,
+    /// 本文件里的 barrel re-export（未解析），见 [`ReexportInfo`]。是 Rust 版
+    /// 独有的新增字段，`#[serde(default)]` 保证缺省时落回空表
+    #[serde(default)]
+    pub reexports: Vec<ReexportInfo>,
+    /// [`reexports`] 里每条 `source` 解析到具体文件之后的结果，由
+    /// [`crate::scanner::resolve_file_imports`] 跟 `resolved_imports` 一起填充。
+    /// 是 Rust 版独有的新增字段
+    #[serde(rename = "resolvedReexports", default)]
+    pub resolved_reexports: Vec<ResolvedReexport>,
+    pub calls: Vec<CallInfo>,
     #[serde(rename = "isEntryPoint")]
     pub is_entry_point: bool,
+    /// 判定 `is_entry_point` 的依据：`"filename"` | `"mainFunction"` | `"manifest"`，
+    /// 由 [`EntryPointReason::as_str`] 产出；非入口点文件为 `None`
+    #[serde(rename = "entryPointReason", skip_serializing_if = "Option::is_none", default)]
+    pub entry_point_reason: Option<String>,
+    /// 本文件中相对导入解析到的具体文件：`(原始 import.source, 解析出的 files 键)`。
+    /// 由 [`crate::scanner::resolve_file_imports`] 填充，是 Rust 版独有的新增字段
+    /// （Node.js 版不写、也不需要读取），不影响跨实现兼容性：缺省时 `#[serde(default)]` 落回空表。
+    /// 只覆盖能定位到具体文件的导入——裸导入（`react`、`<vector>` 这类包/系统库）留空，
+    /// 粗粒度的模块级依赖仍由 `modules[].dependsOn` 承担。
+    #[serde(rename = "resolvedImports", default)]
+    pub resolved_imports: Vec<(String, String)>,
+    /// [`resolved_imports`] 的反向索引：哪些文件把本文件解析为了它们的导入目标
+    #[serde(rename = "importedBy", default)]
+    pub imported_by: Vec<String>,
+    /// 本文件 tree-sitter 语法树里的 `ERROR`/`MISSING` 节点（见
+    /// [`crate::scanner::collect_parse_diagnostics`]），即这份源码本身有语法错误、
+    /// 解析只能凑出一棵"尽力而为"的树。是 Rust 版独有的新增字段，`#[serde(default)]`
+    /// 保证缺省时落回空表，不影响与 Node.js 版的 JSON 兼容性。
+    #[serde(rename = "parseDiagnostics", default)]
+    pub parse_diagnostics: Vec<ParseDiagnostic>,
+}
+
+/// tree-sitter 语法树节点异常的种类：`Error` 对应 `node.is_error()`（解析器完全认不出
+/// 这段语法，产出一个通用错误节点），`Missing` 对应 `node.is_missing()`（解析器能认出
+/// 期望的语法结构，但缺了某个必须的子节点，比如少了一个右括号）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParseDiagnosticKind {
+    Error,
+    Missing,
+}
+
+/// 文件语法树中的一处 `ERROR`/`MISSING` 节点
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParseDiagnostic {
+    pub line: u32,
+    pub column: u32,
+    pub kind: ParseDiagnosticKind,
+    /// 出错节点覆盖的源码文本，单行化并截断，仅供人读，不保证能重新解析
+    pub snippet: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,6 +182,70 @@ pub struct ModuleEntry {
     pub depends_on: Vec<String>,
     #[serde(rename = "dependedBy")]
     pub depended_by: Vec<String>,
+    /// 本模块下所有文件 `code_lines`/`comment_lines`/`blank_lines` 的加总，由
+    /// [`recalculate_module_line_stats`] 统一刷新。Rust 版独有的新增字段，
+    /// `#[serde(default)]` 保证缺省时落回 0，不影响与 Node.js 版的 JSON 兼容性。
+    #[serde(rename = "codeLines", default)]
+    pub code_lines: u32,
+    #[serde(rename = "commentLines", default)]
+    pub comment_lines: u32,
+    #[serde(rename = "blankLines", default)]
+    pub blank_lines: u32,
+}
+
+/// 按 `module.files` 列出的文件路径，对每个模块重新加总 `code_lines`/`comment_lines`/
+/// `blank_lines`；全量扫描（`scanner::assemble_graph`）和增量更新
+/// （`differ::recalculate_summary`）都在全部文件/模块就位之后调用这个函数一次，
+/// 和 `top_complexity_hotspots`/`detect_entry_point` 一样是装配阶段末尾的全量重算，
+/// 不是 per-file 增量。
+pub fn recalculate_module_line_stats(graph: &mut CodeGraph) {
+    for module in graph.modules.values_mut() {
+        let mut code_lines = 0u32;
+        let mut comment_lines = 0u32;
+        let mut blank_lines = 0u32;
+        for file_path in &module.files {
+            if let Some(file) = graph.files.get(file_path) {
+                code_lines += file.code_lines;
+                comment_lines += file.comment_lines;
+                blank_lines += file.blank_lines;
+            }
+        }
+        module.code_lines = code_lines;
+        module.comment_lines = comment_lines;
+        module.blank_lines = blank_lines;
+    }
+}
+
+/// 某一种语言在全项目范围内的行数统计，按 [`FileEntry::language`] 分组对
+/// `code_lines`/`comment_lines`/`blank_lines`（以及文件数）加总，供
+/// [`language_breakdown`] 产出 tokei 风格的逐语言汇总。
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct LanguageLineStats {
+    #[serde(rename = "fileCount")]
+    pub file_count: u32,
+    #[serde(rename = "codeLines")]
+    pub code_lines: u32,
+    #[serde(rename = "commentLines")]
+    pub comment_lines: u32,
+    #[serde(rename = "blankLines")]
+    pub blank_lines: u32,
+}
+
+/// 按 `file.language` 把 `graph.files` 里的 `code_lines`/`comment_lines`/
+/// `blank_lines` 分组加总，得到逐语言的汇总视图。这些原始行数本身由
+/// `scanner::classify_lines` 基于 tree-sitter 语法树识别出的注释节点算出
+/// （见该函数文档），这里只是按语言而不是按模块（[`recalculate_module_line_stats`]）
+/// 重新切一遍同一份数据，给 `metrics`/CLI 展示用。
+pub fn language_breakdown(graph: &CodeGraph) -> BTreeMap<String, LanguageLineStats> {
+    let mut breakdown: BTreeMap<String, LanguageLineStats> = BTreeMap::new();
+    for file in graph.files.values() {
+        let entry = breakdown.entry(file.language.clone()).or_default();
+        entry.file_count += 1;
+        entry.code_lines += file.code_lines;
+        entry.comment_lines += file.comment_lines;
+        entry.blank_lines += file.blank_lines;
+    }
+    breakdown
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -81,17 +262,72 @@ pub struct GraphSummary {
     pub total_functions: u32,
     #[serde(rename = "totalClasses")]
     pub total_classes: u32,
+    #[serde(rename = "totalCodeLines")]
+    pub total_code_lines: u32,
+    #[serde(rename = "totalCommentLines")]
+    pub total_comment_lines: u32,
+    #[serde(rename = "totalBlankLines")]
+    pub total_blank_lines: u32,
     pub languages: HashMap<String, u32>,
     pub modules: Vec<String>,
     #[serde(rename = "entryPoints")]
     pub entry_points: Vec<String>,
+    #[serde(rename = "complexityHotspots")]
+    pub complexity_hotspots: Vec<ComplexityHotspot>,
+    /// 模块依赖图中的强连通分量（大小 > 1，或一个自环），即循环 import；
+    /// 由 [`crate::impact::detect_cycles`] 算出，每次重算 summary 时一并刷新
+    #[serde(rename = "circularDependencies")]
+    pub circular_dependencies: Vec<Vec<String>>,
+    /// 全项目 `files[].parseDiagnostics` 的条目总数，Rust 版独有的新增字段，
+    /// `#[serde(default)]` 保证缺省时落回 0
+    #[serde(rename = "totalParseDiagnostics", default)]
+    pub total_parse_diagnostics: u32,
 }
 
+/// 复杂度最高的函数之一，用于 `GraphSummary.complexityHotspots`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplexityHotspot {
+    pub file: String,
+    pub name: String,
+    pub complexity: u32,
+}
+
+/// 跨 `CodeGraph.files` 挑出复杂度最高的前 `limit` 个函数，按复杂度降序、
+/// 复杂度相同时按文件路径再按函数名排序，保证结果确定
+pub fn top_complexity_hotspots(files: &HashMap<String, FileEntry>, limit: usize) -> Vec<ComplexityHotspot> {
+    let mut hotspots: Vec<ComplexityHotspot> = files
+        .iter()
+        .flat_map(|(path, file)| {
+            file.functions.iter().map(move |f| ComplexityHotspot {
+                file: path.clone(),
+                name: f.name.clone(),
+                complexity: f.complexity,
+            })
+        })
+        .collect();
+
+    hotspots.sort_by(|a, b| {
+        b.complexity
+            .cmp(&a.complexity)
+            .then_with(|| a.file.cmp(&b.file))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    hotspots.truncate(limit);
+    hotspots
+}
+
+/// 默认展示的复杂度热点数量
+pub const COMPLEXITY_HOTSPOT_LIMIT: usize = 10;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphConfig {
     pub languages: Vec<String>,
     #[serde(rename = "excludePatterns")]
     pub exclude_patterns: Vec<String>,
+    /// `--include` 传入的 glob 白名单模式，见 [`crate::traverser::ScanFilter`]。
+    /// Rust 版独有的新增字段，`#[serde(default)]` 保证缺省时落回空表
+    #[serde(rename = "includePatterns", default)]
+    pub include_patterns: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -106,10 +342,38 @@ pub struct CodeGraph {
     pub summary: GraphSummary,
     pub modules: HashMap<String, ModuleEntry>,
     pub files: HashMap<String, FileEntry>,
+    /// C/C++ `#include` 解析诊断（见 [`crate::scanner::resolve_c_includes`]）：哪些
+    /// include 没能映射到 `files` 里的具体文件。是 Rust 版独有的新增字段（Node.js
+    /// 版不写、也不需要读取），`#[serde(default)]` 保证缺省时落回空表，不影响兼容性。
+    #[serde(rename = "includeDiagnostics", default)]
+    pub include_diagnostics: Vec<IncludeDiagnostic>,
+}
+
+/// 未解析的 `#include` 严重程度：引号包含（用户头文件）找不到文件视为错误——项目布局
+/// 大概率有问题；尖括号包含（系统/标准库头）找不到则只是信息提示，毕竟系统头本来就
+/// 不在扫描范围内，这是预期之内的"解析不到"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IncludeSeverity {
+    Error,
+    Info,
+}
+
+/// 一条未能解析到 `files` 里具体文件的 `#include`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IncludeDiagnostic {
+    /// `#include` 原始路径（已去掉引号/尖括号，与 `ImportInfo.source` 一致）
+    pub path: String,
+    #[serde(rename = "includingFile")]
+    pub including_file: String,
+    pub line: u32,
+    pub severity: IncludeSeverity,
 }
 
 /// meta.json 格式与 Node.js 版本完全兼容：
 /// { lastScanAt, commitHash, scanDuration, fileHashes }
+///
+/// `fileStats` 是 Rust 版独有的新增字段（Node.js 版不写、也不需要读取），
+/// 不影响跨实现兼容性：缺省时 `#[serde(default)]` 落回空表。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetaInfo {
     /// 上次扫描时间
@@ -124,6 +388,20 @@ pub struct MetaInfo {
     /// 文件哈希映射（relPath → hash），用于增量更新对比
     #[serde(rename = "fileHashes", default)]
     pub file_hashes: BTreeMap<String, String>,
+    /// 文件大小 + mtime 映射（relPath → FileStat），用于增量更新时跳过未变更文件的重新哈希
+    #[serde(rename = "fileStats", default)]
+    pub file_stats: BTreeMap<String, FileStat>,
+}
+
+/// 一个文件在上次扫描时的大小与修改时间（纳秒级 Unix 时间戳）
+///
+/// 配合 [`crate::differ::detect_changed_files_stat`] 做 mtime+size 快速路径：
+/// 两者都没变就认为文件内容没变，省掉重新读取、哈希整个文件的开销。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStat {
+    pub size: u64,
+    #[serde(rename = "mtimeNanos")]
+    pub mtime_nanos: i64,
 }
 
 // ── 入口点文件名集合 ──────────────────────────────────────────────────────────
@@ -148,17 +426,25 @@ pub fn create_empty_graph(project_name: &str, root_dir: &str) -> CodeGraph {
         config: GraphConfig {
             languages: vec![],
             exclude_patterns: vec![],
+            include_patterns: vec![],
         },
         summary: GraphSummary {
             total_files: 0,
             total_functions: 0,
             total_classes: 0,
+            total_code_lines: 0,
+            total_comment_lines: 0,
+            total_blank_lines: 0,
             languages: HashMap::new(),
             modules: vec![],
             entry_points: vec![],
+            complexity_hotspots: vec![],
+            circular_dependencies: vec![],
+            total_parse_diagnostics: 0,
         },
         modules: HashMap::new(),
         files: HashMap::new(),
+        include_diagnostics: vec![],
     }
 }
 
@@ -181,6 +467,322 @@ pub fn is_entry_point(file_path: &Path) -> bool {
     ENTRY_POINT_NAMES.contains(&stem.as_str())
 }
 
+/// 判定一个文件是入口点的依据——供下游（CLI 输出、LSP 等）展示"为什么"，
+/// 而不只是一个裸 `bool`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryPointReason {
+    /// 文件名/stem 命中固定名单（`main`、`index`、`server`……），最弱的兜底信号
+    Filename,
+    /// Rust 文件里有一个顶层 `fn main`
+    MainFunction,
+    /// `package.json` 的 `main`/`bin`/`exports`，或 `Cargo.toml` 的 `[[bin]]`
+    /// 指向了这个文件
+    Manifest,
+}
+
+impl EntryPointReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntryPointReason::Filename => "filename",
+            EntryPointReason::MainFunction => "mainFunction",
+            EntryPointReason::Manifest => "manifest",
+        }
+    }
+}
+
+/// 从项目清单文件里收集到的入口点绝对路径提示，扫描一次项目根目录就够，
+/// 不必每个文件都重新读一遍 `package.json`/`Cargo.toml`
+#[derive(Debug, Clone, Default)]
+pub struct ManifestHints {
+    entry_paths: HashSet<std::path::PathBuf>,
+}
+
+impl ManifestHints {
+    fn contains(&self, path: &Path) -> bool {
+        self.entry_paths.contains(path)
+    }
+}
+
+/// 读取项目根目录下的 `package.json`、`Cargo.toml`，收集它们指向的入口文件
+/// （绝对路径；清单不存在、解析失败、字段缺失时静默跳过那一个，不影响另一个）。
+/// 和 [`crate::languages::go_lang::read_module_path`] 只看根 `go.mod` 一样，
+/// 这里也只看项目根的清单，不会往子目录递归找 monorepo 里的嵌套 package.json。
+pub fn read_manifest_hints(root_dir: &Path) -> ManifestHints {
+    let mut entry_paths = HashSet::new();
+
+    if let Ok(content) = std::fs::read_to_string(root_dir.join("package.json")) {
+        for rel in package_json_entry_paths(&content) {
+            entry_paths.insert(root_dir.join(rel));
+        }
+    }
+    if let Ok(content) = std::fs::read_to_string(root_dir.join("Cargo.toml")) {
+        for rel in cargo_toml_bin_paths(&content) {
+            entry_paths.insert(root_dir.join(rel));
+        }
+    }
+
+    ManifestHints { entry_paths }
+}
+
+/// 综合判定一个文件是否是入口点、以及判定依据，按优先级从高到低依次尝试：
+/// 清单文件显式声明 > Rust 顶层 `fn main` > 文件名命中固定名单。旧的纯文件名
+/// 启发式 [`is_entry_point`] 仍然保留，作为这里最后一道兜底信号。
+pub fn detect_entry_point(
+    file_entry: &FileEntry,
+    path: &Path,
+    manifest_hints: &ManifestHints,
+) -> Option<EntryPointReason> {
+    if manifest_hints.contains(path) {
+        return Some(EntryPointReason::Manifest);
+    }
+    if file_entry.language == "rust" && file_entry.functions.iter().any(|f| f.name == "main") {
+        return Some(EntryPointReason::MainFunction);
+    }
+    if is_entry_point(path) {
+        return Some(EntryPointReason::Filename);
+    }
+    None
+}
+
+fn normalize_manifest_path(raw: &str) -> String {
+    raw.trim().trim_start_matches("./").replace('\\', "/")
+}
+
+/// 解析 `package.json` 的 `main`/`bin`/`exports` 字段，收集它们指向的相对路径
+///
+/// `pub(crate)` 而不是私有，是因为 `resolver::resolve_path_style_import` 也要用
+/// 它来把一个解析到的包目录换算成真正的入口文件，不必再实现一遍同样的解析
+pub(crate) fn package_json_entry_paths(content: &str) -> Vec<String> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(content) else {
+        return vec![];
+    };
+    let mut paths = Vec::new();
+
+    for key in ["main", "bin"] {
+        match value.get(key) {
+            Some(serde_json::Value::String(s)) => paths.push(normalize_manifest_path(s)),
+            Some(serde_json::Value::Object(map)) => {
+                paths.extend(map.values().filter_map(|v| v.as_str()).map(normalize_manifest_path));
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(exports) = value.get("exports") {
+        collect_exports_paths(exports, &mut paths);
+    }
+
+    paths
+}
+
+/// `exports` 字段可以是一个字符串、也可以是任意深度嵌套的条件导出对象
+/// （`{"import": "...", "require": {...}}`），递归收集所有叶子字符串值
+fn collect_exports_paths(value: &serde_json::Value, out: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(s) => out.push(normalize_manifest_path(s)),
+        serde_json::Value::Object(map) => {
+            for v in map.values() {
+                collect_exports_paths(v, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 从 `Cargo.toml` 里手写扫出每个 `[[bin]]` 小节的 `path = "..."` 字段。不引入
+/// 一个完整的 TOML 解析库——和 `go_lang::parse_go_module_path` 按行扫描 `go.mod`
+/// 是同样的取舍，这里只需要认出这一种小节和这一个字段。
+fn cargo_toml_bin_paths(content: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    let mut in_bin_section = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_bin_section = trimmed == "[[bin]]";
+            continue;
+        }
+        if !in_bin_section {
+            continue;
+        }
+        let Some(rest) = trimmed.strip_prefix("path") else { continue };
+        let Some(rest) = rest.trim_start().strip_prefix('=') else { continue };
+        if let Some(value) = extract_toml_string(rest.trim()) {
+            paths.push(normalize_manifest_path(&value));
+        }
+    }
+
+    paths
+}
+
+fn extract_toml_string(s: &str) -> Option<String> {
+    s.strip_prefix('"')?.strip_suffix('"').map(|v| v.to_string())
+}
+
+// ── 图谱导出 ──────────────────────────────────────────────────────────────────
+
+/// 模块的聚合统计，字段形状和 [`crate::slicer::ModuleStats`] 保持一致，方便消费方
+/// 不用区分 overview.json 和这里的导出图就能复用同一套解析逻辑。graph.rs 处在依赖图
+/// 的底层，不反向依赖 slicer，所以这里单独定义一份而不是复用那边的类型。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeStats {
+    #[serde(rename = "totalFunctions")]
+    pub total_functions: u32,
+    #[serde(rename = "totalClasses")]
+    pub total_classes: u32,
+    #[serde(rename = "totalLines")]
+    pub total_lines: u32,
+}
+
+/// node-link 格式的一个节点（模块），供 D3/force-graph 等可视化工具使用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNode {
+    pub id: String,
+    #[serde(rename = "isEntryPoint")]
+    pub is_entry_point: bool,
+    #[serde(rename = "fileCount")]
+    pub file_count: u32,
+    pub stats: NodeStats,
+}
+
+/// node-link 格式的一条有向边（dependsOn）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphLink {
+    pub source: String,
+    pub target: String,
+}
+
+/// 通用的 node-link 图格式：`{ nodes: [...], links: [{source, target}] }`，
+/// D3 / force-graph 等可视化工具可以直接消费
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeLinkGraph {
+    pub nodes: Vec<GraphNode>,
+    pub links: Vec<GraphLink>,
+}
+
+/// 模块是否含有入口点文件——用来在导出图里高亮入口模块
+fn entry_point_modules(graph: &CodeGraph) -> HashSet<&str> {
+    graph
+        .summary
+        .entry_points
+        .iter()
+        .filter_map(|f| graph.files.get(f))
+        .map(|f| f.module.as_str())
+        .collect()
+}
+
+/// DOT 标签里的反斜杠、双引号需要转义，否则生成的文件无法被 GraphViz 解析
+fn escape_dot_label(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// 统计一个模块下所有文件的函数数/类数/行数加总，供导出图的节点属性使用；
+/// 算法和 [`crate::slicer`] 里 `collect_module_stats` 一致，但 graph.rs 不反向
+/// 依赖 slicer，所以这里单独算一遍
+fn node_stats(graph: &CodeGraph, mod_data: &ModuleEntry) -> NodeStats {
+    let mut total_functions = 0u32;
+    let mut total_classes = 0u32;
+    let mut total_lines = 0u32;
+
+    for file_path in &mod_data.files {
+        if let Some(file_data) = graph.files.get(file_path) {
+            total_functions += file_data.functions.len() as u32;
+            total_classes += file_data.classes.len() as u32;
+            total_lines += file_data.lines;
+        }
+    }
+
+    NodeStats { total_functions, total_classes, total_lines }
+}
+
+/// 把 `total_lines` 映射到 GraphViz 的 `fontsize`：在 10~24pt 之间按行数对数增长，
+/// 行数越多的模块标签越大，但不会因为某个模块特别大就把图撑得无法阅读
+fn dot_fontsize_for_lines(total_lines: u32) -> u32 {
+    let scaled = 10.0 + (total_lines as f64 + 1.0).ln() * 2.5;
+    scaled.clamp(10.0, 24.0).round() as u32
+}
+
+/// 把模块依赖图渲染成 GraphViz DOT：模块是节点，`dependsOn` 是有向边，
+/// 含入口点文件的模块用浅绿色高亮，节点标签标注总行数并按行数缩放字号。
+/// 可以直接喂给 `dot -Tsvg`。
+pub fn export_dot(graph: &CodeGraph) -> String {
+    let entry_modules = entry_point_modules(graph);
+
+    let mut module_names: Vec<&String> = graph.modules.keys().collect();
+    module_names.sort();
+
+    let mut out = String::from("digraph codemap {\n  rankdir=LR;\n");
+
+    for name in &module_names {
+        let Some(mod_data) = graph.modules.get(*name) else { continue };
+        let stats = node_stats(graph, mod_data);
+        let label = format!("{}\\n{} lines", escape_dot_label(name), stats.total_lines);
+        let fontsize = dot_fontsize_for_lines(stats.total_lines);
+        if entry_modules.contains(name.as_str()) {
+            out.push_str(&format!(
+                "  \"{name}\" [label=\"{label}\", fontsize={fontsize}, style=filled, fillcolor=lightgreen];\n",
+                name = escape_dot_label(name)
+            ));
+        } else {
+            out.push_str(&format!(
+                "  \"{name}\" [label=\"{label}\", fontsize={fontsize}];\n",
+                name = escape_dot_label(name)
+            ));
+        }
+    }
+
+    for name in &module_names {
+        let Some(entry) = graph.modules.get(*name) else { continue };
+        let mut deps = entry.depends_on.clone();
+        deps.sort();
+        for dep in deps {
+            out.push_str(&format!(
+                "  \"{}\" -> \"{}\";\n",
+                escape_dot_label(name),
+                escape_dot_label(&dep)
+            ));
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// 把模块依赖图渲染成通用的 node-link JSON，供非 GraphViz 的可视化工具使用。
+/// 节点、边的顺序都经过排序，保证同一张图谱每次导出结果一致。
+pub fn export_json_graph(graph: &CodeGraph) -> NodeLinkGraph {
+    let entry_modules = entry_point_modules(graph);
+
+    let mut module_names: Vec<&String> = graph.modules.keys().collect();
+    module_names.sort();
+
+    let nodes = module_names
+        .iter()
+        .filter_map(|name| graph.modules.get(*name).map(|mod_data| (name, mod_data)))
+        .map(|(name, mod_data)| GraphNode {
+            id: (*name).clone(),
+            is_entry_point: entry_modules.contains(name.as_str()),
+            file_count: mod_data.files.len() as u32,
+            stats: node_stats(graph, mod_data),
+        })
+        .collect();
+
+    let mut links = Vec::new();
+    for name in &module_names {
+        let Some(entry) = graph.modules.get(*name) else { continue };
+        let mut deps = entry.depends_on.clone();
+        deps.sort();
+        for dep in deps {
+            links.push(GraphLink {
+                source: (*name).clone(),
+                target: dep,
+            });
+        }
+    }
+
+    NodeLinkGraph { nodes, links }
+}
+
 /// 保存图谱到 .codemap/ 目录，meta.json 格式与 Node.js 完全兼容
 pub fn save_graph(output_dir: &Path, graph: &CodeGraph) -> anyhow::Result<()> {
     std::fs::create_dir_all(output_dir)?;
@@ -199,6 +801,7 @@ pub fn save_graph(output_dir: &Path, graph: &CodeGraph) -> anyhow::Result<()> {
         commit_hash: graph.commit_hash.clone(),
         scan_duration: 0,
         file_hashes,
+        file_stats: BTreeMap::new(),
     };
     let meta_json = serde_json::to_string_pretty(&meta)?;
     std::fs::write(output_dir.join("meta.json"), meta_json)?;
@@ -217,6 +820,46 @@ pub fn load_meta(output_dir: &Path) -> anyhow::Result<MetaInfo> {
     Ok(serde_json::from_str(&data)?)
 }
 
+/// 把本次扫描得到的文件 `(size, mtime)` 写入 meta.json 的 `fileStats` 字段
+///
+/// 在 `fileHashes`/其余字段已经由 [`save_graph`] 落盘之后单独调用，增量合并进
+/// meta.json，不影响其余字段。meta.json 不存在时视为空 meta（首次扫描场景）。
+pub fn save_file_stats(output_dir: &Path, file_stats: &BTreeMap<String, FileStat>) -> anyhow::Result<()> {
+    let mut meta = load_meta(output_dir).unwrap_or_else(|_| MetaInfo {
+        last_scan_at: chrono_now(),
+        commit_hash: None,
+        scan_duration: 0,
+        file_hashes: BTreeMap::new(),
+        file_stats: BTreeMap::new(),
+    });
+    meta.file_stats = file_stats.clone();
+    let meta_json = serde_json::to_string_pretty(&meta)?;
+    std::fs::write(output_dir.join("meta.json"), meta_json)?;
+    Ok(())
+}
+
+/// 把 `std::fs::Metadata` 转换成 [`FileStat`]；mtime 不可用时 `mtime_nanos` 退化为 0
+/// （退化值不会意外匹配真实的扫描时间戳，所以仍会被 `detect_changed_files_stat`
+/// 当作"已变更"稳妥处理，而不是被误判为"未变更"）
+pub fn file_stat_from_metadata(metadata: &std::fs::Metadata) -> FileStat {
+    let mtime_nanos = metadata
+        .modified()
+        .ok()
+        .and_then(system_time_to_nanos)
+        .unwrap_or(0);
+    FileStat { size: metadata.len(), mtime_nanos }
+}
+
+/// 当前时间的纳秒级 Unix 时间戳，供 `detect_changed_files_stat` 判断 mtime 是否与本次
+/// 扫描"撞车"
+pub fn now_nanos() -> i64 {
+    system_time_to_nanos(std::time::SystemTime::now()).unwrap_or(0)
+}
+
+fn system_time_to_nanos(t: std::time::SystemTime) -> Option<i64> {
+    t.duration_since(std::time::UNIX_EPOCH).ok().map(|d| d.as_nanos() as i64)
+}
+
 // ── 内部工具函数 ──────────────────────────────────────────────────────────────
 
 fn hex_encode(bytes: &[u8]) -> String {
@@ -271,6 +914,100 @@ fn is_leap(year: u64) -> bool {
     (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
 }
 
+fn days_in_month(year: u64, month: u64) -> u64 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 => if is_leap(year) { 29 } else { 28 },
+        _ => 0,
+    }
+}
+
+/// `unix_to_datetime` 的逆运算：把一个 1970 年以后的日期换算成自 epoch 起的天数
+fn days_since_epoch(year: u64, month: u64, day: u64) -> Option<u64> {
+    if year < 1970 || !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+    let mut days = 0u64;
+    for y in 1970..year {
+        days += if is_leap(y) { 366 } else { 365 };
+    }
+    for m in 1..month {
+        days += days_in_month(year, m);
+    }
+    days += day - 1;
+    Some(days)
+}
+
+/// `chrono_now` 的逆运算：把一个 ISO 8601 时间戳解析回 Unix 秒数。
+///
+/// 接受这个 crate 自己写出的精确格式（`YYYY-MM-DDTHH:MM:SS.mmmZ`），也放宽到
+/// 可选的任意位数小数秒，以及用 `+HH:MM`/`-HH:MM` 偏移代替 `Z`（偏移会被换算
+/// 回 UTC）。格式不对、字段越界（月份、天数、时分秒）一律返回 `None`，不 panic。
+pub fn parse_iso8601(s: &str) -> Option<u64> {
+    if s.len() < 20 {
+        return None;
+    }
+    let bytes = s.as_bytes();
+    if bytes[4] != b'-' || bytes[7] != b'-' || bytes[10] != b'T' || bytes[13] != b':' || bytes[16] != b':' {
+        return None;
+    }
+
+    let year: u64 = s.get(0..4)?.parse().ok()?;
+    let month: u64 = s.get(5..7)?.parse().ok()?;
+    let day: u64 = s.get(8..10)?.parse().ok()?;
+    let hour: u64 = s.get(11..13)?.parse().ok()?;
+    let minute: u64 = s.get(14..16)?.parse().ok()?;
+    let second: u64 = s.get(17..19)?.parse().ok()?;
+    if hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+
+    let mut rest = s.get(19..)?;
+    if let Some(after_dot) = rest.strip_prefix('.') {
+        let frac_len = after_dot.chars().take_while(|c| c.is_ascii_digit()).count();
+        if frac_len == 0 {
+            return None;
+        }
+        rest = after_dot.get(frac_len..)?;
+    }
+
+    let offset_secs: i64 = if rest == "Z" {
+        0
+    } else if rest.len() == 6 && matches!(rest.as_bytes()[0], b'+' | b'-') && rest.as_bytes()[3] == b':' {
+        let sign: i64 = if rest.as_bytes()[0] == b'-' { -1 } else { 1 };
+        let offset_hours: i64 = rest.get(1..3)?.parse().ok()?;
+        let offset_minutes: i64 = rest.get(4..6)?.parse().ok()?;
+        if offset_hours > 23 || offset_minutes > 59 {
+            return None;
+        }
+        sign * (offset_hours * 3600 + offset_minutes * 60)
+    } else {
+        return None;
+    };
+
+    let days = days_since_epoch(year, month, day)?;
+    let local_secs = (days * 86400 + hour * 3600 + minute * 60 + second) as i64;
+    let utc_secs = local_secs - offset_secs;
+    u64::try_from(utc_secs).ok()
+}
+
+/// 默认的图谱"陈旧"警告阈值（秒）：超过这个年龄时即便没显式传 `--max-age`
+/// 也应该提示用户重新扫描
+pub const DEFAULT_STALE_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// 图谱距今的年龄（秒）。`last_scan_at` 解析失败，或者（时钟回拨等异常情况下）
+/// 反而晚于当前时间时，返回 `None`——调用方应当把这种情况当作"无法判断"而不是
+/// "新鲜"，静默跳过陈旧检测。
+pub fn graph_age_secs(meta: &MetaInfo) -> Option<u64> {
+    let scanned_at = parse_iso8601(&meta.last_scan_at)?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_secs();
+    now.checked_sub(scanned_at)
+}
+
 // ── 测试 ──────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -307,4 +1044,249 @@ mod tests {
         let parsed: CodeGraph = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.version, "1.0");
     }
+
+    fn make_fn(name: &str, complexity: u32) -> FunctionInfo {
+        FunctionInfo { name: name.to_string(), signature: format!("{}()", name), start_line: 1, end_line: 2, complexity }
+    }
+
+    #[test]
+    fn test_top_complexity_hotspots_sorted_descending_and_truncated() {
+        let mut files = HashMap::new();
+        files.insert("a.ts".to_string(), FileEntry {
+            language: "typescript".into(), module: "a".into(), hash: "sha256:a".into(),
+            lines: 1, code_lines: 1, comment_lines: 0, blank_lines: 0,
+            functions: vec![make_fn("low", 2), make_fn("high", 9)],
+            classes: vec![], types: vec![], imports: vec![], exports: vec![], reexports: vec![], resolved_reexports: vec![], calls: vec![], is_entry_point: false,
+            entry_point_reason: None,
+            resolved_imports: vec![],
+            imported_by: vec![],
+            parse_diagnostics: vec![],
+        });
+        files.insert("b.ts".to_string(), FileEntry {
+            language: "typescript".into(), module: "b".into(), hash: "sha256:b".into(),
+            lines: 1, code_lines: 1, comment_lines: 0, blank_lines: 0,
+            functions: vec![make_fn("mid", 5)],
+            classes: vec![], types: vec![], imports: vec![], exports: vec![], reexports: vec![], resolved_reexports: vec![], calls: vec![], is_entry_point: false,
+            entry_point_reason: None,
+            resolved_imports: vec![],
+            imported_by: vec![],
+            parse_diagnostics: vec![],
+        });
+
+        let hotspots = top_complexity_hotspots(&files, 2);
+        assert_eq!(hotspots.len(), 2);
+        assert_eq!(hotspots[0].name, "high");
+        assert_eq!(hotspots[0].complexity, 9);
+        assert_eq!(hotspots[1].name, "mid");
+    }
+
+    #[test]
+    fn test_language_breakdown_groups_by_language() {
+        let mut graph = create_empty_graph("proj", "/tmp/proj");
+        graph.files.insert("a.ts".to_string(), FileEntry {
+            language: "typescript".into(), module: "a".into(), hash: "sha256:a".into(),
+            lines: 10, code_lines: 7, comment_lines: 2, blank_lines: 1,
+            functions: vec![], classes: vec![], types: vec![], imports: vec![], exports: vec![], reexports: vec![], resolved_reexports: vec![], calls: vec![], is_entry_point: false,
+            entry_point_reason: None,
+            resolved_imports: vec![],
+            imported_by: vec![],
+            parse_diagnostics: vec![],
+        });
+        graph.files.insert("b.ts".to_string(), FileEntry {
+            language: "typescript".into(), module: "b".into(), hash: "sha256:b".into(),
+            lines: 5, code_lines: 3, comment_lines: 1, blank_lines: 1,
+            functions: vec![], classes: vec![], types: vec![], imports: vec![], exports: vec![], reexports: vec![], resolved_reexports: vec![], calls: vec![], is_entry_point: false,
+            entry_point_reason: None,
+            resolved_imports: vec![],
+            imported_by: vec![],
+            parse_diagnostics: vec![],
+        });
+        graph.files.insert("main.py".to_string(), FileEntry {
+            language: "python".into(), module: "main".into(), hash: "sha256:c".into(),
+            lines: 4, code_lines: 2, comment_lines: 1, blank_lines: 1,
+            functions: vec![], classes: vec![], types: vec![], imports: vec![], exports: vec![], reexports: vec![], resolved_reexports: vec![], calls: vec![], is_entry_point: false,
+            entry_point_reason: None,
+            resolved_imports: vec![],
+            imported_by: vec![],
+            parse_diagnostics: vec![],
+        });
+
+        let breakdown = language_breakdown(&graph);
+        assert_eq!(breakdown.len(), 2);
+        let ts = &breakdown["typescript"];
+        assert_eq!(ts.file_count, 2);
+        assert_eq!(ts.code_lines, 10);
+        assert_eq!(ts.comment_lines, 3);
+        assert_eq!(ts.blank_lines, 2);
+        let py = &breakdown["python"];
+        assert_eq!(py.file_count, 1);
+        assert_eq!(py.code_lines, 2);
+    }
+
+    #[test]
+    fn test_meta_info_round_trips_file_stats() {
+        let meta = MetaInfo {
+            last_scan_at: "2026-01-01T00:00:00.000Z".to_string(),
+            commit_hash: None,
+            scan_duration: 0,
+            file_hashes: BTreeMap::new(),
+            file_stats: [("a.ts".to_string(), FileStat { size: 123, mtime_nanos: 456 })].into(),
+        };
+        let json = serde_json::to_string(&meta).unwrap();
+        assert!(json.contains("\"fileStats\""));
+        assert!(json.contains("\"mtimeNanos\""));
+        let parsed: MetaInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.file_stats["a.ts"].size, 123);
+        assert_eq!(parsed.file_stats["a.ts"].mtime_nanos, 456);
+    }
+
+    #[test]
+    fn test_meta_info_defaults_file_stats_when_absent() {
+        // 兼容没有 fileStats 字段的旧 meta.json（或 Node.js 版写出的文件）
+        let json = r#"{"lastScanAt":"2026-01-01T00:00:00.000Z","commitHash":null,"scanDuration":0,"fileHashes":{}}"#;
+        let parsed: MetaInfo = serde_json::from_str(json).unwrap();
+        assert!(parsed.file_stats.is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load_file_stats_round_trip() {
+        let dir = std::env::temp_dir().join(format!("codemap-test-{:?}", std::thread::current().id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let graph = create_empty_graph("test", "/tmp/test");
+        save_graph(&dir, &graph).unwrap();
+
+        let mut stats = BTreeMap::new();
+        stats.insert("a.ts".to_string(), FileStat { size: 1, mtime_nanos: 2 });
+        save_file_stats(&dir, &stats).unwrap();
+
+        let meta = load_meta(&dir).unwrap();
+        assert_eq!(meta.file_stats["a.ts"], FileStat { size: 1, mtime_nanos: 2 });
+        // save_file_stats 不应该破坏已有的其它字段
+        assert_eq!(meta.commit_hash, graph.commit_hash);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    fn make_module_graph() -> CodeGraph {
+        let mut graph = create_empty_graph("test", "/tmp/test");
+        graph.modules.insert(
+            "app".to_string(),
+            ModuleEntry {
+                files: vec!["src/main.rs".to_string()],
+                depends_on: vec!["utils".to_string()],
+                depended_by: vec![],
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
+            },
+        );
+        graph.modules.insert(
+            "utils".to_string(),
+            ModuleEntry {
+                files: vec!["src/utils.rs".to_string()],
+                depends_on: vec![],
+                depended_by: vec!["app".to_string()],
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
+            },
+        );
+        graph.files.insert(
+            "src/main.rs".to_string(),
+            FileEntry {
+                language: "rust".into(), module: "app".into(), hash: "sha256:a".into(),
+                lines: 1, code_lines: 1, comment_lines: 0, blank_lines: 0,
+                functions: vec![], classes: vec![], types: vec![], imports: vec![], exports: vec![], reexports: vec![], resolved_reexports: vec![], calls: vec![], is_entry_point: true,
+                entry_point_reason: Some("filename".to_string()),
+                resolved_imports: vec![],
+                imported_by: vec![],
+                parse_diagnostics: vec![],
+            },
+        );
+        graph.summary.entry_points = vec!["src/main.rs".to_string()];
+        graph
+    }
+
+    #[test]
+    fn test_parse_iso8601_round_trips_chrono_now_format() {
+        let now = chrono_now();
+        let parsed = parse_iso8601(&now).unwrap();
+        let now_secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        assert!(now_secs.abs_diff(parsed) <= 1);
+    }
+
+    #[test]
+    fn test_parse_iso8601_exact_known_value() {
+        // 2026-01-01T00:00:00.000Z == 1767225600
+        assert_eq!(parse_iso8601("2026-01-01T00:00:00.000Z"), Some(1767225600));
+    }
+
+    #[test]
+    fn test_parse_iso8601_accepts_optional_fraction_lengths() {
+        let base = parse_iso8601("2026-01-01T00:00:00Z").unwrap();
+        assert_eq!(parse_iso8601("2026-01-01T00:00:00.0Z"), Some(base));
+        assert_eq!(parse_iso8601("2026-01-01T00:00:00.123456Z"), Some(base));
+    }
+
+    #[test]
+    fn test_parse_iso8601_normalizes_offset_to_utc() {
+        let utc = parse_iso8601("2026-01-01T12:00:00.000Z").unwrap();
+        assert_eq!(parse_iso8601("2026-01-01T14:00:00.000+02:00"), Some(utc));
+        assert_eq!(parse_iso8601("2026-01-01T07:00:00.000-05:00"), Some(utc));
+    }
+
+    #[test]
+    fn test_parse_iso8601_rejects_malformed_input() {
+        assert_eq!(parse_iso8601("not a date"), None);
+        assert_eq!(parse_iso8601("2026-13-01T00:00:00.000Z"), None); // 月份越界
+        assert_eq!(parse_iso8601("2026-02-30T00:00:00.000Z"), None); // 2026 非闰年，没有 2 月 30 日
+        assert_eq!(parse_iso8601("2026-01-01T25:00:00.000Z"), None); // 小时越界
+        assert_eq!(parse_iso8601("2026-01-01T00:00:00.000+25:00"), None); // 偏移小时越界
+        assert_eq!(parse_iso8601("2026-01-01 00:00:00.000Z"), None); // 缺少 'T' 分隔符
+    }
+
+    #[test]
+    fn test_graph_age_secs() {
+        let mut meta = MetaInfo {
+            last_scan_at: chrono_now(),
+            commit_hash: None,
+            scan_duration: 0,
+            file_hashes: BTreeMap::new(),
+            file_stats: BTreeMap::new(),
+        };
+        assert!(graph_age_secs(&meta).unwrap() <= 1);
+
+        meta.last_scan_at = "not a date".to_string();
+        assert_eq!(graph_age_secs(&meta), None);
+    }
+
+    #[test]
+    fn test_export_dot_highlights_entry_point_module() {
+        let graph = make_module_graph();
+        let dot = export_dot(&graph);
+        assert!(dot.starts_with("digraph codemap {\n"));
+        assert!(dot.contains("\"app\" [label=\"app\\n1 lines\", fontsize=12, style=filled, fillcolor=lightgreen];"));
+        assert!(dot.contains("\"utils\" [label=\"utils\\n0 lines\", fontsize=10];"));
+        assert!(dot.contains("\"app\" -> \"utils\";"));
+    }
+
+    #[test]
+    fn test_export_json_graph_builds_nodes_and_links() {
+        let graph = make_module_graph();
+        let node_link = export_json_graph(&graph);
+        assert_eq!(node_link.nodes.len(), 2);
+        let app = node_link.nodes.iter().find(|n| n.id == "app").unwrap();
+        assert!(app.is_entry_point);
+        assert_eq!(app.file_count, 1);
+        assert_eq!(app.stats.total_lines, 1);
+        let utils = node_link.nodes.iter().find(|n| n.id == "utils").unwrap();
+        assert!(!utils.is_entry_point);
+        assert_eq!(utils.stats.total_lines, 0);
+        assert_eq!(node_link.links.len(), 1);
+        assert_eq!(node_link.links[0].source, "app");
+        assert_eq!(node_link.links[0].target, "utils");
+    }
 }
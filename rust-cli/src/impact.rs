@@ -1,4 +1,5 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
 
 use crate::graph::{CodeGraph, ModuleEntry};
 
@@ -7,16 +8,31 @@ use crate::graph::{CodeGraph, ModuleEntry};
 pub struct ImpactResult {
     pub target_type: TargetType,
     pub target_module: String,
+    /// 仅当 `target_type` 为 `TargetType::Function` 时有值：被分析的函数名
+    /// （Rust 方法为 `Type::method` 形式，与 `FunctionInfo::name` 一致）
+    pub target_function: Option<String>,
     pub direct_dependants: Vec<String>,
     pub transitive_dependants: Vec<String>,
     pub impacted_modules: Vec<String>,
     pub impacted_files: Vec<String>,
+    /// 若目标模块处于一个循环依赖环中，给出该环的全部成员（含目标自身）；
+    /// 非空时说明上面的传递依赖方列表可能不完整——环内模块互相依赖，
+    /// "谁影响谁" 已经不是单向的 BFS 能完整描述的关系。函数粒度分析（见
+    /// `TargetType::Function`）不走模块依赖图，恒为 `None`。
+    pub cycle_warning: Option<Vec<String>>,
+    /// target 既不是已知模块、文件，也不是任何函数名时，按编辑距离从模块名和文件
+    /// basename 里挑出的"是不是想输入……"候选，升序排列；target 能正常解析时恒为空
+    pub suggestions: Vec<String>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum TargetType {
     Module,
     File,
+    /// target 精确匹配到某个函数/方法名（见 [`FunctionInfo::name`](crate::graph::FunctionInfo)）。
+    /// 这种情况下 dependants 由 `calls` 边（见 [`crate::languages::CallInfo`]）算出，
+    /// 是函数调用方，不是模块级别的 import 依赖方
+    Function,
 }
 
 impl TargetType {
@@ -24,17 +40,61 @@ impl TargetType {
         match self {
             TargetType::Module => "module",
             TargetType::File => "file",
+            TargetType::Function => "function",
         }
     }
 }
 
-/// 分析修改某个模块或文件的影响范围。
+/// 分析修改某个模块、文件或函数的影响范围。
 ///
-/// target 可以是模块名或文件路径（支持部分匹配）。
+/// target 可以是模块名、文件路径（支持部分匹配）或函数/方法名。函数名匹配优先级
+/// 最低——只有在它既不是模块名、也匹配不到任何文件路径时才会尝试。
 /// max_depth 控制 BFS 最大深度（默认 3）。
 pub fn analyze_impact(graph: &CodeGraph, target: &str, max_depth: u32) -> ImpactResult {
-    // 1. 确定目标类型和所属模块
-    let (target_type, target_module) = resolve_target(graph, target);
+    // 1. 确定目标类型和所属模块（函数目标额外带上函数名）
+    let (target_type, target_module, target_function) = resolve_target(graph, target);
+
+    if target_type == TargetType::Function {
+        let function_name = target_function.clone().unwrap_or_default();
+        let callers_of = build_call_graph(graph);
+        let (direct_dependants, transitive_dependants) =
+            bfs_function_callers(&callers_of, &function_name, max_depth);
+
+        // 受影响函数 = 目标自身 + 所有直接/传递调用方；按函数名反查包含它们的文件/模块
+        let mut impacted_names: HashSet<&str> = HashSet::new();
+        impacted_names.insert(function_name.as_str());
+        impacted_names.extend(direct_dependants.iter().map(String::as_str));
+        impacted_names.extend(transitive_dependants.iter().map(String::as_str));
+
+        let mut impacted_modules: HashSet<String> = HashSet::new();
+        let mut impacted_files: Vec<String> = Vec::new();
+        for (path, file) in &graph.files {
+            if file.functions.iter().any(|f| impacted_names.contains(f.name.as_str())) {
+                impacted_files.push(path.clone());
+                impacted_modules.insert(file.module.clone());
+            }
+        }
+        impacted_files.sort();
+        let mut impacted_modules: Vec<String> = impacted_modules.into_iter().collect();
+        impacted_modules.sort();
+
+        return ImpactResult {
+            target_type,
+            target_module,
+            target_function: Some(function_name),
+            direct_dependants,
+            transitive_dependants,
+            impacted_modules,
+            impacted_files,
+            cycle_warning: None,
+            suggestions: Vec::new(),
+        };
+    }
+
+    // target 既不是模块、文件，也不是函数名——resolve_target 落到兜底分支时
+    // target_module 恒等于原始 target 本身，且不是一个真实存在的模块名
+    let target_found = graph.modules.contains_key(&target_module);
+    let suggestions = if target_found { Vec::new() } else { suggest_targets(graph, target) };
 
     // 2. 直接依赖方
     let direct_dependants = match graph.modules.get(&target_module) {
@@ -57,65 +117,373 @@ pub fn analyze_impact(graph: &CodeGraph, target: &str, max_depth: u32) -> Impact
         .collect();
     impacted_files.sort();
 
+    // 6. 若目标模块身处循环依赖环中，标记出来提醒调用方结果可能不完整
+    let cycle_warning = detect_cycles(graph)
+        .into_iter()
+        .find(|scc| scc.contains(&target_module));
+
     ImpactResult {
         target_type,
         target_module,
+        target_function: None,
         direct_dependants,
         transitive_dependants,
         impacted_modules,
         impacted_files,
+        cycle_warning,
+        suggestions,
+    }
+}
+
+/// 给解析不到任何模块/文件/函数的 target 找"是不是想输入……"候选
+///
+/// 对比对象是每个模块 id 和每个文件的 basename（不含目录，`core/mod.rs` 只比较
+/// `mod.rs`），按 [`lev_distance`] 升序排列；距离超过 `max(3, target.len() / 3)`
+/// 的候选视为不相关，直接丢弃——阈值跟着 target 长度走，避免给一个三五个字符的
+/// target 硬塞一堆八竿子打不着的长名字。做法借用 cargo 给拼错的子命令提建议时
+/// 用的 `lev_distance` 思路。
+fn suggest_targets(graph: &CodeGraph, target: &str) -> Vec<String> {
+    let threshold = (target.len() / 3).max(3);
+
+    let mut candidates: HashSet<&str> = graph.modules.keys().map(String::as_str).collect();
+    for path in graph.files.keys() {
+        let basename = path.rsplit('/').next().unwrap_or(path);
+        candidates.insert(basename);
+    }
+
+    let mut scored: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .map(|candidate| (lev_distance(target, candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .collect();
+    scored.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+    scored.into_iter().map(|(_, name)| name.to_string()).collect()
+}
+
+/// 经典 Wagner-Fischer 编辑距离（插入/删除/替换代价均为 1），cargo 提示拼错子命令
+/// 用的就是这一套，这里按同样的思路套用到模块名/文件名上
+fn lev_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() {
+        return b.len();
+    }
+    if b.is_empty() {
+        return a.len();
+    }
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0usize; b.len() + 1];
+    for i in 1..=a.len() {
+        curr[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// 把所有文件的 `calls` 边聚合成 `callee → callers` 表，供函数粒度的影响分析使用
+///
+/// 按函数名聚合，不带文件路径——见 [`crate::graph::CallInfo`] 的文档注释，
+/// 跨文件同名函数目前无法区分
+fn build_call_graph(graph: &CodeGraph) -> HashMap<String, HashSet<String>> {
+    let mut callers_of: HashMap<String, HashSet<String>> = HashMap::new();
+    for file in graph.files.values() {
+        for call in &file.calls {
+            callers_of.entry(call.callee.clone()).or_default().insert(call.caller.clone());
+        }
+    }
+    callers_of
+}
+
+/// 在 `callers_of`（callee → callers）上做 BFS，返回 `start` 的直接调用方和
+/// （不含直接调用方的）传递调用方，深度不超过 `max_depth`
+fn bfs_function_callers(
+    callers_of: &HashMap<String, HashSet<String>>,
+    start: &str,
+    max_depth: u32,
+) -> (Vec<String>, Vec<String>) {
+    let mut direct: Vec<String> = callers_of
+        .get(start)
+        .map(|s| s.iter().cloned().collect())
+        .unwrap_or_default();
+    direct.sort();
+
+    let mut visited: HashSet<String> = HashSet::new();
+    visited.insert(start.to_string());
+    visited.extend(direct.iter().cloned());
+
+    let mut transitive: Vec<String> = Vec::new();
+    let mut frontier = direct.clone();
+    let mut depth = 1;
+    while depth < max_depth && !frontier.is_empty() {
+        let mut next = Vec::new();
+        for node in &frontier {
+            if let Some(callers) = callers_of.get(node) {
+                for caller in callers {
+                    if visited.insert(caller.clone()) {
+                        transitive.push(caller.clone());
+                        next.push(caller.clone());
+                    }
+                }
+            }
+        }
+        frontier = next;
+        depth += 1;
+    }
+
+    transitive.sort();
+    (direct, transitive)
+}
+
+/// 用迭代版 Tarjan 算法在 `depends_on` 边上找出所有强连通分量（循环依赖组）
+///
+/// `rebuild_dependencies` 只负责建边，从不检查环，而 [`analyze_impact`] 的 BFS
+/// 默认图是无环的——这个函数补上这个缺口。用显式栈模拟递归，保持一个单调递增的
+/// `index` 计数器、每个模块的 `index`/`lowlink`、一个带 `on_stack` 集合的显式栈：
+/// 每碰到未访问模块就压入一帧，沿 `depends_on` 边探索邻居，邻居未访问过就递归
+/// 探索并在回溯时用 `lowlink[w]` 收紧 `lowlink[v]`，邻居已在栈上就直接用它的
+/// `index[w]` 收紧；当 `lowlink[v] == index[v]` 时从栈顶弹出到 `v`，这些模块就是
+/// 一个强连通分量。只返回成员数大于一、或存在自环的分量——单个模块且无自环不算
+/// "循环依赖"。
+pub fn detect_cycles(graph: &CodeGraph) -> Vec<Vec<String>> {
+    struct Frame {
+        node: String,
+        neighbor_idx: usize,
+    }
+
+    let mut counter = 0usize;
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut lowlink: HashMap<String, usize> = HashMap::new();
+    let mut on_stack: HashSet<String> = HashSet::new();
+    let mut stack: Vec<String> = Vec::new();
+    let mut sccs: Vec<Vec<String>> = Vec::new();
+
+    // 排序只是为了让结果在相同输入下确定性地一致，不影响算法正确性
+    let mut module_names: Vec<String> = graph.modules.keys().cloned().collect();
+    module_names.sort();
+
+    for start in &module_names {
+        if index.contains_key(start) {
+            continue;
+        }
+
+        index.insert(start.clone(), counter);
+        lowlink.insert(start.clone(), counter);
+        counter += 1;
+        stack.push(start.clone());
+        on_stack.insert(start.clone());
+        let mut frames = vec![Frame { node: start.clone(), neighbor_idx: 0 }];
+
+        while let Some(frame) = frames.last_mut() {
+            let v = frame.node.clone();
+            let neighbors: Vec<String> = graph
+                .modules
+                .get(&v)
+                .map(|m| m.depends_on.clone())
+                .unwrap_or_default();
+
+            if frame.neighbor_idx < neighbors.len() {
+                let w = neighbors[frame.neighbor_idx].clone();
+                frame.neighbor_idx += 1;
+
+                if !index.contains_key(&w) {
+                    index.insert(w.clone(), counter);
+                    lowlink.insert(w.clone(), counter);
+                    counter += 1;
+                    stack.push(w.clone());
+                    on_stack.insert(w.clone());
+                    frames.push(Frame { node: w, neighbor_idx: 0 });
+                } else if on_stack.contains(&w) {
+                    let w_index = index[&w];
+                    if w_index < lowlink[&v] {
+                        lowlink.insert(v.clone(), w_index);
+                    }
+                }
+                continue;
+            }
+
+            frames.pop();
+            if let Some(parent_frame) = frames.last() {
+                let parent = parent_frame.node.clone();
+                let v_low = lowlink[&v];
+                if v_low < lowlink[&parent] {
+                    lowlink.insert(parent, v_low);
+                }
+            }
+
+            if lowlink[&v] == index[&v] {
+                let mut scc = Vec::new();
+                loop {
+                    let w = stack.pop().expect("node pushed before being closed");
+                    on_stack.remove(&w);
+                    scc.push(w.clone());
+                    if w == v {
+                        break;
+                    }
+                }
+                let is_self_loop = scc.len() == 1
+                    && graph.modules.get(&v).map(|m| m.depends_on.contains(&v)).unwrap_or(false);
+                if scc.len() > 1 || is_self_loop {
+                    scc.sort();
+                    sccs.push(scc);
+                }
+            }
+        }
+    }
+
+    sccs.sort();
+    sccs
+}
+
+/// 找出 `a`、`b` 两个模块在 `depends_on` 有向无环图里共同依赖的“最深”上游模块——
+/// 即两者都依赖、但不会被其他公共依赖模块进一步依赖的那些模块。改一下这些模块
+/// 会同时影响 `a` 和 `b`，是两者共享的最具体基础代码。
+///
+/// 先分别从 `a`、`b` 出发沿 `depends_on` 边做闭包遍历，取交集得到所有公共祖先；
+/// 再把其中仍是另一个公共祖先之（传递）上游的模块剔除，只留下这条“公共祖先”链
+/// 里最靠下游（离 `a`/`b` 最近）的那些节点——即最大前沿。若 `a` 本身依赖 `b`
+/// （或反之），`b`（或 `a`）自己就是唯一幸存者。目标解析不到时返回空，
+/// 与 [`resolve_target`] 对“找不到”的处理方式一致。
+pub fn shared_dependencies(graph: &CodeGraph, a: &str, b: &str) -> Vec<String> {
+    let (_, a_module, _) = resolve_target(graph, a);
+    let (_, b_module, _) = resolve_target(graph, b);
+
+    if !graph.modules.contains_key(&a_module) || !graph.modules.contains_key(&b_module) {
+        return Vec::new();
+    }
+
+    let reach_a = depends_on_closure(&graph.modules, &a_module);
+    let reach_b = depends_on_closure(&graph.modules, &b_module);
+
+    let common: Vec<String> = reach_a.intersection(&reach_b).cloned().collect();
+
+    // 只保留最大前沿：丢弃那些本身是另一个公共祖先之(传递)上游的模块
+    let mut maximal: Vec<String> = common
+        .iter()
+        .filter(|candidate| {
+            !common.iter().any(|other| {
+                other != *candidate && depends_on_closure(&graph.modules, other).contains(*candidate)
+            })
+        })
+        .cloned()
+        .collect();
+
+    maximal.sort();
+    maximal
+}
+
+/// 沿 `depends_on` 边做闭包遍历，返回 `start` 自身及其所有传递依赖
+fn depends_on_closure(modules: &HashMap<String, ModuleEntry>, start: &str) -> HashSet<String> {
+    let mut seen = HashSet::new();
+    seen.insert(start.to_string());
+    let mut stack = vec![start.to_string()];
+    while let Some(current) = stack.pop() {
+        if let Some(entry) = modules.get(&current) {
+            for dep in &entry.depends_on {
+                if seen.insert(dep.clone()) {
+                    stack.push(dep.clone());
+                }
+            }
+        }
     }
+    seen
 }
 
-fn resolve_target(graph: &CodeGraph, target: &str) -> (TargetType, String) {
+fn resolve_target(graph: &CodeGraph, target: &str) -> (TargetType, String, Option<String>) {
     // 优先匹配模块名
     if graph.modules.contains_key(target) {
-        return (TargetType::Module, target.to_string());
+        return (TargetType::Module, target.to_string(), None);
     }
 
     // 精确文件路径匹配
     if let Some(file) = graph.files.get(target) {
-        return (TargetType::File, file.module.clone());
+        return (TargetType::File, file.module.clone(), None);
     }
 
     // 部分文件路径匹配
     if let Some(matched) = graph.files.keys().find(|f| f.contains(target)) {
         let module = graph.files[matched].module.clone();
-        return (TargetType::File, module);
+        return (TargetType::File, module, None);
+    }
+
+    // 函数/方法名精确匹配——优先级最低，只有模块名和文件路径都匹配不到时才尝试
+    for file in graph.files.values() {
+        if let Some(func) = file.functions.iter().find(|f| f.name == target) {
+            return (TargetType::Function, file.module.clone(), Some(func.name.clone()));
+        }
     }
 
     // 未找到 — 返回空结果
-    (TargetType::Module, target.to_string())
+    (TargetType::Module, target.to_string(), None)
 }
 
-/// BFS 遍历 dependedBy 边，返回所有传递依赖方（不含起始模块），按名称排序。
-fn bfs_dependants(
-    modules: &HashMap<String, ModuleEntry>,
-    start: &str,
-    max_depth: u32,
-) -> Vec<String> {
-    let mut visited: HashSet<String> = HashSet::new();
-    visited.insert(start.to_string());
+/// 按深度非递减顺序惰性产出 `(module, depth)` 的 dependedBy 遍历器
+///
+/// 用 `BinaryHeap<Reverse<(depth, module)>>` 代替普通队列：离起点最近的模块总是
+/// 先弹出，即使图中存在多条不同长度的路径通向同一个模块。可选的 `stop_set` 中的
+/// 模块会被产出但不再展开其 `depended_by` 边——调用方可以用它圈出已知边界（比如
+/// 不想让遍历越过的 test-only 模块）。调用方可以对这个迭代器 `take_while`/提前
+/// `break`，不必像以前那样先把整个传递闭包物化成 `Vec` 才能看第一个结果。
+pub struct Dependants<'a> {
+    modules: &'a HashMap<String, ModuleEntry>,
+    stop_set: Option<&'a HashSet<String>>,
+    heap: BinaryHeap<Reverse<(u32, String)>>,
+    seen: HashSet<String>,
+}
 
-    let mut result: Vec<String> = Vec::new();
-    // (module_name, current_depth)
-    let mut queue: VecDeque<(String, u32)> = VecDeque::new();
-    queue.push_back((start.to_string(), 0));
+impl<'a> Dependants<'a> {
+    pub fn new(
+        modules: &'a HashMap<String, ModuleEntry>,
+        start: &str,
+        stop_set: Option<&'a HashSet<String>>,
+    ) -> Self {
+        let mut seen = HashSet::new();
+        seen.insert(start.to_string());
+        let mut heap = BinaryHeap::new();
+        heap.push(Reverse((0u32, start.to_string())));
+        Self { modules, stop_set, heap, seen }
+    }
+}
 
-    while let Some((current, depth)) = queue.pop_front() {
-        if depth >= max_depth {
-            continue;
-        }
-        let Some(mod_entry) = modules.get(&current) else {
-            continue;
-        };
-        for dep in &mod_entry.depended_by {
-            if visited.insert(dep.clone()) {
-                result.push(dep.clone());
-                queue.push_back((dep.clone(), depth + 1));
+impl<'a> Iterator for Dependants<'a> {
+    type Item = (String, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((depth, current)) = self.heap.pop()?;
+
+        let fenced = self.stop_set.is_some_and(|s| s.contains(&current));
+        if !fenced {
+            if let Some(entry) = self.modules.get(&current) {
+                for dep in &entry.depended_by {
+                    if self.seen.insert(dep.clone()) {
+                        self.heap.push(Reverse((depth + 1, dep.clone())));
+                    }
+                }
             }
         }
+
+        Some((current, depth))
     }
+}
+
+/// 遍历 dependedBy 边，返回所有传递依赖方（不含起始模块），按名称排序。
+///
+/// 基于 [`Dependants`]：跳过起始模块自身那一项，然后在深度超过 `max_depth` 时
+/// 借助堆遍历的非递减深度顺序用 `take_while` 提前停止。
+fn bfs_dependants(
+    modules: &HashMap<String, ModuleEntry>,
+    start: &str,
+    max_depth: u32,
+) -> Vec<String> {
+    let mut result: Vec<String> = Dependants::new(modules, start, None)
+        .skip(1)
+        .take_while(|(_, depth)| *depth <= max_depth)
+        .map(|(module, _)| module)
+        .collect();
 
     result.sort();
     result
@@ -143,6 +511,10 @@ mod tests {
                 files: vec!["src/core/mod.rs".to_string()],
                 depends_on: vec![],
                 depended_by: vec!["utils".to_string(), "app".to_string()],
+            
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
             },
         );
         modules.insert(
@@ -151,6 +523,10 @@ mod tests {
                 files: vec!["src/utils/mod.rs".to_string()],
                 depends_on: vec!["core".to_string()],
                 depended_by: vec!["app".to_string()],
+            
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
             },
         );
         modules.insert(
@@ -159,6 +535,10 @@ mod tests {
                 files: vec!["src/main.rs".to_string()],
                 depends_on: vec!["core".to_string(), "utils".to_string()],
                 depended_by: vec![],
+            
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
             },
         );
 
@@ -170,12 +550,22 @@ mod tests {
                 module: "core".to_string(),
                 hash: "sha256:abc".to_string(),
                 lines: 10,
+                code_lines: 8,
+                comment_lines: 0,
+                blank_lines: 2,
                 functions: vec![],
                 classes: vec![],
                 types: vec![],
                 imports: vec![],
                 exports: vec![],
+                reexports: vec![],
+                resolved_reexports: vec![],
+                calls: vec![],
                 is_entry_point: false,
+                entry_point_reason: None,
+                resolved_imports: vec![],
+                imported_by: vec![],
+                parse_diagnostics: vec![],
             },
         );
 
@@ -190,17 +580,25 @@ mod tests {
             config: GraphConfig {
                 languages: vec![],
                 exclude_patterns: vec![],
+                include_patterns: vec![],
             },
             summary: GraphSummary {
                 total_files: 3,
                 total_functions: 0,
                 total_classes: 0,
+                total_code_lines: 0,
+                total_comment_lines: 0,
+                total_blank_lines: 0,
                 languages: HashMap::new(),
                 modules: vec!["core".to_string(), "utils".to_string(), "app".to_string()],
                 entry_points: vec![],
+                complexity_hotspots: vec![],
+                circular_dependencies: vec![],
+                total_parse_diagnostics: 0,
             },
             modules,
             files,
+            include_diagnostics: vec![],
         }
     }
 
@@ -263,6 +661,35 @@ mod tests {
         assert!(result.impacted_files.is_empty());
     }
 
+    #[test]
+    fn test_impact_unknown_target_suggests_closest_module_name() {
+        let graph = make_graph();
+        let result = analyze_impact(&graph, "coer", 3);
+        assert!(result.suggestions.contains(&"core".to_string()));
+    }
+
+    #[test]
+    fn test_impact_known_target_has_no_suggestions() {
+        let graph = make_graph();
+        let result = analyze_impact(&graph, "core", 3);
+        assert!(result.suggestions.is_empty());
+    }
+
+    #[test]
+    fn test_impact_unknown_target_far_from_everything_has_no_suggestions() {
+        let graph = make_graph();
+        let result = analyze_impact(&graph, "zzzzzzzzzzzzzzzzzzzz", 3);
+        assert!(result.suggestions.is_empty());
+    }
+
+    #[test]
+    fn test_lev_distance_basic_cases() {
+        assert_eq!(lev_distance("core", "core"), 0);
+        assert_eq!(lev_distance("cor", "core"), 1);
+        assert_eq!(lev_distance("atuh", "auth"), 2);
+        assert_eq!(lev_distance("", "abc"), 3);
+    }
+
     #[test]
     fn test_bfs_depth_limit() {
         let graph = make_graph();
@@ -271,6 +698,174 @@ mod tests {
         assert!(result.transitive_dependants.is_empty());
     }
 
+    #[test]
+    fn test_detect_cycles_none_on_acyclic_graph() {
+        let graph = make_graph();
+        assert!(detect_cycles(&graph).is_empty());
+    }
+
+    #[test]
+    fn test_detect_cycles_finds_mutual_dependency() {
+        let mut graph = make_graph();
+        // 让 core 也依赖 app，形成 core <-> app <-> utils 的环
+        graph.modules.get_mut("core").unwrap().depends_on.push("app".to_string());
+        graph.modules.get_mut("app").unwrap().depended_by.push("core".to_string());
+
+        let cycles = detect_cycles(&graph);
+        assert_eq!(cycles.len(), 1);
+        let mut members = cycles[0].clone();
+        members.sort();
+        assert_eq!(members, vec!["app".to_string(), "core".to_string(), "utils".to_string()]);
+    }
+
+    #[test]
+    fn test_detect_cycles_finds_self_loop() {
+        let mut graph = make_graph();
+        graph.modules.get_mut("core").unwrap().depends_on.push("core".to_string());
+
+        let cycles = detect_cycles(&graph);
+        assert_eq!(cycles, vec![vec!["core".to_string()]]);
+    }
+
+    #[test]
+    fn test_impact_sets_cycle_warning_when_target_in_cycle() {
+        let mut graph = make_graph();
+        graph.modules.get_mut("core").unwrap().depends_on.push("app".to_string());
+        graph.modules.get_mut("app").unwrap().depended_by.push("core".to_string());
+
+        let result = analyze_impact(&graph, "core", 3);
+        assert!(result.cycle_warning.is_some());
+        let mut members = result.cycle_warning.unwrap();
+        members.sort();
+        assert_eq!(members, vec!["app".to_string(), "core".to_string(), "utils".to_string()]);
+    }
+
+    #[test]
+    fn test_impact_no_cycle_warning_on_acyclic_graph() {
+        let graph = make_graph();
+        let result = analyze_impact(&graph, "core", 3);
+        assert!(result.cycle_warning.is_none());
+    }
+
+    #[test]
+    fn test_dependants_yields_nondecreasing_depth() {
+        let graph = make_graph();
+        let depths: Vec<u32> = Dependants::new(&graph.modules, "core", None)
+            .map(|(_, depth)| depth)
+            .collect();
+        assert!(depths.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(depths[0], 0); // 起始模块自身先被产出
+    }
+
+    #[test]
+    fn test_dependants_stop_set_fences_off_expansion() {
+        // core <- utils <- app，utils 是唯一通往 app 的路径
+        let mut modules = HashMap::new();
+        modules.insert(
+            "core".to_string(),
+            ModuleEntry { files: vec![], depends_on: vec![], depended_by: vec!["utils".to_string()], code_lines: 0, comment_lines: 0, blank_lines: 0 },
+        );
+        modules.insert(
+            "utils".to_string(),
+            ModuleEntry {
+                files: vec![],
+                depends_on: vec!["core".to_string()],
+                depended_by: vec!["app".to_string()],
+            
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
+            },
+        );
+        modules.insert(
+            "app".to_string(),
+            ModuleEntry { files: vec![], depends_on: vec!["utils".to_string()], depended_by: vec![], code_lines: 0, comment_lines: 0, blank_lines: 0 },
+        );
+
+        // 把 utils 圈进 stop_set 后，app 不应再被探出
+        let mut stop_set = HashSet::new();
+        stop_set.insert("utils".to_string());
+        let reached: Vec<String> = Dependants::new(&modules, "core", Some(&stop_set))
+            .map(|(module, _)| module)
+            .collect();
+        assert!(reached.contains(&"utils".to_string()));
+        assert!(!reached.contains(&"app".to_string()));
+
+        // 不设 stop_set 时应该能一路探到 app
+        let reached_unfenced: Vec<String> =
+            Dependants::new(&modules, "core", None).map(|(module, _)| module).collect();
+        assert!(reached_unfenced.contains(&"app".to_string()));
+    }
+
+    #[test]
+    fn test_dependants_can_stop_early_without_materializing() {
+        let graph = make_graph();
+        let first = Dependants::new(&graph.modules, "core", None).next().unwrap();
+        assert_eq!(first, ("core".to_string(), 0));
+    }
+
+    #[test]
+    fn test_shared_dependencies_direct_relation_is_the_gca() {
+        let graph = make_graph();
+        // app depends_on [core, utils], utils depends_on [core] -> app 和 utils
+        // 唯一的公共祖先链是 utils -> core，最大前沿只留下离两者更近的 utils
+        assert_eq!(shared_dependencies(&graph, "app", "utils"), vec!["utils".to_string()]);
+    }
+
+    #[test]
+    fn test_shared_dependencies_keeps_closest_shared_ancestor() {
+        // x -> shared1 -> shared2, y -> shared1 -> shared2（钻石型依赖）
+        let mut modules = HashMap::new();
+        modules.insert(
+            "x".to_string(),
+            ModuleEntry { files: vec![], depends_on: vec!["shared1".to_string()], depended_by: vec![], code_lines: 0, comment_lines: 0, blank_lines: 0 },
+        );
+        modules.insert(
+            "y".to_string(),
+            ModuleEntry { files: vec![], depends_on: vec!["shared1".to_string()], depended_by: vec![], code_lines: 0, comment_lines: 0, blank_lines: 0 },
+        );
+        modules.insert(
+            "shared1".to_string(),
+            ModuleEntry { files: vec![], depends_on: vec!["shared2".to_string()], depended_by: vec![], code_lines: 0, comment_lines: 0, blank_lines: 0 },
+        );
+        modules.insert(
+            "shared2".to_string(),
+            ModuleEntry { files: vec![], depends_on: vec![], depended_by: vec![], code_lines: 0, comment_lines: 0, blank_lines: 0 },
+        );
+        let graph = graph_with_modules(modules);
+
+        // shared2 是 shared1 的上游，被公共祖先 shared1 支配，只保留最深的 shared1
+        assert_eq!(shared_dependencies(&graph, "x", "y"), vec!["shared1".to_string()]);
+    }
+
+    #[test]
+    fn test_shared_dependencies_empty_when_target_missing() {
+        let graph = make_graph();
+        assert!(shared_dependencies(&graph, "core", "nonexistent").is_empty());
+    }
+
+    #[test]
+    fn test_shared_dependencies_empty_when_no_common_ancestor() {
+        let mut modules = HashMap::new();
+        modules.insert(
+            "x".to_string(),
+            ModuleEntry { files: vec![], depends_on: vec![], depended_by: vec![], code_lines: 0, comment_lines: 0, blank_lines: 0 },
+        );
+        modules.insert(
+            "y".to_string(),
+            ModuleEntry { files: vec![], depends_on: vec![], depended_by: vec![], code_lines: 0, comment_lines: 0, blank_lines: 0 },
+        );
+        let graph = graph_with_modules(modules);
+        assert!(shared_dependencies(&graph, "x", "y").is_empty());
+    }
+
+    fn graph_with_modules(modules: HashMap<String, ModuleEntry>) -> CodeGraph {
+        let mut graph = make_graph();
+        graph.modules = modules;
+        graph.files = HashMap::new();
+        graph
+    }
+
     #[test]
     fn test_impacted_files_sorted() {
         let graph = make_graph();
@@ -282,4 +877,134 @@ mod tests {
         };
         assert_eq!(result.impacted_files, sorted);
     }
+
+    fn make_function(name: &str) -> crate::graph::FunctionInfo {
+        crate::graph::FunctionInfo {
+            name: name.to_string(),
+            signature: format!("fn {name}()"),
+            start_line: 1,
+            end_line: 2,
+            complexity: 1,
+        }
+    }
+
+    /// 调用链：`handler` 调用 `parse`，`parse` 调用 `tokenize`
+    fn make_call_graph() -> CodeGraph {
+        let mut graph = make_graph();
+        graph.files.insert(
+            "src/app/handler.rs".to_string(),
+            FileEntry {
+                language: "rust".to_string(),
+                module: "app".to_string(),
+                hash: "sha256:def".to_string(),
+                lines: 10,
+                code_lines: 8,
+                comment_lines: 0,
+                blank_lines: 2,
+                functions: vec![make_function("handler")],
+                classes: vec![],
+                types: vec![],
+                imports: vec![],
+                exports: vec![],
+                reexports: vec![],
+                resolved_reexports: vec![],
+                calls: vec![crate::graph::CallInfo {
+                    caller: "handler".to_string(),
+                    callee: "parse".to_string(),
+                    line: 5,
+                    resolved: false,
+                }],
+                is_entry_point: false,
+                entry_point_reason: None,
+                resolved_imports: vec![],
+                imported_by: vec![],
+                parse_diagnostics: vec![],
+            },
+        );
+        graph.files.insert(
+            "src/utils/parse.rs".to_string(),
+            FileEntry {
+                language: "rust".to_string(),
+                module: "utils".to_string(),
+                hash: "sha256:ghi".to_string(),
+                lines: 10,
+                code_lines: 8,
+                comment_lines: 0,
+                blank_lines: 2,
+                functions: vec![make_function("parse")],
+                classes: vec![],
+                types: vec![],
+                imports: vec![],
+                exports: vec![],
+                reexports: vec![],
+                resolved_reexports: vec![],
+                calls: vec![crate::graph::CallInfo {
+                    caller: "parse".to_string(),
+                    callee: "tokenize".to_string(),
+                    line: 3,
+                    resolved: false,
+                }],
+                is_entry_point: false,
+                entry_point_reason: None,
+                resolved_imports: vec![],
+                imported_by: vec![],
+                parse_diagnostics: vec![],
+            },
+        );
+        graph.files.insert(
+            "src/core/tokenize.rs".to_string(),
+            FileEntry {
+                language: "rust".to_string(),
+                module: "core".to_string(),
+                hash: "sha256:jkl".to_string(),
+                lines: 10,
+                code_lines: 8,
+                comment_lines: 0,
+                blank_lines: 2,
+                functions: vec![make_function("tokenize")],
+                classes: vec![],
+                types: vec![],
+                imports: vec![],
+                exports: vec![],
+                reexports: vec![],
+                resolved_reexports: vec![],
+                calls: vec![],
+                is_entry_point: false,
+                entry_point_reason: None,
+                resolved_imports: vec![],
+                imported_by: vec![],
+                parse_diagnostics: vec![],
+            },
+        );
+        graph
+    }
+
+    #[test]
+    fn test_impact_function_direct_and_transitive_callers() {
+        let graph = make_call_graph();
+        let result = analyze_impact(&graph, "tokenize", 3);
+        assert_eq!(result.target_type, TargetType::Function);
+        assert_eq!(result.target_function, Some("tokenize".to_string()));
+        assert_eq!(result.direct_dependants, vec!["parse".to_string()]);
+        assert_eq!(result.transitive_dependants, vec!["handler".to_string()]);
+        assert!(result.impacted_files.contains(&"src/app/handler.rs".to_string()));
+        assert!(result.impacted_files.contains(&"src/utils/parse.rs".to_string()));
+        assert!(result.impacted_files.contains(&"src/core/tokenize.rs".to_string()));
+    }
+
+    #[test]
+    fn test_impact_function_depth_limit() {
+        let graph = make_call_graph();
+        let result = analyze_impact(&graph, "tokenize", 1);
+        assert_eq!(result.direct_dependants, vec!["parse".to_string()]);
+        assert!(result.transitive_dependants.is_empty());
+    }
+
+    #[test]
+    fn test_impact_function_no_callers() {
+        let graph = make_call_graph();
+        let result = analyze_impact(&graph, "handler", 3);
+        assert!(result.direct_dependants.is_empty());
+        assert!(result.transitive_dependants.is_empty());
+    }
 }
@@ -1,9 +1,25 @@
 use tree_sitter::{Language, Tree};
 use super::{
-    ClassInfo, ExportInfo, FunctionInfo, ImportInfo, LanguageAdapter,
-    find_descendant_of_type, node_text, walk_nodes,
+    CallInfo, ClassInfo, ExportInfo, FunctionInfo, ImportInfo, LanguageAdapter,
+    compute_complexity, find_descendant_of_type, node_text, walk_nodes,
 };
 
+/// 嵌套函数定义/lambda 的 kind（lambda_expression 仅 C++ 会出现）：遇到时停止向下累计复杂度
+const STOP_KINDS: &[&str] = &["function_definition", "lambda_expression"];
+
+/// 判断节点是否是一个计入圈复杂度的分支节点（C 和 C++ 共用）
+fn is_branch_node(node: tree_sitter::Node, source: &[u8]) -> bool {
+    match node.kind() {
+        "if_statement" | "for_statement" | "while_statement" | "do_statement"
+        | "case_statement" | "catch_clause" | "conditional_expression" => true,
+        "binary_expression" => node
+            .child_by_field_name("operator")
+            .map(|op| matches!(node_text(op, source), "&&" | "||"))
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
 pub struct CAdapter;
 
 impl CAdapter {
@@ -32,6 +48,10 @@ impl LanguageAdapter for CAdapter {
     fn extract_classes(&self, tree: &Tree, source: &[u8]) -> Vec<ClassInfo> {
         extract_c_classes(tree, source)
     }
+
+    fn extract_calls(&self, tree: &Tree, source: &[u8]) -> Vec<CallInfo> {
+        extract_c_calls(tree, source)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -57,17 +77,69 @@ pub fn extract_c_functions(tree: &Tree, source: &[u8]) -> Vec<FunctionInfo> {
         let params = func_decl.child_by_field_name("parameters")
             .map(|p| extract_c_params(p, source))
             .unwrap_or_default();
+        let complexity = compute_complexity(node, STOP_KINDS, &mut |n| is_branch_node(n, source));
         functions.push(FunctionInfo {
             name,
             start_line: node.start_position().row + 1,
             end_line: node.end_position().row + 1,
-            params,
+            params: params.into_iter().map(super::ParamInfo::simple).collect(),
             is_exported: !is_static,
+            complexity,
+            return_type: None,
+            type_parameters: None,
+            metrics: super::compute_symbol_metrics(node, source),
+            decorators: Vec::new(),
+            doc: None,
+        });
+    });
+    functions.extend(extract_c_function_like_macros(tree, source));
+    functions
+}
+
+/// 函数式宏（`#define FOO(x) ...`）在调用方看来和真函数没有区别，所以也计入
+/// `FunctionInfo`：参数名来自 `preproc_params` 里的 `identifier` 子节点（没有类型信息，
+/// 直接复用 `ParamInfo::simple`）；宏没有函数体可供遍历分支节点，复杂度固定记 1
+fn extract_c_function_like_macros(tree: &Tree, source: &[u8]) -> Vec<FunctionInfo> {
+    let mut functions = Vec::new();
+    walk_nodes(tree.root_node(), &mut |node| {
+        if node.kind() != "preproc_function_def" {
+            return;
+        }
+        let Some(name_node) = node.child_by_field_name("name") else { return };
+        let params = node
+            .child_by_field_name("parameters")
+            .map(|p| extract_macro_params(p, source))
+            .unwrap_or_default();
+        functions.push(FunctionInfo {
+            name: node_text(name_node, source).to_string(),
+            start_line: node.start_position().row + 1,
+            end_line: node.end_position().row + 1,
+            params: params.into_iter().map(super::ParamInfo::simple).collect(),
+            is_exported: true,
+            complexity: 1,
+            return_type: None,
+            type_parameters: None,
+            metrics: super::compute_symbol_metrics(node, source),
+            decorators: Vec::new(),
+            doc: None,
         });
     });
     functions
 }
 
+/// 和 `extract_c_params` 同样的思路，但宏的形参列表（`preproc_params`）里直接是裸
+/// `identifier` 节点，没有 `parameter_declaration`/`declarator` 那层包装
+fn extract_macro_params(params_node: tree_sitter::Node, source: &[u8]) -> Vec<String> {
+    let mut params = Vec::new();
+    let mut cursor = params_node.walk();
+    for child in params_node.children(&mut cursor) {
+        if child.kind() == "identifier" {
+            params.push(node_text(child, source).to_string());
+        }
+    }
+    params
+}
+
 pub fn extract_c_includes(tree: &Tree, source: &[u8]) -> Vec<ImportInfo> {
     let mut imports = Vec::new();
     walk_nodes(tree.root_node(), &mut |node| {
@@ -88,6 +160,7 @@ pub fn extract_c_includes(tree: &Tree, source: &[u8]) -> Vec<ImportInfo> {
             source: raw,
             names: Vec::new(),
             is_default: is_system,
+            dynamic: false,
         });
     });
     imports
@@ -106,7 +179,7 @@ pub fn extract_c_exports(tree: &Tree, source: &[u8]) -> Vec<ExportInfo> {
                     if let Some(name_node) = func_decl.child_by_field_name("declarator") {
                         let name = bare_identifier(node_text(name_node, source));
                         if seen.insert(name.clone()) {
-                            exports.push(ExportInfo { name, kind: "function".into() });
+                            exports.push(ExportInfo { name, kind: "function".into(), doc: None, reexport_source: None, star: false });
                         }
                     }
                 }
@@ -118,7 +191,7 @@ pub fn extract_c_exports(tree: &Tree, source: &[u8]) -> Vec<ExportInfo> {
                 if let Some(n) = node.child_by_field_name("name") {
                     let name = node_text(n, source).to_string();
                     if seen.insert(name.clone()) {
-                        exports.push(ExportInfo { name, kind: "struct".into() });
+                        exports.push(ExportInfo { name, kind: "struct".into(), doc: None, reexport_source: None, star: false });
                     }
                 }
             }
@@ -126,7 +199,7 @@ pub fn extract_c_exports(tree: &Tree, source: &[u8]) -> Vec<ExportInfo> {
                 if let Some(n) = node.child_by_field_name("name") {
                     let name = node_text(n, source).to_string();
                     if seen.insert(name.clone()) {
-                        exports.push(ExportInfo { name, kind: "enum".into() });
+                        exports.push(ExportInfo { name, kind: "enum".into(), doc: None, reexport_source: None, star: false });
                     }
                 }
             }
@@ -134,7 +207,23 @@ pub fn extract_c_exports(tree: &Tree, source: &[u8]) -> Vec<ExportInfo> {
                 if let Some(n) = find_descendant_of_type(node, "type_identifier") {
                     let name = node_text(n, source).to_string();
                     if seen.insert(name.clone()) {
-                        exports.push(ExportInfo { name, kind: "typedef".into() });
+                        exports.push(ExportInfo { name, kind: "typedef".into(), doc: None, reexport_source: None, star: false });
+                    }
+                }
+            }
+            "preproc_def" => {
+                if let Some(n) = node.child_by_field_name("name") {
+                    let name = node_text(n, source).to_string();
+                    if seen.insert(name.clone()) {
+                        exports.push(ExportInfo { name, kind: "macro".into(), doc: None, reexport_source: None, star: false });
+                    }
+                }
+            }
+            "preproc_function_def" => {
+                if let Some(n) = node.child_by_field_name("name") {
+                    let name = node_text(n, source).to_string();
+                    if seen.insert(name.clone()) {
+                        exports.push(ExportInfo { name, kind: "macro_function".into(), doc: None, reexport_source: None, star: false });
                     }
                 }
             }
@@ -160,6 +249,10 @@ pub fn extract_c_classes(tree: &Tree, source: &[u8]) -> Vec<ClassInfo> {
                         end_line: node.end_position().row + 1,
                         methods: Vec::new(),
                         kind: kind.into(),
+                        metrics: super::compute_symbol_metrics(node, source),
+                        decorators: Vec::new(),
+                        doc: None,
+                        members: Vec::new(),
                     });
                 }
             }
@@ -169,6 +262,56 @@ pub fn extract_c_classes(tree: &Tree, source: &[u8]) -> Vec<ClassInfo> {
     classes
 }
 
+/// C/C++ 共用的调用边提取：C 适配器直接用，C++ 适配器原样复用（调用点的节点种类
+/// 在两种语法里一致，差别只在于 C++ 多出的 `field_expression`/`qualified_identifier` callee）
+pub fn extract_c_calls(tree: &Tree, source: &[u8]) -> Vec<CallInfo> {
+    let mut calls = Vec::new();
+    walk_nodes(tree.root_node(), &mut |node| {
+        if node.kind() != "call_expression" {
+            return;
+        }
+        let Some(func_node) = node.child_by_field_name("function") else { return; };
+        let Some(callee) = call_expression_callee(func_node, source) else { return; };
+        let Some(caller) = enclosing_function_name(node, source) else { return; };
+        calls.push(CallInfo {
+            caller,
+            callee,
+            line: node.start_position().row + 1,
+        });
+    });
+    calls
+}
+
+/// `call_expression` 的 `function` 字段可能是裸标识符、`field_expression`
+/// （`obj.method()`/`obj->method()`，取 `field`）或 C++ 的 `qualified_identifier`
+/// （`Ns::fn()`，取最后一段，复用 `bare_identifier` 的裁剪规则）
+fn call_expression_callee(func_node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    match func_node.kind() {
+        "identifier" => Some(node_text(func_node, source).to_string()),
+        "field_expression" => func_node
+            .child_by_field_name("field")
+            .map(|n| node_text(n, source).to_string()),
+        "qualified_identifier" => Some(bare_identifier(node_text(func_node, source))),
+        _ => None,
+    }
+}
+
+/// 从调用点向上找到最近的 `function_definition`，返回其名字（与 `extract_c_functions`
+/// 同样直接取 `function_declarator` 的 `declarator` 文本，out-of-line 的 C++ 方法因此
+/// 会带上 `Class::` 限定前缀）；不在任何函数体内则返回 `None`
+fn enclosing_function_name(node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        if n.kind() == "function_definition" {
+            let func_decl = find_descendant_of_type(n, "function_declarator")?;
+            let name_node = func_decl.child_by_field_name("declarator")?;
+            return Some(node_text(name_node, source).to_string());
+        }
+        current = n.parent();
+    }
+    None
+}
+
 fn has_storage_class_static(func_def: tree_sitter::Node, source: &[u8]) -> bool {
     let mut cursor = func_def.walk();
     for child in func_def.children(&mut cursor) {
@@ -187,7 +330,7 @@ fn bare_identifier(text: &str) -> String {
     }
 }
 
-fn extract_c_params(params_node: tree_sitter::Node, source: &[u8]) -> Vec<String> {
+pub(crate) fn extract_c_params(params_node: tree_sitter::Node, source: &[u8]) -> Vec<String> {
     let mut params = Vec::new();
     let mut cursor = params_node.walk();
     for child in params_node.children(&mut cursor) {
@@ -253,4 +396,44 @@ struct Point {
         let classes = adapter.extract_classes(&tree, src.as_bytes());
         assert!(classes.iter().any(|c| c.name == "Point" && c.kind == "struct"));
     }
+
+    #[test]
+    fn test_c_extract_object_like_macro_as_export() {
+        let src = "#define MAX_SIZE 128\n";
+        let tree = parse(src);
+        let adapter = CAdapter::new();
+        let exports = adapter.extract_exports(&tree, src.as_bytes());
+        assert!(exports.iter().any(|e| e.name == "MAX_SIZE" && e.kind == "macro"));
+    }
+
+    #[test]
+    fn test_c_extract_function_like_macro_as_export_and_function() {
+        let src = "#define SQUARE(x) ((x) * (x))\n";
+        let tree = parse(src);
+        let adapter = CAdapter::new();
+        let exports = adapter.extract_exports(&tree, src.as_bytes());
+        assert!(exports.iter().any(|e| e.name == "SQUARE" && e.kind == "macro_function"));
+
+        let fns = adapter.extract_functions(&tree, src.as_bytes());
+        let square = fns.iter().find(|f| f.name == "SQUARE").expect("SQUARE should appear as a function");
+        assert!(square.is_exported);
+        assert_eq!(square.params.len(), 1);
+        assert_eq!(square.params[0].name, "x");
+    }
+
+    #[test]
+    fn test_c_extract_calls() {
+        let src = r#"
+void helper() {}
+
+int main() {
+    helper();
+    return 0;
+}
+"#;
+        let tree = parse(src);
+        let adapter = CAdapter::new();
+        let calls = adapter.extract_calls(&tree, src.as_bytes());
+        assert!(calls.iter().any(|c| c.caller == "main" && c.callee == "helper"));
+    }
 }
@@ -1,9 +1,9 @@
 use tree_sitter::{Language, Tree};
 use super::{
-    ClassInfo, ExportInfo, FunctionInfo, ImportInfo, LanguageAdapter,
-    find_descendant_of_type, node_text, walk_nodes,
+    CallInfo, ClassInfo, ExportInfo, FunctionInfo, ImportInfo, LanguageAdapter,
+    find_child_of_type, find_descendant_of_type, node_text, walk_nodes,
 };
-use super::c_lang::{extract_c_includes, extract_c_functions, extract_c_exports, extract_c_classes};
+use super::c_lang::{extract_c_includes, extract_c_functions, extract_c_exports, extract_c_classes, extract_c_calls};
 
 pub struct CppAdapter;
 
@@ -33,17 +33,18 @@ impl LanguageAdapter for CppAdapter {
 
     fn extract_classes(&self, tree: &Tree, source: &[u8]) -> Vec<ClassInfo> {
         let mut classes = extract_c_classes(tree, source);
-        // 额外处理 C++ 类方法
+        // 额外处理 C++ 类/结构体方法（struct 同样可以带成员函数和 access_specifier）
         walk_nodes(tree.root_node(), &mut |node| {
-            if node.kind() != "class_specifier" {
+            if node.kind() != "class_specifier" && node.kind() != "struct_specifier" {
                 return;
             }
             if node.child_by_field_name("body").is_none() {
                 return;
             }
+            let expected_kind = if node.kind() == "class_specifier" { "class" } else { "struct" };
             if let Some(name_node) = node.child_by_field_name("name") {
                 let class_name = node_text(name_node, source).to_string();
-                if let Some(ci) = classes.iter_mut().find(|c| c.name == class_name && c.kind == "class") {
+                if let Some(ci) = classes.iter_mut().find(|c| c.name == class_name && c.kind == expected_kind) {
                     ci.methods = extract_cpp_methods(node, source);
                 }
             }
@@ -63,6 +64,10 @@ impl LanguageAdapter for CppAdapter {
                     end_line: node.end_position().row + 1,
                     methods: Vec::new(),
                     kind: "enum".into(),
+                    metrics: super::compute_symbol_metrics(node, source),
+                    decorators: Vec::new(),
+                    doc: None,
+                    members: Vec::new(),
                 });
             }
         });
@@ -78,27 +83,180 @@ impl LanguageAdapter for CppAdapter {
                     end_line: node.end_position().row + 1,
                     methods: Vec::new(),
                     kind: "namespace".into(),
+                    metrics: super::compute_symbol_metrics(node, source),
+                    decorators: Vec::new(),
+                    doc: None,
+                    members: Vec::new(),
                 });
             }
         });
+        // 模板类/结构体被 tree-sitter 包在 template_declaration 节点里，
+        // 上面按 class_specifier/struct_specifier 直接匹配的两趟都够不到它们，单独解包一次
+        walk_nodes(tree.root_node(), &mut |node| {
+            if node.kind() != "template_declaration" {
+                return;
+            }
+            let inner = match find_child_of_type(node, "class_specifier")
+                .or_else(|| find_child_of_type(node, "struct_specifier"))
+            {
+                Some(n) => n,
+                None => return,
+            };
+            if inner.child_by_field_name("body").is_none() {
+                return;
+            }
+            let name_node = match inner.child_by_field_name("name") {
+                Some(n) => n,
+                None => return,
+            };
+            let name = node_text(name_node, source).to_string();
+            if classes.iter().any(|c| c.name == name) {
+                return;
+            }
+            let kind = if inner.kind() == "class_specifier" { "class" } else { "struct" };
+            classes.push(ClassInfo {
+                name,
+                start_line: inner.start_position().row + 1,
+                end_line: inner.end_position().row + 1,
+                methods: Vec::new(),
+                kind: kind.into(),
+                metrics: super::compute_symbol_metrics(inner, source),
+                decorators: Vec::new(),
+                doc: None,
+                members: Vec::new(),
+            });
+        });
+        // 类外定义的方法（`void Engine::start() {}`）挂到对应类上
+        attribute_out_of_line_methods(&mut classes, tree, source);
         classes
     }
+
+    fn extract_calls(&self, tree: &Tree, source: &[u8]) -> Vec<CallInfo> {
+        // 调用点的节点种类与 C 一致，`qualified_identifier`/`field_expression` callee
+        // 本来就是 C++ 专属情形，复用同一套提取逻辑
+        extract_c_calls(tree, source)
+    }
 }
 
-fn extract_cpp_methods(class_node: tree_sitter::Node, source: &[u8]) -> Vec<String> {
+/// 只看类体（`field_declaration_list`）的直接子节点，顺着遇到的 `access_specifier`
+/// （`public:`/`private:`/`protected:`）切换当前访问级别——`class` 默认 private、
+/// `struct` 默认 public，和实际 C++ 语义一致。内联定义（`function_definition`，有函数体）
+/// 和纯声明（`field_declaration` 的 declarator 是 `function_declarator`，比如只有
+/// `void start();` 没有函数体）都记一条方法；不递归进嵌套的 class/struct，避免把内部类的
+/// 方法错记成外层类的
+fn extract_cpp_methods(class_node: tree_sitter::Node, source: &[u8]) -> Vec<super::MethodInfo> {
     let mut methods = Vec::new();
-    walk_nodes(class_node, &mut |node| {
-        if node.kind() == "function_definition" {
-            if let Some(func_decl) = find_descendant_of_type(node, "function_declarator") {
-                if let Some(name_node) = func_decl.child_by_field_name("declarator") {
-                    methods.push(node_text(name_node, source).to_string());
+    let Some(body) = class_node.child_by_field_name("body") else { return methods };
+    let default_access = if class_node.kind() == "struct_specifier" { "public" } else { "private" };
+    let mut access = default_access;
+    let mut cursor = body.walk();
+    for child in body.children(&mut cursor) {
+        match child.kind() {
+            "access_specifier" => {
+                access = node_text(child, source).trim_end_matches(':').trim();
+            }
+            "function_definition" => {
+                let Some(func_decl) = find_descendant_of_type(child, "function_declarator") else { continue };
+                let Some(name_node) = func_decl.child_by_field_name("declarator") else { continue };
+                if name_node.kind() != "identifier" && name_node.kind() != "field_identifier" {
+                    continue; // 跳过析构函数/运算符重载等非普通标识符名字，保持和其余方法提取一致的粒度
+                }
+                let params = func_decl
+                    .child_by_field_name("parameters")
+                    .map(|p| super::c_lang::extract_c_params(p, source))
+                    .unwrap_or_default();
+                methods.push(super::MethodInfo {
+                    name: node_text(name_node, source).to_string(),
+                    start_line: child.start_position().row + 1,
+                    end_line: child.end_position().row + 1,
+                    params: params.into_iter().map(super::ParamInfo::simple).collect(),
+                    access: Some(access.to_string()),
+                });
+            }
+            "field_declaration" => {
+                let Some(declarator) = child.child_by_field_name("declarator") else { continue };
+                if declarator.kind() != "function_declarator" {
+                    continue; // 普通数据成员
+                }
+                let Some(name_node) = declarator.child_by_field_name("declarator") else { continue };
+                if name_node.kind() != "identifier" && name_node.kind() != "field_identifier" {
+                    continue;
                 }
+                let params = declarator
+                    .child_by_field_name("parameters")
+                    .map(|p| super::c_lang::extract_c_params(p, source))
+                    .unwrap_or_default();
+                methods.push(super::MethodInfo {
+                    name: node_text(name_node, source).to_string(),
+                    start_line: child.start_position().row + 1,
+                    end_line: child.end_position().row + 1,
+                    params: params.into_iter().map(super::ParamInfo::simple).collect(),
+                    access: Some(access.to_string()),
+                });
             }
+            _ => {}
         }
-    });
+    }
     methods
 }
 
+/// 扫描顶层（命名空间/全局作用域）的 `function_definition`，把用限定名定义的
+/// 类外方法（如 `void Engine::start() {}`）归属到同名的 class/struct 上。
+/// 如果类体里已经有这个方法的原型（`void start();`，被 `extract_cpp_methods` 记过一条，
+/// 带着从 `access_specifier` 读到的访问级别），就地补上真实的行区间和参数，不重复新增
+/// 一条；否则（原型本身也在别的翻译单元里，这个文件看不到）新增一条，访问级别留空。
+fn attribute_out_of_line_methods(classes: &mut [ClassInfo], tree: &Tree, source: &[u8]) {
+    walk_nodes(tree.root_node(), &mut |node| {
+        if node.kind() != "function_definition" {
+            return;
+        }
+        let func_decl = match find_descendant_of_type(node, "function_declarator") {
+            Some(n) => n,
+            None => return,
+        };
+        let declarator = match func_decl.child_by_field_name("declarator") {
+            Some(n) => n,
+            None => return,
+        };
+        if declarator.kind() != "qualified_identifier" {
+            return;
+        }
+        let full = node_text(declarator, source);
+        let Some(sep) = full.rfind("::") else { return };
+        let method_name = &full[sep + 2..];
+        // 嵌套限定符（A::B::method）只有最后一段 scope 才是方法实际所属的类
+        let scope_last = full[..sep].rsplit("::").next().unwrap_or(&full[..sep]);
+        let Some(ci) = classes
+            .iter_mut()
+            .find(|c| c.name == scope_last && (c.kind == "class" || c.kind == "struct"))
+        else {
+            return;
+        };
+        let params: Vec<super::ParamInfo> = func_decl
+            .child_by_field_name("parameters")
+            .map(|p| super::c_lang::extract_c_params(p, source))
+            .unwrap_or_default()
+            .into_iter()
+            .map(super::ParamInfo::simple)
+            .collect();
+        let start_line = node.start_position().row + 1;
+        let end_line = node.end_position().row + 1;
+        if let Some(existing) = ci.methods.iter_mut().find(|m| m.name == method_name) {
+            existing.start_line = start_line;
+            existing.end_line = end_line;
+            existing.params = params;
+        } else {
+            ci.methods.push(super::MethodInfo {
+                name: method_name.to_string(),
+                start_line,
+                end_line,
+                params,
+                access: None,
+            });
+        }
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -174,4 +332,124 @@ namespace MyLib {
         assert!(!exports.iter().any(|e| e.name == "MyLib" && e.kind == "namespace"),
             "namespace should not appear in exports");
     }
+
+    #[test]
+    fn test_cpp_out_of_line_method_attributed_to_class() {
+        let src = r#"
+class Engine {
+public:
+    void start();
+};
+
+void Engine::start() {}
+"#;
+        let tree = parse(src);
+        let adapter = CppAdapter::new();
+        let classes = adapter.extract_classes(&tree, src.as_bytes());
+        let engine = classes.iter().find(|c| c.name == "Engine").unwrap();
+        let start = engine.methods.iter().find(|m| m.name == "start").unwrap();
+        assert_eq!(start.access, Some("public".to_string()));
+        assert_eq!(start.start_line, 7);
+    }
+
+    #[test]
+    fn test_cpp_out_of_line_constructor_and_nested_scope() {
+        let src = r#"
+namespace app {
+class Engine {
+public:
+    Engine();
+    void run();
+};
+}
+
+app::Engine::Engine() {}
+void app::Engine::run() {}
+"#;
+        let tree = parse(src);
+        let adapter = CppAdapter::new();
+        let classes = adapter.extract_classes(&tree, src.as_bytes());
+        let engine = classes.iter().find(|c| c.name == "Engine").unwrap();
+        assert!(engine.methods.iter().any(|m| m.name == "Engine"));
+        assert!(engine.methods.iter().any(|m| m.name == "run"));
+    }
+
+    #[test]
+    fn test_cpp_methods_track_access_level() {
+        let src = r#"
+class Engine {
+public:
+    void start() {}
+protected:
+    void configure() {}
+private:
+    void reset(int level) {}
+};
+"#;
+        let tree = parse(src);
+        let adapter = CppAdapter::new();
+        let classes = adapter.extract_classes(&tree, src.as_bytes());
+        let engine = classes.iter().find(|c| c.name == "Engine").unwrap();
+
+        let start = engine.methods.iter().find(|m| m.name == "start").unwrap();
+        assert_eq!(start.access, Some("public".to_string()));
+
+        let configure = engine.methods.iter().find(|m| m.name == "configure").unwrap();
+        assert_eq!(configure.access, Some("protected".to_string()));
+
+        let reset = engine.methods.iter().find(|m| m.name == "reset").unwrap();
+        assert_eq!(reset.access, Some("private".to_string()));
+        assert_eq!(reset.params.len(), 1);
+        assert_eq!(reset.params[0].name, "level");
+    }
+
+    #[test]
+    fn test_cpp_struct_methods_default_to_public_access() {
+        let src = r#"
+struct Point {
+    void move_by(int dx, int dy) {}
+};
+"#;
+        let tree = parse(src);
+        let adapter = CppAdapter::new();
+        let classes = adapter.extract_classes(&tree, src.as_bytes());
+        let point = classes.iter().find(|c| c.name == "Point").unwrap();
+        let move_by = point.methods.iter().find(|m| m.name == "move_by").unwrap();
+        assert_eq!(move_by.access, Some("public".to_string()));
+    }
+
+    #[test]
+    fn test_cpp_template_class_discovered() {
+        let src = r#"
+template <typename T>
+class Box {
+public:
+    T value;
+};
+"#;
+        let tree = parse(src);
+        let adapter = CppAdapter::new();
+        let classes = adapter.extract_classes(&tree, src.as_bytes());
+        assert!(classes.iter().any(|c| c.name == "Box" && c.kind == "class"));
+    }
+
+    #[test]
+    fn test_cpp_extract_calls_including_qualified_and_member() {
+        let src = r#"
+class Engine {
+public:
+    void start();
+};
+
+void Engine::start() {
+    helper();
+    logger_.info();
+}
+"#;
+        let tree = parse(src);
+        let adapter = CppAdapter::new();
+        let calls = adapter.extract_calls(&tree, src.as_bytes());
+        assert!(calls.iter().any(|c| c.caller == "Engine::start" && c.callee == "helper"));
+        assert!(calls.iter().any(|c| c.caller == "Engine::start" && c.callee == "info"));
+    }
 }
@@ -1,9 +1,138 @@
+use std::collections::HashMap;
 use tree_sitter::{Language, Tree};
 use super::{
-    ClassInfo, ExportInfo, FunctionInfo, ImportInfo, LanguageAdapter,
-    node_text, strip_quotes, walk_nodes,
+    CallInfo, ClassInfo, ExportInfo, FunctionInfo, ImportInfo, LanguageAdapter,
+    compute_complexity, find_child_of_type, node_text, strip_quotes, walk_nodes,
 };
 
+/// Go import 相对当前模块的来源分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoImportOrigin {
+    /// 标准库（首段不含 `.`，如 `fmt`、`net/http`）
+    Stdlib,
+    /// 当前模块内部包（路径等于或前缀匹配 go.mod 的 module 路径）
+    Internal,
+    /// 第三方依赖（其余情况，如 `github.com/foo/bar`）
+    External,
+}
+
+/// 按 go.mod 的 module 路径对一个 Go import 路径分类
+///
+/// `module_path` 为 `None` 时（未找到/解析 go.mod）一律退化为 Stdlib/External 二分类，
+/// 不会误判 Internal
+pub fn classify_go_import(import_path: &str, module_path: Option<&str>) -> GoImportOrigin {
+    if let Some(module_path) = module_path {
+        if import_path == module_path
+            || import_path.strip_prefix(module_path).is_some_and(|rest| rest.starts_with('/'))
+        {
+            return GoImportOrigin::Internal;
+        }
+    }
+    let first_segment = import_path.split('/').next().unwrap_or(import_path);
+    if first_segment.contains('.') {
+        GoImportOrigin::External
+    } else {
+        GoImportOrigin::Stdlib
+    }
+}
+
+/// 解析 `go.mod` 文件内容里的 `module <path>` 指令
+pub fn parse_go_module_path(go_mod_content: &str) -> Option<String> {
+    go_mod_content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("module ").map(|rest| rest.trim().to_string()))
+}
+
+/// 读取项目根目录下的 `go.mod`（不存在/无法解析时返回 `None`）
+pub fn read_module_path(root_dir: &std::path::Path) -> Option<String> {
+    let content = std::fs::read_to_string(root_dir.join("go.mod")).ok()?;
+    parse_go_module_path(&content)
+}
+
+/// 一个 method_declaration 按接收者类型归并前的轻量记录
+#[derive(Clone)]
+struct ReceiverMethod {
+    name: String,
+    start_line: usize,
+    end_line: usize,
+}
+
+impl ReceiverMethod {
+    fn into_method_info(self) -> super::MethodInfo {
+        super::MethodInfo {
+            name: self.name,
+            start_line: self.start_line,
+            end_line: self.end_line,
+            params: Vec::new(),
+            access: None,
+        }
+    }
+}
+
+/// 取出 `method_declaration` 接收者声明的基础类型名（`*T` 去掉前导的 `*`）
+fn method_receiver_type(node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    let receiver = node.child_by_field_name("receiver")?;
+    let param = find_child_of_type(receiver, "parameter_declaration")?;
+    let type_node = param.child_by_field_name("type")?;
+    let base = if type_node.kind() == "pointer_type" {
+        type_node.named_child(0)?
+    } else {
+        type_node
+    };
+    Some(node_text(base, source).to_string())
+}
+
+/// 收集紧贴在 `node` 上方、彼此之间没有空行的 `//` 行注释（或单个 `/* */` 块注释），
+/// 按原始顺序拼接成文档字符串；markers 去除。没有紧邻的注释则返回 `None`
+fn go_doc_comment(node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    let mut comments = Vec::new();
+    let mut current = node;
+    while let Some(prev) = current.prev_sibling() {
+        if prev.kind() != "comment" || current.start_position().row != prev.end_position().row + 1 {
+            break;
+        }
+        comments.push(prev);
+        current = prev;
+    }
+    if comments.is_empty() {
+        return None;
+    }
+    comments.reverse();
+    let lines: Vec<String> = comments
+        .iter()
+        .map(|c| strip_go_comment_markers(node_text(*c, source)))
+        .collect();
+    Some(lines.join("\n"))
+}
+
+/// 去掉单行注释的前导 `//`（及其后的单个空格）或块注释的 `/* */` 包裹
+fn strip_go_comment_markers(raw: &str) -> String {
+    let trimmed = raw.trim();
+    if let Some(rest) = trimmed.strip_prefix("/*") {
+        rest.trim_end_matches("*/").trim().to_string()
+    } else if let Some(rest) = trimmed.strip_prefix("//") {
+        rest.trim_start().to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// 嵌套函数字面量的 kind：遇到时停止向下累计复杂度
+const STOP_KINDS: &[&str] = &["function_declaration", "method_declaration", "func_literal"];
+
+/// 判断节点是否是一个计入圈复杂度的分支节点
+fn is_branch_node(node: tree_sitter::Node, source: &[u8]) -> bool {
+    match node.kind() {
+        "if_statement" | "for_statement" | "expression_case" | "type_case"
+        | "communication_case" => true,
+        "binary_expression" => node
+            .child_by_field_name("operator")
+            .map(|op| matches!(node_text(op, source), "&&" | "||"))
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
 pub struct GoAdapter;
 
 impl GoAdapter {
@@ -28,12 +157,19 @@ impl LanguageAdapter for GoAdapter {
                             .map(|p| extract_go_params(p, source))
                             .unwrap_or_default();
                         let is_exported = is_go_exported(&name);
+                        let complexity = compute_complexity(node, STOP_KINDS, &mut |n| is_branch_node(n, source));
                         functions.push(FunctionInfo {
                             name,
                             start_line: node.start_position().row + 1,
                             end_line: node.end_position().row + 1,
-                            params,
+                            params: params.into_iter().map(super::ParamInfo::simple).collect(),
                             is_exported,
+                            complexity,
+                            return_type: None,
+                            type_parameters: None,
+                            metrics: super::compute_symbol_metrics(node, source),
+                            decorators: Vec::new(),
+                            doc: go_doc_comment(node, source),
                         });
                     }
                 }
@@ -44,12 +180,19 @@ impl LanguageAdapter for GoAdapter {
                             .map(|p| extract_go_params(p, source))
                             .unwrap_or_default();
                         let is_exported = is_go_exported(&name);
+                        let complexity = compute_complexity(node, STOP_KINDS, &mut |n| is_branch_node(n, source));
                         functions.push(FunctionInfo {
                             name,
                             start_line: node.start_position().row + 1,
                             end_line: node.end_position().row + 1,
-                            params,
+                            params: params.into_iter().map(super::ParamInfo::simple).collect(),
                             is_exported,
+                            complexity,
+                            return_type: None,
+                            type_parameters: None,
+                            metrics: super::compute_symbol_metrics(node, source),
+                            decorators: Vec::new(),
+                            doc: go_doc_comment(node, source),
                         });
                     }
                 }
@@ -91,6 +234,7 @@ impl LanguageAdapter for GoAdapter {
                 source: src,
                 names: vec![symbol],
                 is_default: false,
+                dynamic: false,
             });
         });
         imports
@@ -104,7 +248,7 @@ impl LanguageAdapter for GoAdapter {
                     if let Some(n) = node.child_by_field_name("name") {
                         let name = node_text(n, source).to_string();
                         if is_go_exported(&name) {
-                            exports.push(ExportInfo { name, kind: "function".into() });
+                            exports.push(ExportInfo { name, kind: "function".into(), doc: go_doc_comment(node, source), reexport_source: None, star: false });
                         }
                     }
                 }
@@ -119,7 +263,10 @@ impl LanguageAdapter for GoAdapter {
                                     _ => "type",
                                 })
                                 .unwrap_or("type");
-                            exports.push(ExportInfo { name, kind: kind.into() });
+                            let decl_node = node.parent()
+                                .filter(|p| p.kind() == "type_declaration")
+                                .unwrap_or(node);
+                            exports.push(ExportInfo { name, kind: kind.into(), doc: go_doc_comment(decl_node, source), reexport_source: None, star: false });
                         }
                     }
                 }
@@ -130,6 +277,26 @@ impl LanguageAdapter for GoAdapter {
     }
 
     fn extract_classes(&self, tree: &Tree, source: &[u8]) -> Vec<ClassInfo> {
+        // 第一遍：按接收者类型把所有方法归并到一起
+        let mut methods_by_receiver: HashMap<String, Vec<ReceiverMethod>> = HashMap::new();
+        walk_nodes(tree.root_node(), &mut |node| {
+            if node.kind() != "method_declaration" {
+                return;
+            }
+            let Some(receiver_type) = method_receiver_type(node, source) else {
+                return;
+            };
+            let Some(name_node) = node.child_by_field_name("name") else {
+                return;
+            };
+            methods_by_receiver.entry(receiver_type).or_default().push(ReceiverMethod {
+                name: node_text(name_node, source).to_string(),
+                start_line: node.start_position().row + 1,
+                end_line: node.end_position().row + 1,
+            });
+        });
+
+        // 第二遍：按声明的 struct/interface 建 ClassInfo，顺带认领同名接收者的方法
         let mut classes = Vec::new();
         walk_nodes(tree.root_node(), &mut |node| {
             if node.kind() != "type_spec" {
@@ -148,17 +315,93 @@ impl LanguageAdapter for GoAdapter {
                 let decl_node = node.parent()
                     .filter(|p| p.kind() == "type_declaration")
                     .unwrap_or(node);
+                let name = node_text(name_node, source).to_string();
+                let methods = methods_by_receiver
+                    .remove(&name)
+                    .map(|entries| entries.into_iter().map(ReceiverMethod::into_method_info).collect())
+                    .unwrap_or_default();
                 classes.push(ClassInfo {
-                    name: node_text(name_node, source).to_string(),
+                    name,
                     start_line: decl_node.start_position().row + 1,
                     end_line: decl_node.end_position().row + 1,
-                    methods: Vec::new(),
+                    methods,
                     kind: kind.into(),
+                    metrics: super::compute_symbol_metrics(decl_node, source),
+                    decorators: Vec::new(),
+                    doc: go_doc_comment(decl_node, source),
+                    members: Vec::new(),
                 });
             }
         });
+
+        // 剩下没被任何本文件内 struct/interface 认领的方法——接收者多半声明在
+        // 同一个包的另一个文件里，不能丢，打包成一个 "orphan_methods" 条目，
+        // 让它们仍然出现在图谱里，留给后续跨文件关联
+        let mut orphan_receivers: Vec<&String> = methods_by_receiver.keys().collect();
+        orphan_receivers.sort();
+        for receiver in orphan_receivers {
+            let entries = &methods_by_receiver[receiver];
+            let start_line = entries.iter().map(|m| m.start_line).min().unwrap_or(0);
+            let end_line = entries.iter().map(|m| m.end_line).max().unwrap_or(0);
+            classes.push(ClassInfo {
+                name: receiver.clone(),
+                start_line,
+                end_line,
+                methods: entries.iter().map(|m| m.clone().into_method_info()).collect(),
+                kind: "orphan_methods".into(),
+                metrics: super::SymbolMetrics::default(),
+                decorators: Vec::new(),
+                doc: None,
+                members: Vec::new(),
+            });
+        }
+
         classes
     }
+
+    fn extract_calls(&self, tree: &Tree, source: &[u8]) -> Vec<CallInfo> {
+        let mut calls = Vec::new();
+        walk_nodes(tree.root_node(), &mut |node| {
+            if node.kind() != "call_expression" {
+                return;
+            }
+            let Some(func_node) = node.child_by_field_name("function") else { return; };
+            let Some(callee) = call_expression_callee(func_node, source) else { return; };
+            let Some(caller) = enclosing_function_name(node, source) else { return; };
+            calls.push(CallInfo {
+                caller,
+                callee,
+                line: node.start_position().row + 1,
+            });
+        });
+        calls
+    }
+}
+
+/// `call_expression` 的 `function` 字段要么是裸标识符，要么是 `selector_expression`
+/// （取 `field`，即 `obj.Method()` → `Method`）
+fn call_expression_callee(func_node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    match func_node.kind() {
+        "identifier" => Some(node_text(func_node, source).to_string()),
+        "selector_expression" => func_node
+            .child_by_field_name("field")
+            .map(|n| node_text(n, source).to_string()),
+        _ => None,
+    }
+}
+
+/// 从调用点向上找到最近的 `function_declaration`/`method_declaration`，返回其名字；
+/// 找不到（模块级初始化表达式里的调用）则返回 `None`
+fn enclosing_function_name(node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        if matches!(n.kind(), "function_declaration" | "method_declaration") {
+            let name_node = n.child_by_field_name("name")?;
+            return Some(node_text(name_node, source).to_string());
+        }
+        current = n.parent();
+    }
+    None
 }
 
 fn is_go_exported(name: &str) -> bool {
@@ -243,4 +486,163 @@ type Server struct {
         assert_eq!(classes[0].name, "Server");
         assert_eq!(classes[0].kind, "struct");
     }
+
+    #[test]
+    fn test_go_methods_attach_to_receiver_struct() {
+        let src = r#"package main
+
+type Server struct {
+    host string
+}
+
+func (s Server) Addr() string {
+    return s.host
+}
+
+func (s *Server) Close() error {
+    return nil
+}
+"#;
+        let tree = parse(src);
+        let adapter = GoAdapter::new();
+        let classes = adapter.extract_classes(&tree, src.as_bytes());
+        assert_eq!(classes.len(), 1);
+        assert_eq!(classes[0].name, "Server");
+        assert!(classes[0].methods.iter().any(|m| m.name == "Addr"));
+        assert!(classes[0].methods.iter().any(|m| m.name == "Close"));
+    }
+
+    #[test]
+    fn test_go_orphan_methods_kept_when_receiver_type_not_declared() {
+        let src = r#"package main
+
+func (c *Client) Do() error {
+    return nil
+}
+"#;
+        let tree = parse(src);
+        let adapter = GoAdapter::new();
+        let classes = adapter.extract_classes(&tree, src.as_bytes());
+        assert_eq!(classes.len(), 1);
+        assert_eq!(classes[0].name, "Client");
+        assert_eq!(classes[0].kind, "orphan_methods");
+        assert_eq!(classes[0].methods.iter().map(|m| m.name.clone()).collect::<Vec<_>>(), vec!["Do".to_string()]);
+    }
+
+    #[test]
+    fn test_go_doc_comment_on_function() {
+        let src = r#"package main
+
+// Hello greets the given name.
+// It always returns a friendly string.
+func Hello(name string) string {
+    return "Hello " + name
+}
+
+func helper() {}
+"#;
+        let tree = parse(src);
+        let adapter = GoAdapter::new();
+        let fns = adapter.extract_functions(&tree, src.as_bytes());
+        let hello = fns.iter().find(|f| f.name == "Hello").unwrap();
+        assert_eq!(hello.doc.as_deref(), Some("Hello greets the given name.\nIt always returns a friendly string."));
+        let helper = fns.iter().find(|f| f.name == "helper").unwrap();
+        assert_eq!(helper.doc, None);
+    }
+
+    #[test]
+    fn test_go_doc_comment_not_attached_across_blank_line() {
+        let src = r#"package main
+
+// unrelated comment
+
+func Hello(name string) string {
+    return name
+}
+"#;
+        let tree = parse(src);
+        let adapter = GoAdapter::new();
+        let fns = adapter.extract_functions(&tree, src.as_bytes());
+        let hello = fns.iter().find(|f| f.name == "Hello").unwrap();
+        assert_eq!(hello.doc, None);
+    }
+
+    #[test]
+    fn test_go_doc_comment_on_struct() {
+        let src = r#"package main
+
+// Server listens for incoming connections.
+type Server struct {
+    host string
+}
+"#;
+        let tree = parse(src);
+        let adapter = GoAdapter::new();
+        let classes = adapter.extract_classes(&tree, src.as_bytes());
+        assert_eq!(classes[0].doc.as_deref(), Some("Server listens for incoming connections."));
+    }
+
+    #[test]
+    fn test_classify_go_import_stdlib() {
+        assert_eq!(classify_go_import("fmt", Some("example.com/app")), GoImportOrigin::Stdlib);
+        assert_eq!(classify_go_import("net/http", Some("example.com/app")), GoImportOrigin::Stdlib);
+    }
+
+    #[test]
+    fn test_classify_go_import_internal() {
+        assert_eq!(
+            classify_go_import("example.com/app/internal/util", Some("example.com/app")),
+            GoImportOrigin::Internal
+        );
+        assert_eq!(
+            classify_go_import("example.com/app", Some("example.com/app")),
+            GoImportOrigin::Internal
+        );
+        // 前缀相同但不是路径边界，不算 Internal（如 example.com/app2）
+        assert_eq!(
+            classify_go_import("example.com/app2/util", Some("example.com/app")),
+            GoImportOrigin::External
+        );
+    }
+
+    #[test]
+    fn test_classify_go_import_external() {
+        assert_eq!(
+            classify_go_import("github.com/foo/bar", Some("example.com/app")),
+            GoImportOrigin::External
+        );
+    }
+
+    #[test]
+    fn test_classify_go_import_without_module_path_falls_back_to_stdlib_external() {
+        assert_eq!(classify_go_import("fmt", None), GoImportOrigin::Stdlib);
+        assert_eq!(classify_go_import("github.com/foo/bar", None), GoImportOrigin::External);
+    }
+
+    #[test]
+    fn test_parse_go_module_path() {
+        let content = "module example.com/app\n\ngo 1.21\n\nrequire (\n\tgithub.com/foo/bar v1.0.0\n)\n";
+        assert_eq!(parse_go_module_path(content), Some("example.com/app".to_string()));
+    }
+
+    #[test]
+    fn test_parse_go_module_path_missing() {
+        assert_eq!(parse_go_module_path("go 1.21\n"), None);
+    }
+
+    #[test]
+    fn test_go_extract_calls() {
+        let src = r#"package main
+
+func outer() {
+    helper()
+    obj.Method()
+}
+"#;
+        let tree = parse(src);
+        let adapter = GoAdapter::new();
+        let calls = adapter.extract_calls(&tree, src.as_bytes());
+        assert!(calls.iter().any(|c| c.caller == "outer" && c.callee == "helper"));
+        assert!(calls.iter().any(|c| c.caller == "outer" && c.callee == "Method"));
+    }
 }
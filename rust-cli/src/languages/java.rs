@@ -1,9 +1,25 @@
 use tree_sitter::{Language, Tree};
 use super::{
-    ClassInfo, ExportInfo, FunctionInfo, ImportInfo, LanguageAdapter,
-    node_text, walk_nodes,
+    CallInfo, ClassInfo, ExportInfo, FunctionInfo, ImportInfo, LanguageAdapter,
+    compute_complexity, node_text, walk_nodes,
 };
 
+/// 嵌套方法/lambda 的 kind：遇到时停止向下累计复杂度
+const STOP_KINDS: &[&str] = &["method_declaration", "constructor_declaration", "lambda_expression"];
+
+/// 判断节点是否是一个计入圈复杂度的分支节点
+fn is_branch_node(node: tree_sitter::Node, source: &[u8]) -> bool {
+    match node.kind() {
+        "if_statement" | "for_statement" | "enhanced_for_statement" | "while_statement"
+        | "do_statement" | "catch_clause" | "switch_label" | "ternary_expression" => true,
+        "binary_expression" => node
+            .child_by_field_name("operator")
+            .map(|op| matches!(node_text(op, source), "&&" | "||"))
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
 pub struct JavaAdapter;
 
 impl JavaAdapter {
@@ -36,12 +52,19 @@ impl LanguageAdapter for JavaAdapter {
                 .map(|p| extract_java_params(p, source))
                 .unwrap_or_default();
             let is_exported = has_modifier(node, source, "public");
+            let complexity = compute_complexity(node, STOP_KINDS, &mut |n| is_branch_node(n, source));
             functions.push(FunctionInfo {
                 name: qualified_name,
                 start_line: node.start_position().row + 1,
                 end_line: node.end_position().row + 1,
-                params,
+                params: params.into_iter().map(super::ParamInfo::simple).collect(),
                 is_exported,
+                complexity,
+                return_type: None,
+                type_parameters: None,
+                metrics: super::compute_symbol_metrics(node, source),
+                decorators: Vec::new(),
+                doc: None,
             });
         });
         functions
@@ -69,12 +92,14 @@ impl LanguageAdapter for JavaAdapter {
                     source: src,
                     names: vec![symbol],
                     is_default: false,
+                    dynamic: false,
                 });
             } else {
                 imports.push(ImportInfo {
                     source: path,
                     names: Vec::new(),
                     is_default: false,
+                    dynamic: false,
                 });
             }
         });
@@ -95,6 +120,9 @@ impl LanguageAdapter for JavaAdapter {
                     exports.push(ExportInfo {
                         name: node_text(n, source).to_string(),
                         kind: kind.into(),
+                        doc: None,
+                        reexport_source: None,
+                        star: false,
                     });
                 }
             }
@@ -119,11 +147,51 @@ impl LanguageAdapter for JavaAdapter {
                     end_line: node.end_position().row + 1,
                     methods,
                     kind: kind.into(),
+                    metrics: super::compute_symbol_metrics(node, source),
+                    decorators: Vec::new(),
+                    doc: None,
+                    members: Vec::new(),
                 });
             }
         });
         classes
     }
+
+    fn extract_calls(&self, tree: &Tree, source: &[u8]) -> Vec<CallInfo> {
+        let mut calls = Vec::new();
+        walk_nodes(tree.root_node(), &mut |node| {
+            if node.kind() != "method_invocation" {
+                return;
+            }
+            let Some(name_node) = node.child_by_field_name("name") else { return; };
+            let Some(caller) = enclosing_method_name(node, source) else { return; };
+            calls.push(CallInfo {
+                caller,
+                callee: node_text(name_node, source).to_string(),
+                line: node.start_position().row + 1,
+            });
+        });
+        calls
+    }
+}
+
+/// 从调用点向上找到最近的 `method_declaration`/`constructor_declaration`，返回其限定名
+/// （见 `extract_functions` 里 `Class.method` 的拼法）；找不到（字段初始化表达式里的
+/// 调用）则返回 `None`
+fn enclosing_method_name(node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        if matches!(n.kind(), "method_declaration" | "constructor_declaration") {
+            let name_node = n.child_by_field_name("name")?;
+            let class_name = find_enclosing_class_name(n, source);
+            return Some(match &class_name {
+                Some(c) => format!("{}.{}", c, node_text(name_node, source)),
+                None => node_text(name_node, source).to_string(),
+            });
+        }
+        current = n.parent();
+    }
+    None
 }
 
 fn find_enclosing_class_name(node: tree_sitter::Node, source: &[u8]) -> Option<String> {
@@ -169,12 +237,12 @@ fn extract_java_params(params_node: tree_sitter::Node, source: &[u8]) -> Vec<Str
     params
 }
 
-fn extract_java_methods(class_node: tree_sitter::Node, source: &[u8]) -> Vec<String> {
+fn extract_java_methods(class_node: tree_sitter::Node, source: &[u8]) -> Vec<super::MethodInfo> {
     let mut methods = Vec::new();
     walk_nodes(class_node, &mut |node| {
         if node.kind() == "method_declaration" {
             if let Some(n) = node.child_by_field_name("name") {
-                methods.push(node_text(n, source).to_string());
+                methods.push(super::MethodInfo::simple(node_text(n, source).to_string()));
             }
         }
     });
@@ -232,4 +300,21 @@ public interface Runnable {}
         assert!(classes.iter().any(|c| c.name == "Animal" && c.kind == "class"));
         assert!(classes.iter().any(|c| c.name == "Runnable" && c.kind == "interface"));
     }
+
+    #[test]
+    fn test_java_extract_calls() {
+        let src = r#"
+public class Greeter {
+    public void outer() {
+        helper();
+        obj.method();
+    }
+}
+"#;
+        let tree = parse(src);
+        let adapter = JavaAdapter::new();
+        let calls = adapter.extract_calls(&tree, src.as_bytes());
+        assert!(calls.iter().any(|c| c.caller == "Greeter.outer" && c.callee == "helper"));
+        assert!(calls.iter().any(|c| c.caller == "Greeter.outer" && c.callee == "method"));
+    }
 }
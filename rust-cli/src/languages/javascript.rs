@@ -1,9 +1,29 @@
 use tree_sitter::{Language, Tree};
 use super::{
-    ClassInfo, ExportInfo, FunctionInfo, ImportInfo, LanguageAdapter,
+    CallInfo, ClassInfo, ExportInfo, FunctionInfo, ImportInfo, LanguageAdapter,
+    compute_complexity, extract_cjs_export, extract_cjs_or_dynamic_import, extract_reexports,
     find_child_of_type, node_text, strip_quotes, walk_nodes,
 };
 
+/// 嵌套函数/闭包定义的 kind：遇到时停止向下累计复杂度，让内层函数有自己的分数
+const STOP_KINDS: &[&str] = &[
+    "function_declaration", "function_expression", "arrow_function",
+    "method_definition", "generator_function_declaration", "generator_function",
+];
+
+/// 判断节点是否是一个计入圈复杂度的分支节点
+fn is_branch_node(node: tree_sitter::Node, source: &[u8]) -> bool {
+    match node.kind() {
+        "if_statement" | "for_statement" | "for_in_statement" | "while_statement"
+        | "do_statement" | "switch_case" | "catch_clause" | "ternary_expression" => true,
+        "binary_expression" => node
+            .child_by_field_name("operator")
+            .map(|op| matches!(node_text(op, source), "&&" | "||"))
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
 /// JavaScript 适配器（复用 TypeScript 逻辑，使用 JS grammar）
 pub struct JavaScriptAdapter;
 
@@ -30,12 +50,19 @@ impl LanguageAdapter for JavaScriptAdapter {
                     let is_exported = node.parent()
                         .map(|p| p.kind() == "export_statement")
                         .unwrap_or(false);
+                    let complexity = compute_complexity(node, STOP_KINDS, &mut |n| is_branch_node(n, source));
                     functions.push(FunctionInfo {
                         name,
                         start_line: node.start_position().row + 1,
                         end_line: node.end_position().row + 1,
-                        params,
+                        params: params.into_iter().map(super::ParamInfo::simple).collect(),
                         is_exported,
+                        complexity,
+                        return_type: None,
+                        type_parameters: None,
+                        metrics: super::compute_symbol_metrics(node, source),
+                        decorators: Vec::new(),
+                        doc: None,
                     });
                 }
             }
@@ -58,12 +85,19 @@ impl LanguageAdapter for JavaScriptAdapter {
                                         let is_exported = node.parent()
                                             .map(|p| p.kind() == "export_statement")
                                             .unwrap_or(false);
+                                        let complexity = compute_complexity(val, STOP_KINDS, &mut |n| is_branch_node(n, source));
                                         functions.push(FunctionInfo {
                                             name,
                                             start_line: node.start_position().row + 1,
                                             end_line: node.end_position().row + 1,
-                                            params,
+                                            params: params.into_iter().map(super::ParamInfo::simple).collect(),
                                             is_exported,
+                                            complexity,
+                                            return_type: None,
+                                            type_parameters: None,
+                                            metrics: super::compute_symbol_metrics(val, source),
+                                            decorators: Vec::new(),
+                                            doc: None,
                                         });
                                     }
                                 }
@@ -79,6 +113,11 @@ impl LanguageAdapter for JavaScriptAdapter {
     fn extract_imports(&self, tree: &Tree, source: &[u8]) -> Vec<ImportInfo> {
         let mut imports = Vec::new();
         walk_nodes(tree.root_node(), &mut |node| {
+            // `require('x')` / 动态 `import('x')`，见 [`crate::languages::extract_cjs_or_dynamic_import`]
+            if let Some(import) = extract_cjs_or_dynamic_import(node, source) {
+                imports.push(import);
+                return;
+            }
             if node.kind() != "import_statement" {
                 return;
             }
@@ -109,7 +148,7 @@ impl LanguageAdapter for JavaScriptAdapter {
                     }
                 }
             }
-            imports.push(ImportInfo { source: src, names, is_default: false });
+            imports.push(ImportInfo { source: src, names, is_default: false, dynamic: false });
         });
         imports
     }
@@ -117,17 +156,29 @@ impl LanguageAdapter for JavaScriptAdapter {
     fn extract_exports(&self, tree: &Tree, source: &[u8]) -> Vec<ExportInfo> {
         let mut exports = Vec::new();
         walk_nodes(tree.root_node(), &mut |node| {
+            // `exports.NAME = ...` / `module.exports = ...`，见
+            // [`crate::languages::extract_cjs_export`]
+            if let Some(cjs_exports) = extract_cjs_export(node, source) {
+                exports.extend(cjs_exports);
+                return;
+            }
             if node.kind() != "export_statement" {
                 return;
             }
+            // barrel 文件的 re-export：`export { a, b } from '../mod'` 或
+            // `export * from './routes'`，见 [`crate::languages::extract_reexports`]
+            if let Some(reexports) = extract_reexports(node, source) {
+                exports.extend(reexports);
+                return;
+            }
             if let Some(func) = find_child_of_type(node, "function_declaration") {
                 if let Some(n) = func.child_by_field_name("name") {
-                    exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "function".into() });
+                    exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "function".into(), doc: None, reexport_source: None, star: false });
                 }
             }
             if let Some(cls) = find_child_of_type(node, "class_declaration") {
                 if let Some(n) = cls.child_by_field_name("name") {
-                    exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "class".into() });
+                    exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "class".into(), doc: None, reexport_source: None, star: false });
                 }
             }
             if let Some(lex) = find_child_of_type(node, "lexical_declaration") {
@@ -135,7 +186,7 @@ impl LanguageAdapter for JavaScriptAdapter {
                 for decl in lex.children(&mut c) {
                     if decl.kind() == "variable_declarator" {
                         if let Some(n) = decl.child_by_field_name("name") {
-                            exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "variable".into() });
+                            exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "variable".into(), doc: None, reexport_source: None, star: false });
                         }
                     }
                 }
@@ -147,7 +198,7 @@ impl LanguageAdapter for JavaScriptAdapter {
                         let n = spec.child_by_field_name("name")
                             .or_else(|| spec.named_child(0));
                         if let Some(n) = n {
-                            exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "variable".into() });
+                            exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "variable".into(), doc: None, reexport_source: None, star: false });
                         }
                     }
                 }
@@ -165,7 +216,7 @@ impl LanguageAdapter for JavaScriptAdapter {
                     walk_nodes(node, &mut |child| {
                         if child.kind() == "method_definition" {
                             if let Some(mn) = child.child_by_field_name("name") {
-                                methods.push(node_text(mn, source).to_string());
+                                methods.push(super::MethodInfo::simple(node_text(mn, source).to_string()));
                             }
                         }
                     });
@@ -175,12 +226,76 @@ impl LanguageAdapter for JavaScriptAdapter {
                         end_line: node.end_position().row + 1,
                         methods,
                         kind: "class".into(),
+                        metrics: super::compute_symbol_metrics(node, source),
+                        decorators: Vec::new(),
+                        doc: None,
+                        members: Vec::new(),
                     });
                 }
             }
         });
         classes
     }
+
+    fn extract_calls(&self, tree: &Tree, source: &[u8]) -> Vec<CallInfo> {
+        let mut calls = Vec::new();
+        walk_nodes(tree.root_node(), &mut |node| {
+            if node.kind() != "call_expression" {
+                return;
+            }
+            let Some(func_node) = node.child_by_field_name("function") else { return; };
+            let Some(callee) = call_expression_callee(func_node, source) else { return; };
+            let Some(caller) = enclosing_function_name(node, source) else { return; };
+            calls.push(CallInfo {
+                caller,
+                callee,
+                line: node.start_position().row + 1,
+            });
+        });
+        calls
+    }
+}
+
+/// `call_expression` 的 `function` 字段要么是裸标识符，要么是 `member_expression`
+/// （取 `property`，即 `obj.method()` → `method`）
+fn call_expression_callee(func_node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    match func_node.kind() {
+        "identifier" => Some(node_text(func_node, source).to_string()),
+        "member_expression" => func_node
+            .child_by_field_name("property")
+            .map(|n| node_text(n, source).to_string()),
+        _ => None,
+    }
+}
+
+/// 从调用点向上找到最近的具名函数/方法（`STOP_KINDS` 里的节点种类），返回其名字；
+/// 匿名函数表达式/箭头函数找不到绑定的变量名时返回 `None`，调用方跳过这类边
+fn enclosing_function_name(node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        match n.kind() {
+            "function_declaration" | "generator_function_declaration" | "generator_function" => {
+                return n.child_by_field_name("name").map(|name| node_text(name, source).to_string());
+            }
+            "method_definition" => {
+                return n.child_by_field_name("name").map(|name| node_text(name, source).to_string());
+            }
+            "arrow_function" | "function_expression" => {
+                // 具名场景：`const foo = (...) => {}` / `const foo = function () {}`
+                if let Some(parent) = n.parent() {
+                    if parent.kind() == "variable_declarator" {
+                        if let Some(name_node) = parent.child_by_field_name("name") {
+                            return Some(node_text(name_node, source).to_string());
+                        }
+                    }
+                }
+                return None;
+            }
+            _ => {}
+        }
+        current = n.parent();
+    }
+    None
 }
 
 fn extract_js_params(params_node: tree_sitter::Node, source: &[u8]) -> Vec<String> {
@@ -229,4 +344,50 @@ const add = (a, b) => a + b;
         assert_eq!(imports.len(), 2);
         assert_eq!(imports[0].source, "fs");
     }
+
+    #[test]
+    fn test_js_extract_require_and_dynamic_import() {
+        let src = "const fs = require('fs');\nasync function load() {\n    const mod = await import('./lazy');\n}\n";
+        let tree = parse(src);
+        let adapter = JavaScriptAdapter::new();
+        let imports = adapter.extract_imports(&tree, src.as_bytes());
+        assert_eq!(imports.len(), 2);
+        assert_eq!(imports[0].source, "fs");
+        assert!(!imports[0].dynamic);
+        assert_eq!(imports[1].source, "./lazy");
+        assert!(imports[1].dynamic);
+    }
+
+    #[test]
+    fn test_js_extract_cjs_exports() {
+        let src = "exports.greet = function () {};\nmodule.exports.farewell = 1;\n";
+        let tree = parse(src);
+        let adapter = JavaScriptAdapter::new();
+        let exports = adapter.extract_exports(&tree, src.as_bytes());
+        assert!(exports.iter().any(|e| e.name == "greet"));
+        assert!(exports.iter().any(|e| e.name == "farewell"));
+    }
+
+    #[test]
+    fn test_js_extract_module_exports_reexport() {
+        let src = "module.exports = require('./other');\n";
+        let tree = parse(src);
+        let adapter = JavaScriptAdapter::new();
+        let exports = adapter.extract_exports(&tree, src.as_bytes());
+        assert_eq!(exports.len(), 1);
+        assert!(exports[0].star);
+        assert_eq!(exports[0].reexport_source.as_deref(), Some("./other"));
+    }
+
+    #[test]
+    fn test_js_extract_module_exports_object() {
+        let src = "module.exports = { a, b: 2, 'c': 3 };\n";
+        let tree = parse(src);
+        let adapter = JavaScriptAdapter::new();
+        let exports = adapter.extract_exports(&tree, src.as_bytes());
+        let names: Vec<_> = exports.iter().map(|e| e.name.as_str()).collect();
+        assert!(names.contains(&"a"));
+        assert!(names.contains(&"b"));
+        assert!(names.contains(&"c"));
+    }
 }
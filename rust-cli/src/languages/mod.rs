@@ -6,18 +6,168 @@ pub mod rust_lang;
 pub mod java;
 pub mod c_lang;
 pub mod cpp;
+pub mod query_adapter;
 
 // ---------------------------------------------------------------------------
 // 公共数据结构
 // ---------------------------------------------------------------------------
 
+/// 参数在函数签名中扮演的角色
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+    /// 普通位置参数
+    Positional,
+    /// `*args` 风格的可变位置参数
+    VarArgs,
+    /// `**kwargs` 风格的可变关键字参数
+    KwArgs,
+    /// `*` 分隔符之后的仅限关键字参数（目前仅 Python 区分）
+    KeywordOnly,
+    /// `...args` 风格的剩余参数（目前仅 TypeScript 区分；与 Python 单星号的
+    /// `VarArgs` 不同前缀，分开一个变体以免签名渲染成 Python 语法）
+    Rest,
+}
+
+#[derive(Debug, Clone)]
+pub struct ParamInfo {
+    pub name: String,
+    pub type_annotation: Option<String>,
+    pub default: Option<String>,
+    pub kind: ParamKind,
+    /// TypeScript 的 `?` 可选参数标记（`opts?: Opts`），与拥有默认值是两回事——
+    /// 一个参数可以既无默认值又是可选的。目前只有 TS 适配器会填充为 `true`；
+    /// 其余语言留空（`false`）待后续适配器跟进同一模式
+    pub optional: bool,
+}
+
+impl ParamInfo {
+    /// 构造一个没有类型注解/默认值的普通位置参数；大多数语言的适配器目前只能
+    /// 提取到参数名，用这个构造器保持调用点简洁
+    pub fn simple(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            type_annotation: None,
+            default: None,
+            kind: ParamKind::Positional,
+            optional: false,
+        }
+    }
+
+    /// 渲染成函数签名里的单个参数片段，例如 `x: int = 0`、`*args`、`**kwargs`、`opts?: Opts`
+    pub fn render(&self) -> String {
+        let prefix = match self.kind {
+            ParamKind::VarArgs => "*",
+            ParamKind::KwArgs => "**",
+            ParamKind::Rest => "...",
+            ParamKind::Positional | ParamKind::KeywordOnly => "",
+        };
+        let mut rendered = format!("{}{}", prefix, self.name);
+        if self.optional {
+            rendered.push('?');
+        }
+        if let Some(t) = &self.type_annotation {
+            rendered.push_str(": ");
+            rendered.push_str(t);
+        }
+        if let Some(d) = &self.default {
+            rendered.push_str(" = ");
+            rendered.push_str(d);
+        }
+        rendered
+    }
+}
+
+/// 单个函数/类的行数统计：物理行数、代码行、注释行、空行
+///
+/// 圈复杂度不放在这里——`FunctionInfo::complexity` 已经承载这个数字，这里只补上
+/// chunk0-1 为整个文件做过的行分类在符号粒度上的缺口。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SymbolMetrics {
+    pub lines: u32,
+    pub code_lines: u32,
+    pub comment_lines: u32,
+    pub blank_lines: u32,
+}
+
+/// 在 `node` 的行区间内统计代码/注释/空行，复用 `node` 子树中的注释节点范围判定每行归属
+///
+/// 规则与 `scanner::classify_lines` 对整个文件做的事一致：去除首尾空白后为空 → 空行；
+/// 非空白字符的字节跨度完整落在某个注释节点内 → 注释行；其余（含行尾注释的混合行）算代码行。
+pub fn compute_symbol_metrics(node: tree_sitter::Node, source: &[u8]) -> SymbolMetrics {
+    let start_row = node.start_position().row;
+    let end_row = node.end_position().row;
+
+    let mut comment_ranges = Vec::new();
+    walk_nodes(node, &mut |n| {
+        if n.kind().ends_with("comment") {
+            comment_ranges.push((n.start_byte(), n.end_byte()));
+        }
+    });
+
+    let text = String::from_utf8_lossy(source);
+    let mut code_lines = 0u32;
+    let mut comment_lines = 0u32;
+    let mut blank_lines = 0u32;
+
+    let mut byte_offset = 0usize;
+    for (row, line) in text.split('\n').enumerate() {
+        let line_start = byte_offset;
+        byte_offset += line.len() + 1;
+        if row < start_row || row > end_row {
+            continue;
+        }
+
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            blank_lines += 1;
+            continue;
+        }
+
+        let leading_ws = line.len() - line.trim_start().len();
+        let trailing_ws = line.len() - line.trim_end().len();
+        let span_start = line_start + leading_ws;
+        let span_end = line_start + line.len() - trailing_ws;
+
+        let fully_in_comment = comment_ranges
+            .iter()
+            .any(|&(cs, ce)| cs <= span_start && span_end <= ce);
+
+        if fully_in_comment {
+            comment_lines += 1;
+        } else {
+            code_lines += 1;
+        }
+    }
+
+    SymbolMetrics {
+        lines: (end_row - start_row + 1) as u32,
+        code_lines,
+        comment_lines,
+        blank_lines,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct FunctionInfo {
     pub name: String,
     pub start_line: usize,
     pub end_line: usize,
-    pub params: Vec<String>,
+    pub params: Vec<ParamInfo>,
     pub is_exported: bool,
+    pub complexity: u32,
+    /// 返回类型标注（目前只有 Python、TypeScript 适配器会填充）
+    pub return_type: Option<String>,
+    /// 泛型参数列表的原始文本，含尖括号，如 `<T, U extends Foo>`。
+    /// 目前只有 TS 适配器会填充；其余语言留空待后续适配器跟进同一模式
+    pub type_parameters: Option<String>,
+    pub metrics: SymbolMetrics,
+    /// 附着在该符号上的装饰器/注解/属性宏源文本，如 `@app.route("/x")`、`@staticmethod`。
+    /// 目前只有 Python 适配器会填充；其余语言有各自的等价物（Java 注解、Rust 属性宏、
+    /// TS 装饰器）留空待后续适配器跟进同一模式
+    pub decorators: Vec<String>,
+    /// 紧贴在声明上方、无空行间隔的文档注释，markers 已去除。
+    /// 目前只有 Go 适配器会填充；其余语言留空待后续适配器跟进同一模式
+    pub doc: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -25,12 +175,82 @@ pub struct ImportInfo {
     pub source: String,
     pub names: Vec<String>,
     pub is_default: bool,
+    /// `require(...)`/动态 `import(...)` 调用识别出的导入；目前只有 JS/TS 适配器会置位
+    pub dynamic: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct ExportInfo {
     pub name: String,
     pub kind: String, // "function", "class", "type", "variable"
+    /// 紧贴在声明上方、无空行间隔的文档注释，参见 `FunctionInfo::doc`
+    pub doc: Option<String>,
+    /// barrel 文件里的 re-export 来源模块，如 `export { login } from '../auth/login'`
+    /// 里的 `'../auth/login'`；本地声明的导出为 `None`。目前只有 JS/TS 适配器会
+    /// 填充，其余语言没有对应语法，留 `None`
+    pub reexport_source: Option<String>,
+    /// `export * from './routes'` 这种整体再导出；此时 `name` 留空，真正的符号
+    /// 列表要等 [`crate::slicer`] 的 re-export 解析阶段把来源模块的导出折叠进来。
+    /// 目前只有 JS/TS 适配器会填充
+    pub star: bool,
+}
+
+/// 一条函数调用边：`caller` 调用了 `callee`，发生在源文件第 `line` 行（1-based）
+///
+/// `caller`/`callee` 是函数名（Rust 方法名形如 `Type::method`，与
+/// `FunctionInfo::name` 的命名方式一致），不含文件路径——同名函数跨文件无法区分，
+/// 消费方（目前是 `impact::analyze_impact`）按名字匹配。
+#[derive(Debug, Clone)]
+pub struct CallInfo {
+    pub caller: String,
+    pub callee: String,
+    pub line: usize,
+}
+
+/// 类/结构体内的一个方法。大多数语言的适配器目前只提取得到方法名，用
+/// [`MethodInfo::simple`] 构造；C++ 适配器能额外拿到行区间、参数和访问级别
+#[derive(Debug, Clone)]
+pub struct MethodInfo {
+    pub name: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub params: Vec<ParamInfo>,
+    /// 方法可见性（`"public"`/`"private"`/`"protected"`）。目前只有 C++ 适配器会填充；
+    /// 其余语言有各自的等价物（Python 的下划线前缀约定、Rust 的 `pub`）留空待后续适配器
+    /// 跟进同一模式
+    pub access: Option<String>,
+}
+
+impl MethodInfo {
+    /// 构造一个只有名字的方法记录，行区间记 0、无参数无访问级别；大多数语言的适配器
+    /// 目前只能提取到方法名，用这个构造器保持调用点简洁，与 [`ParamInfo::simple`] 同样的思路
+    pub fn simple(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            start_line: 0,
+            end_line: 0,
+            params: Vec::new(),
+            access: None,
+        }
+    }
+}
+
+/// 成员在所属类型里扮演的角色：字段还是方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemberKind {
+    Field,
+    Method,
+}
+
+/// interface/type 字面量里的一个成员（`property_signature`/`method_signature`/
+/// `index_signature`），让消费方能看到接口的形状，而不只是一个空壳声明
+#[derive(Debug, Clone)]
+pub struct Member {
+    pub name: String,
+    pub kind: MemberKind,
+    /// TypeScript 的 `?` 可选成员标记，与 `ParamInfo::optional` 同样的含义
+    pub optional: bool,
+    pub type_annotation: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -38,8 +258,17 @@ pub struct ClassInfo {
     pub name: String,
     pub start_line: usize,
     pub end_line: usize,
-    pub methods: Vec<String>,
+    pub methods: Vec<MethodInfo>,
     pub kind: String, // "class", "interface", "struct", "enum", "trait"
+    pub metrics: SymbolMetrics,
+    /// 附着在该符号上的装饰器/注解/属性宏源文本，参见 `FunctionInfo::decorators`
+    pub decorators: Vec<String>,
+    /// 紧贴在声明上方、无空行间隔的文档注释，参见 `FunctionInfo::doc`
+    pub doc: Option<String>,
+    /// interface/type 字面量的成员列表。目前只有 TS 适配器会填充（`interface` 声明、
+    /// 以及值是 `object_type` 的 `type` 别名）；其余语言/`kind` 留空待后续适配器
+    /// 跟进同一模式
+    pub members: Vec<Member>,
 }
 
 // ---------------------------------------------------------------------------
@@ -52,6 +281,13 @@ pub trait LanguageAdapter: Send + Sync {
     fn extract_imports(&self, tree: &tree_sitter::Tree, source: &[u8]) -> Vec<ImportInfo>;
     fn extract_exports(&self, tree: &tree_sitter::Tree, source: &[u8]) -> Vec<ExportInfo>;
     fn extract_classes(&self, tree: &tree_sitter::Tree, source: &[u8]) -> Vec<ClassInfo>;
+
+    /// 提取函数调用边，供 `impact::analyze_impact` 做函数粒度的影响分析。
+    /// 默认返回空；八个内置语言适配器都覆盖了这个方法，只有 `QueryAdapter`
+    /// 沿用默认实现。
+    fn extract_calls(&self, _tree: &tree_sitter::Tree, _source: &[u8]) -> Vec<CallInfo> {
+        Vec::new()
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -75,20 +311,56 @@ pub fn get_adapter(lang: crate::traverser::Language) -> Box<dyn LanguageAdapter>
 // 共享辅助函数
 // ---------------------------------------------------------------------------
 
-/// 深度优先遍历所有节点，对每个节点调用 visitor
-/// 注意：使用递归实现，极端深层嵌套（>1000层）可能导致栈溢出
-pub fn walk_nodes<F>(node: tree_sitter::Node, visitor: &mut F)
+/// 让 `walk_nodes`/`walk_nodes_bounded` 的访问者既可以像以前一样返回 `()`（总是继续遍历），
+/// 也可以返回 `ControlFlow<()>` 以便提前终止——找到想要的节点后不必走完整棵子树
+pub trait VisitControl {
+    fn is_break(&self) -> bool;
+}
+
+impl VisitControl for () {
+    fn is_break(&self) -> bool {
+        false
+    }
+}
+
+impl VisitControl for std::ops::ControlFlow<()> {
+    fn is_break(&self) -> bool {
+        matches!(self, std::ops::ControlFlow::Break(()))
+    }
+}
+
+/// 深度优先（先序）遍历所有节点，对每个节点调用 visitor
+///
+/// 使用显式栈迭代实现，不依赖调用栈深度，因此不会在异常深层嵌套（生成代码里并不少见）
+/// 上发生栈溢出。visitor 返回 `ControlFlow::Break(())` 可以提前终止遍历；返回 `()` 的
+/// 旧式 visitor 照常工作，等价于“从不提前终止”。
+pub fn walk_nodes<F, R>(node: tree_sitter::Node, visitor: &mut F)
 where
-    F: FnMut(tree_sitter::Node),
+    F: FnMut(tree_sitter::Node) -> R,
+    R: VisitControl,
 {
-    visitor(node);
-    let mut cursor = node.walk();
-    if cursor.goto_first_child() {
-        loop {
-            walk_nodes(cursor.node(), visitor);
-            if !cursor.goto_next_sibling() {
-                break;
-            }
+    walk_nodes_bounded(node, None, visitor);
+}
+
+/// 与 [`walk_nodes`] 相同，但可选地限制下探深度：达到 `max_depth` 的节点仍会被访问，
+/// 但不再继续下探其子节点。用于防御病态输入（例如自动生成、嵌套深度失控的代码）。
+pub fn walk_nodes_bounded<F, R>(node: tree_sitter::Node, max_depth: Option<usize>, visitor: &mut F)
+where
+    F: FnMut(tree_sitter::Node) -> R,
+    R: VisitControl,
+{
+    let mut stack = vec![(node, 0usize)];
+    while let Some((current, depth)) = stack.pop() {
+        if visitor(current).is_break() {
+            return;
+        }
+        if max_depth.is_some_and(|limit| depth >= limit) {
+            continue;
+        }
+        let mut cursor = current.walk();
+        let children: Vec<_> = current.children(&mut cursor).collect();
+        for child in children.into_iter().rev() {
+            stack.push((child, depth + 1));
         }
     }
 }
@@ -104,23 +376,25 @@ pub fn find_child_of_type<'a>(node: tree_sitter::Node<'a>, kind: &str) -> Option
     None
 }
 
-/// 查找第一个指定类型的后代节点（BFS）
+/// 查找第一个指定类型的后代节点（先序深度优先，复用 `walk_nodes` 的显式栈遍历，
+/// 一旦命中就通过 `ControlFlow::Break` 提前终止，不会扫描整棵子树）
 pub fn find_descendant_of_type<'a>(node: tree_sitter::Node<'a>, kind: &str) -> Option<tree_sitter::Node<'a>> {
-    let mut queue = std::collections::VecDeque::new();
+    let mut found = None;
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        queue.push_back(child);
-    }
-    while let Some(current) = queue.pop_front() {
-        if current.kind() == kind {
-            return Some(current);
-        }
-        let mut c = current.walk();
-        for child in current.children(&mut c) {
-            queue.push_back(child);
+        if found.is_some() {
+            break;
         }
+        walk_nodes(child, &mut |n| -> std::ops::ControlFlow<()> {
+            if n.kind() == kind {
+                found = Some(n);
+                std::ops::ControlFlow::Break(())
+            } else {
+                std::ops::ControlFlow::Continue(())
+            }
+        });
     }
-    None
+    found
 }
 
 /// 去除字符串两端的引号
@@ -132,3 +406,256 @@ pub fn strip_quotes(s: &str) -> String {
 pub fn node_text<'a>(node: tree_sitter::Node, source: &'a [u8]) -> &'a str {
     node.utf8_text(source).unwrap_or("")
 }
+
+/// 识别一条 `export_statement` 是否是 barrel 文件式的 re-export：
+/// `export { a, b } from '../mod'`（逐个具名再导出）或 `export * from './routes'`/
+/// `export * as ns from './routes'`（整体再导出）。JS/TS 语法里这类 export 都带
+/// `source` 字段，本地声明的 export（`export function foo`、`export class Foo`等）
+/// 没有，借这个区分；不是 re-export 就返回 `None`，让调用方继续走原来的本地导出分支。
+///
+/// 返回的每条 [`ExportInfo`] 都带上 `reexport_source`，真正的符号列表（尤其是
+/// `star: true` 那条，此时 `name` 是空的）要等 [`crate::slicer`] 的 re-export
+/// 解析阶段把来源模块的导出折叠进来才完整。
+pub fn extract_reexports(node: tree_sitter::Node, source: &[u8]) -> Option<Vec<ExportInfo>> {
+    let source_node = node.child_by_field_name("source")?;
+    let reexport_source = strip_quotes(node_text(source_node, source));
+
+    let mut c = node.walk();
+    let has_star = node.children(&mut c).any(|child| child.kind() == "*");
+    if has_star {
+        return Some(vec![ExportInfo {
+            name: String::new(),
+            kind: "reexport".into(),
+            doc: None,
+            reexport_source: Some(reexport_source),
+            star: true,
+        }]);
+    }
+
+    let mut exports = Vec::new();
+    if let Some(clause) = find_child_of_type(node, "export_clause") {
+        let mut c = clause.walk();
+        for spec in clause.children(&mut c) {
+            if spec.kind() == "export_specifier" {
+                let n = spec.child_by_field_name("name").or_else(|| spec.named_child(0));
+                if let Some(n) = n {
+                    exports.push(ExportInfo {
+                        name: node_text(n, source).to_string(),
+                        kind: "variable".into(),
+                        doc: None,
+                        reexport_source: Some(reexport_source.clone()),
+                        star: false,
+                    });
+                }
+            }
+        }
+    }
+    Some(exports)
+}
+
+/// 识别一次函数调用是否是 CommonJS `require('x')` 或动态 `import('x')`：前者是
+/// `call_expression`，`function` 字段是名为 `require` 的 `identifier`；后者的
+/// `function` 字段是语法树里单独的 `import` 节点（不是 identifier）。两者都把
+/// 第一个参数当作模块路径，`dynamic` 只在动态 `import()` 的情形下置位——
+/// `require` 调用虽然也发生在运行时，但这里沿用 Node.js 版对 `dynamic` 的定义，
+/// 只标记语法形式为 `import(...)` 的那一种。不是这两种调用形式就返回 `None`。
+pub fn extract_cjs_or_dynamic_import(node: tree_sitter::Node, source: &[u8]) -> Option<ImportInfo> {
+    if node.kind() != "call_expression" {
+        return None;
+    }
+    let function = node.child_by_field_name("function")?;
+    let is_dynamic_import = function.kind() == "import";
+    let is_require = function.kind() == "identifier" && node_text(function, source) == "require";
+    if !is_dynamic_import && !is_require {
+        return None;
+    }
+
+    let args = node.child_by_field_name("arguments")?;
+    let arg = args.named_child(0)?;
+    if arg.kind() != "string" {
+        return None;
+    }
+    Some(ImportInfo {
+        source: strip_quotes(node_text(arg, source)),
+        names: Vec::new(),
+        is_default: false,
+        dynamic: is_dynamic_import,
+    })
+}
+
+/// 识别 CommonJS 风格的导出赋值：
+/// - `exports.NAME = ...` / `module.exports.NAME = ...` → 单条具名导出 `NAME`
+/// - `module.exports = require('./other')` → 整体再导出，等价于 `export * from`，
+///   复用 [`ExportInfo::star`]/[`ExportInfo::reexport_source`]
+/// - `module.exports = { a, b, c: value }` → 对象字面量的每个键各算一条具名导出
+///
+/// 只处理顶层是 `assignment_expression` 的 `expression_statement`，不是这个形状
+/// 就返回 `None`，让调用方继续走本地声明的导出分支。
+pub fn extract_cjs_export(node: tree_sitter::Node, source: &[u8]) -> Option<Vec<ExportInfo>> {
+    if node.kind() != "expression_statement" {
+        return None;
+    }
+    let assignment = find_child_of_type(node, "assignment_expression")?;
+    let left = assignment.child_by_field_name("left")?;
+    let right = assignment.child_by_field_name("right")?;
+    let left_text = node_text(left, source);
+
+    if let Some(name) = left_text
+        .strip_prefix("module.exports.")
+        .or_else(|| left_text.strip_prefix("exports."))
+    {
+        // 只认单层属性名（`exports.foo`）；`exports.foo.bar` 这种多级赋值不是在声明
+        // 一个叫 `foo.bar` 的导出，交给调用方的本地声明分支处理（大概率什么都提取不到）
+        if name.contains('.') {
+            return None;
+        }
+        return Some(vec![ExportInfo {
+            name: name.to_string(),
+            kind: "variable".into(),
+            doc: None,
+            reexport_source: None,
+            star: false,
+        }]);
+    }
+
+    if left_text != "module.exports" {
+        return None;
+    }
+
+    if right.kind() == "call_expression" {
+        let import = extract_cjs_or_dynamic_import(right, source)?;
+        return Some(vec![ExportInfo {
+            name: String::new(),
+            kind: "reexport".into(),
+            doc: None,
+            reexport_source: Some(import.source),
+            star: true,
+        }]);
+    }
+
+    if right.kind() == "object" {
+        let mut exports = Vec::new();
+        let mut c = right.walk();
+        for prop in right.named_children(&mut c) {
+            let name = match prop.kind() {
+                "shorthand_property_identifier" => Some(node_text(prop, source).to_string()),
+                "pair" => prop
+                    .child_by_field_name("key")
+                    .filter(|k| k.kind() != "computed_property_name")
+                    .map(|k| strip_quotes(node_text(k, source))),
+                _ => None,
+            };
+            if let Some(name) = name {
+                exports.push(ExportInfo {
+                    name,
+                    kind: "variable".into(),
+                    doc: None,
+                    reexport_source: None,
+                    star: false,
+                });
+            }
+        }
+        return Some(exports);
+    }
+
+    None
+}
+
+/// 计算函数体的圈复杂度：1 + 分支节点数量
+///
+/// 从 `function_node` 开始遍历其所有子孙节点，每当 `is_branch` 对某个节点返回
+/// `true`（如 `if_statement`、`for_statement`，或者 `&&`/`||` 这类需要检查操作符
+/// 才能判断的二元表达式）就计数加一。遇到 `stop_kinds`（嵌套的函数/闭包定义）时
+/// 不再深入其内部，让内层函数拥有自己独立的复杂度分数，不被外层函数重复计入。
+pub fn compute_complexity<F>(function_node: tree_sitter::Node, stop_kinds: &[&str], is_branch: &mut F) -> u32
+where
+    F: FnMut(tree_sitter::Node) -> bool,
+{
+    fn walk<F>(node: tree_sitter::Node, is_root: bool, stop_kinds: &[&str], is_branch: &mut F, count: &mut u32)
+    where
+        F: FnMut(tree_sitter::Node) -> bool,
+    {
+        if !is_root && stop_kinds.contains(&node.kind()) {
+            return;
+        }
+        if is_branch(node) {
+            *count += 1;
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            walk(child, false, stop_kinds, is_branch, count);
+        }
+    }
+
+    let mut count = 1u32;
+    walk(function_node, true, stop_kinds, is_branch, &mut count);
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_rust(source: &str) -> tree_sitter::Tree {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_rust::LANGUAGE.into())
+            .unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    #[test]
+    fn walk_nodes_visits_in_pre_order() {
+        let src = "fn a() {} fn b() {} fn c() {}";
+        let tree = parse_rust(src);
+        let mut kinds = Vec::new();
+        walk_nodes(tree.root_node(), &mut |n| kinds.push(n.kind()));
+        assert_eq!(kinds[0], "source_file");
+        let fn_positions: Vec<_> = kinds
+            .iter()
+            .enumerate()
+            .filter(|(_, k)| **k == "function_item")
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(fn_positions.len(), 3);
+        assert!(fn_positions.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn walk_nodes_can_break_early() {
+        let src = "fn a() {} fn b() {} fn c() {}";
+        let tree = parse_rust(src);
+        let mut visited = 0;
+        walk_nodes(tree.root_node(), &mut |n| -> std::ops::ControlFlow<()> {
+            visited += 1;
+            if n.kind() == "function_item" {
+                std::ops::ControlFlow::Break(())
+            } else {
+                std::ops::ControlFlow::Continue(())
+            }
+        });
+        // stops right after the first function_item, long before the third
+        assert!(visited < 6);
+    }
+
+    #[test]
+    fn walk_nodes_bounded_stops_descending_past_max_depth() {
+        let src = "fn a() { if true { if true { 1; } } }";
+        let tree = parse_rust(src);
+        let mut kinds = Vec::new();
+        walk_nodes_bounded(tree.root_node(), Some(1), &mut |n| kinds.push(n.kind()));
+        // root (depth 0) and its direct children (depth 1) are visited, but nothing deeper
+        assert!(kinds.contains(&"source_file"));
+        assert!(kinds.contains(&"function_item"));
+        assert!(!kinds.contains(&"if_expression"));
+    }
+
+    #[test]
+    fn find_descendant_of_type_finds_nested_match() {
+        let src = "fn a() { fn b() { let x: i32 = 1; } }";
+        let tree = parse_rust(src);
+        let found = find_descendant_of_type(tree.root_node(), "let_declaration");
+        assert!(found.is_some());
+        assert!(find_descendant_of_type(tree.root_node(), "no_such_kind").is_none());
+    }
+}
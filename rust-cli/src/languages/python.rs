@@ -1,9 +1,25 @@
 use tree_sitter::{Language, Tree};
 use super::{
-    ClassInfo, ExportInfo, FunctionInfo, ImportInfo, LanguageAdapter,
-    node_text, strip_quotes, walk_nodes,
+    CallInfo, ClassInfo, ExportInfo, FunctionInfo, ImportInfo, LanguageAdapter,
+    ParamInfo, ParamKind, compute_complexity, compute_symbol_metrics, node_text, strip_quotes, walk_nodes,
 };
 
+/// 嵌套函数/lambda 的 kind：遇到时停止向下累计复杂度
+const STOP_KINDS: &[&str] = &["function_definition", "lambda"];
+
+/// 判断节点是否是一个计入圈复杂度的分支节点
+fn is_branch_node(node: tree_sitter::Node, source: &[u8]) -> bool {
+    match node.kind() {
+        "if_statement" | "elif_clause" | "for_statement" | "while_statement"
+        | "except_clause" | "conditional_expression" | "case_clause" => true,
+        "boolean_operator" => node
+            .child_by_field_name("operator")
+            .map(|op| matches!(node_text(op, source), "and" | "or"))
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
 pub struct PythonAdapter;
 
 impl PythonAdapter {
@@ -30,12 +46,21 @@ impl LanguageAdapter for PythonAdapter {
                     let params = func.child_by_field_name("parameters")
                         .map(|p| extract_python_params(p, source))
                         .unwrap_or_default();
+                    let return_type = func.child_by_field_name("return_type")
+                        .map(|n| node_text(n, source).to_string());
+                    let complexity = compute_complexity(func, STOP_KINDS, &mut |n| is_branch_node(n, source));
                     functions.push(FunctionInfo {
                         name,
                         start_line: child.start_position().row + 1,
                         end_line: child.end_position().row + 1,
                         params,
                         is_exported: true, // Python 默认公开
+                        complexity,
+                        return_type,
+                        type_parameters: None,
+                        metrics: compute_symbol_metrics(child, source),
+                        decorators: extract_decorators(child, source),
+                        doc: None,
                     });
                 }
             }
@@ -57,6 +82,7 @@ impl LanguageAdapter for PythonAdapter {
                                     source: name.clone(),
                                     names: vec![name],
                                     is_default: false,
+                                    dynamic: false,
                                 });
                             }
                             "aliased_import" => {
@@ -67,6 +93,7 @@ impl LanguageAdapter for PythonAdapter {
                                         source: name.clone(),
                                         names: vec![name],
                                         is_default: false,
+                                        dynamic: false,
                                     });
                                 }
                             }
@@ -108,6 +135,7 @@ impl LanguageAdapter for PythonAdapter {
                         source: module,
                         names,
                         is_default: false,
+                        dynamic: false,
                     });
                 }
                 _ => {}
@@ -120,7 +148,7 @@ impl LanguageAdapter for PythonAdapter {
         // 先尝试 __all__
         if let Some(all_exports) = extract_dunder_all(tree, source) {
             return all_exports.into_iter()
-                .map(|name| ExportInfo { name, kind: "variable".into() })
+                .map(|name| ExportInfo { name, kind: "variable".into(), doc: None, reexport_source: None, star: false })
                 .collect();
         }
         // 回退：所有顶层函数和类
@@ -133,6 +161,9 @@ impl LanguageAdapter for PythonAdapter {
                     exports.push(ExportInfo {
                         name: node_text(n, source).to_string(),
                         kind: "function".into(),
+                        doc: None,
+                        reexport_source: None,
+                        star: false,
                     });
                 }
             } else if let Some(cls) = unwrap_decorated(child, "class_definition") {
@@ -140,6 +171,9 @@ impl LanguageAdapter for PythonAdapter {
                     exports.push(ExportInfo {
                         name: node_text(n, source).to_string(),
                         kind: "class".into(),
+                        doc: None,
+                        reexport_source: None,
+                        star: false,
                     });
                 }
             }
@@ -161,12 +195,77 @@ impl LanguageAdapter for PythonAdapter {
                         end_line: child.end_position().row + 1,
                         methods,
                         kind: "class".into(),
+                        metrics: compute_symbol_metrics(child, source),
+                        decorators: extract_decorators(child, source),
+                        doc: None,
+                        members: Vec::new(),
                     });
                 }
             }
         }
         classes
     }
+
+    fn extract_calls(&self, tree: &Tree, source: &[u8]) -> Vec<CallInfo> {
+        let mut calls = Vec::new();
+        walk_nodes(tree.root_node(), &mut |node| {
+            if node.kind() != "call" {
+                return;
+            }
+            let Some(func_node) = node.child_by_field_name("function") else { return; };
+            let Some(callee) = call_callee(func_node, source) else { return; };
+            let Some(caller) = enclosing_function_name(node, source) else { return; };
+            calls.push(CallInfo {
+                caller,
+                callee,
+                line: node.start_position().row + 1,
+            });
+        });
+        calls
+    }
+}
+
+/// `call` 的 `function` 字段要么是裸标识符，要么是 `attribute`
+/// （取 `attribute`，即 `obj.method()` → `method`）
+fn call_callee(func_node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    match func_node.kind() {
+        "identifier" => Some(node_text(func_node, source).to_string()),
+        "attribute" => func_node
+            .child_by_field_name("attribute")
+            .map(|n| node_text(n, source).to_string()),
+        _ => None,
+    }
+}
+
+/// 从调用点向上找到最近的 `function_definition`（跳过 `decorated_definition` 包装层，
+/// 它不改变函数本身），返回其名字；不在任何函数体内（模块顶层调用）则返回 `None`
+fn enclosing_function_name(node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        if n.kind() == "function_definition" {
+            let name_node = n.child_by_field_name("name")?;
+            return Some(node_text(name_node, source).to_string());
+        }
+        current = n.parent();
+    }
+    None
+}
+
+/// 收集 `decorated_definition` 包装下每个 `decorator` 子节点的源文本（去掉前导 `@`），
+/// 如 `@app.route("/x")`、`@staticmethod`；`node` 不是 `decorated_definition` 时返回空
+fn extract_decorators(node: tree_sitter::Node, source: &[u8]) -> Vec<String> {
+    if node.kind() != "decorated_definition" {
+        return Vec::new();
+    }
+    let mut decorators = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.kind() == "decorator" {
+            let text = node_text(child, source).trim_start_matches('@').trim().to_string();
+            decorators.push(text);
+        }
+    }
+    decorators
 }
 
 fn unwrap_decorated<'a>(node: tree_sitter::Node<'a>, expected: &str) -> Option<tree_sitter::Node<'a>> {
@@ -184,31 +283,124 @@ fn unwrap_decorated<'a>(node: tree_sitter::Node<'a>, expected: &str) -> Option<t
     None
 }
 
-fn extract_python_params(params_node: tree_sitter::Node, source: &[u8]) -> Vec<String> {
+/// 提取参数列表，保留类型注解、默认值与 `*args`/`**kwargs`/仅限关键字参数标记
+///
+/// 一旦遇到裸 `*` 分隔符（不带名字，只是用来分隔位置参数和仅限关键字参数），之后的
+/// 普通参数都标记为 `ParamKind::KeywordOnly`，直到遇到 `**kwargs` 或参数列表结束。
+fn extract_python_params(params_node: tree_sitter::Node, source: &[u8]) -> Vec<ParamInfo> {
     let mut params = Vec::new();
+    let mut keyword_only = false;
     let mut cursor = params_node.walk();
     for child in params_node.children(&mut cursor) {
         match child.kind() {
-            "identifier" => params.push(node_text(child, source).to_string()),
-            "typed_parameter" | "default_parameter" | "typed_default_parameter" => {
+            "identifier" => {
+                let kind = if keyword_only { ParamKind::KeywordOnly } else { ParamKind::Positional };
+                params.push(ParamInfo {
+                    name: node_text(child, source).to_string(),
+                    type_annotation: None,
+                    default: None,
+                    kind,
+                    optional: false,
+                });
+            }
+            "list_splat_pattern" => {
                 if let Some(n) = child.named_child(0) {
-                    params.push(node_text(n, source).to_string());
+                    params.push(ParamInfo {
+                        name: node_text(n, source).to_string(),
+                        type_annotation: None,
+                        default: None,
+                        kind: ParamKind::VarArgs,
+                        optional: false,
+                    });
+                }
+                keyword_only = true;
+            }
+            "dictionary_splat_pattern" => {
+                if let Some(n) = child.named_child(0) {
+                    params.push(ParamInfo {
+                        name: node_text(n, source).to_string(),
+                        type_annotation: None,
+                        default: None,
+                        kind: ParamKind::KwArgs,
+                        optional: false,
+                    });
+                }
+            }
+            "default_parameter" => {
+                let name = child.child_by_field_name("name")
+                    .map(|n| node_text(n, source).to_string())
+                    .unwrap_or_default();
+                let default = child.child_by_field_name("value")
+                    .map(|n| node_text(n, source).to_string());
+                let kind = if keyword_only { ParamKind::KeywordOnly } else { ParamKind::Positional };
+                params.push(ParamInfo { name, type_annotation: None, default, kind, optional: false });
+            }
+            "typed_parameter" => {
+                let type_annotation = child.child_by_field_name("type")
+                    .map(|n| node_text(n, source).to_string());
+                match child.named_child(0) {
+                    Some(inner) if inner.kind() == "list_splat_pattern" => {
+                        if let Some(n) = inner.named_child(0) {
+                            params.push(ParamInfo {
+                                name: node_text(n, source).to_string(),
+                                type_annotation,
+                                default: None,
+                                kind: ParamKind::VarArgs,
+                                optional: false,
+                            });
+                        }
+                        keyword_only = true;
+                    }
+                    Some(inner) if inner.kind() == "dictionary_splat_pattern" => {
+                        if let Some(n) = inner.named_child(0) {
+                            params.push(ParamInfo {
+                                name: node_text(n, source).to_string(),
+                                type_annotation,
+                                default: None,
+                                kind: ParamKind::KwArgs,
+                                optional: false,
+                            });
+                        }
+                    }
+                    Some(inner) => {
+                        let kind = if keyword_only { ParamKind::KeywordOnly } else { ParamKind::Positional };
+                        params.push(ParamInfo {
+                            name: node_text(inner, source).to_string(),
+                            type_annotation,
+                            default: None,
+                            kind,
+                            optional: false,
+                        });
+                    }
+                    None => {}
                 }
             }
+            "typed_default_parameter" => {
+                let name = child.child_by_field_name("name")
+                    .map(|n| node_text(n, source).to_string())
+                    .unwrap_or_default();
+                let type_annotation = child.child_by_field_name("type")
+                    .map(|n| node_text(n, source).to_string());
+                let default = child.child_by_field_name("value")
+                    .map(|n| node_text(n, source).to_string());
+                let kind = if keyword_only { ParamKind::KeywordOnly } else { ParamKind::Positional };
+                params.push(ParamInfo { name, type_annotation, default, kind, optional: false });
+            }
+            "*" => keyword_only = true,
             _ => {}
         }
     }
     params
 }
 
-fn extract_class_methods(class_node: tree_sitter::Node, source: &[u8]) -> Vec<String> {
+fn extract_class_methods(class_node: tree_sitter::Node, source: &[u8]) -> Vec<super::MethodInfo> {
     let mut methods = Vec::new();
     if let Some(body) = class_node.child_by_field_name("body") {
         let mut cursor = body.walk();
         for child in body.children(&mut cursor) {
             if let Some(func) = unwrap_decorated(child, "function_definition") {
                 if let Some(n) = func.child_by_field_name("name") {
-                    methods.push(node_text(n, source).to_string());
+                    methods.push(super::MethodInfo::simple(node_text(n, source).to_string()));
                 }
             }
         }
@@ -314,7 +506,58 @@ class Animal:
         let classes = adapter.extract_classes(&tree, src.as_bytes());
         assert_eq!(classes.len(), 1);
         assert_eq!(classes[0].name, "Animal");
-        assert!(classes[0].methods.contains(&"speak".to_string()));
+        assert!(classes[0].methods.iter().any(|m| m.name == "speak"));
+    }
+
+    #[test]
+    fn test_python_extract_decorators() {
+        let src = r#"
+@app.route("/x")
+@staticmethod
+def handler():
+    pass
+
+@dataclass
+class Point:
+    x: int
+    y: int
+
+def plain():
+    pass
+"#;
+        let tree = parse(src);
+        let adapter = PythonAdapter::new();
+        let fns = adapter.extract_functions(&tree, src.as_bytes());
+        let handler = fns.iter().find(|f| f.name == "handler").unwrap();
+        assert_eq!(handler.decorators, vec!["app.route(\"/x\")".to_string(), "staticmethod".to_string()]);
+        let plain = fns.iter().find(|f| f.name == "plain").unwrap();
+        assert!(plain.decorators.is_empty());
+
+        let classes = adapter.extract_classes(&tree, src.as_bytes());
+        let point = classes.iter().find(|c| c.name == "Point").unwrap();
+        assert_eq!(point.decorators, vec!["dataclass".to_string()]);
+    }
+
+    #[test]
+    fn test_python_extract_rich_params_and_return_type() {
+        let src = r#"
+def handler(self, name: str, count: int = 0, *args, flag: bool = False, **kwargs) -> dict:
+    pass
+"#;
+        let tree = parse(src);
+        let adapter = PythonAdapter::new();
+        let fns = adapter.extract_functions(&tree, src.as_bytes());
+        let handler = fns.iter().find(|f| f.name == "handler").unwrap();
+        assert_eq!(handler.return_type.as_deref(), Some("dict"));
+
+        let by_name = |n: &str| handler.params.iter().find(|p| p.name == n).unwrap();
+        assert_eq!(by_name("self").kind, super::ParamKind::Positional);
+        assert_eq!(by_name("name").type_annotation.as_deref(), Some("str"));
+        assert_eq!(by_name("count").default.as_deref(), Some("0"));
+        assert_eq!(by_name("args").kind, super::ParamKind::VarArgs);
+        assert_eq!(by_name("flag").kind, super::ParamKind::KeywordOnly);
+        assert_eq!(by_name("flag").default.as_deref(), Some("False"));
+        assert_eq!(by_name("kwargs").kind, super::ParamKind::KwArgs);
     }
 
     #[test]
@@ -332,4 +575,18 @@ def _private(): pass
         assert!(exports.iter().any(|e| e.name == "foo"));
         assert!(exports.iter().any(|e| e.name == "bar"));
     }
+
+    #[test]
+    fn test_python_extract_calls() {
+        let src = r#"
+def outer():
+    helper()
+    obj.method()
+"#;
+        let tree = parse(src);
+        let adapter = PythonAdapter::new();
+        let calls = adapter.extract_calls(&tree, src.as_bytes());
+        assert!(calls.iter().any(|c| c.caller == "outer" && c.callee == "helper"));
+        assert!(calls.iter().any(|c| c.caller == "outer" && c.callee == "method"));
+    }
 }
@@ -0,0 +1,298 @@
+use tree_sitter::{Language as TsLanguage, Query, QueryCursor, QueryMatch, Tree};
+use super::{ClassInfo, ExportInfo, FunctionInfo, ImportInfo, LanguageAdapter, ParamInfo, node_text, strip_quotes};
+
+/// 基于声明式 tree-sitter 查询的适配器——目前仅用于演示/测试，`get_adapter()` 不构造它，
+/// 八个手写适配器（`GoAdapter` 等）都还没有改为基于它实现
+///
+/// 其余适配器（如 `GoAdapter`）都是手写 `walk_nodes` + `child_by_field_name` 遍历，
+/// 每种语言重复一遍几乎相同的逻辑。`QueryAdapter` 改用 S-表达式查询字符串描述提取规则
+/// （类比编辑器 bundle 的 `.scm` highlight/tags 查询），带有固定命名的捕获组：
+/// `@function.def`/`@function.name`/`@function.params`、`@class.def`/`@class.name`、
+/// `@import.def`/`@import.source`、`@export.def`/`@export.name`。新增一门语言时只需提供
+/// 查询文本，不必再写一遍遍历代码；查询在构造时编译一次并缓存在适配器实例上。
+pub struct QueryAdapter {
+    language: TsLanguage,
+    function_query: Option<Query>,
+    class_query: Option<Query>,
+    import_query: Option<Query>,
+    export_query: Option<Query>,
+}
+
+/// 构造 `QueryAdapter` 所需的查询文本；某一类别留空则该类别的提取结果始终为空
+pub struct QueryAdapterConfig {
+    pub language: TsLanguage,
+    pub function_query: Option<String>,
+    pub class_query: Option<String>,
+    pub import_query: Option<String>,
+    pub export_query: Option<String>,
+}
+
+impl QueryAdapter {
+    /// 编译配置中的查询；某个类别编译失败时静默跳过（置为 None）而不是 panic，
+    /// 便于用户提供查询文本后快速试错
+    pub fn new(config: QueryAdapterConfig) -> Self {
+        let compile = |src: &Option<String>| -> Option<Query> {
+            src.as_ref().and_then(|s| Query::new(&config.language, s).ok())
+        };
+        let function_query = compile(&config.function_query);
+        let class_query = compile(&config.class_query);
+        let import_query = compile(&config.import_query);
+        let export_query = compile(&config.export_query);
+        Self {
+            language: config.language,
+            function_query,
+            class_query,
+            import_query,
+            export_query,
+        }
+    }
+}
+
+/// 在一次匹配中查找指定名称捕获组对应的节点
+fn capture_node<'t>(
+    query: &Query,
+    m: &QueryMatch<'t, 't>,
+    name: &str,
+) -> Option<tree_sitter::Node<'t>> {
+    let idx = query.capture_names().iter().position(|n| *n == name)?;
+    m.captures
+        .iter()
+        .find(|c| c.index as usize == idx)
+        .map(|c| c.node)
+}
+
+/// 判断节点是否位于某个 kind 包含 "export" 的祖先节点之下（JS/TS 风格的 export 包裹）
+fn is_inside_export(node: tree_sitter::Node) -> bool {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        if n.kind().contains("export") {
+            return true;
+        }
+        current = n.parent();
+    }
+    false
+}
+
+/// 从参数列表节点中提取各个 identifier 类型子孙作为参数名（忽略类型标注等）
+fn extract_param_names(params_node: tree_sitter::Node, source: &[u8]) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut cursor = params_node.walk();
+    for child in params_node.children(&mut cursor) {
+        if child.kind().ends_with("identifier") {
+            names.push(node_text(child, source).to_string());
+        } else {
+            let mut inner = child.walk();
+            for grandchild in child.children(&mut inner) {
+                if grandchild.kind().ends_with("identifier") {
+                    names.push(node_text(grandchild, source).to_string());
+                    break;
+                }
+            }
+        }
+    }
+    names
+}
+
+impl LanguageAdapter for QueryAdapter {
+    fn language(&self) -> TsLanguage {
+        self.language.clone()
+    }
+
+    fn extract_functions(&self, tree: &Tree, source: &[u8]) -> Vec<FunctionInfo> {
+        let query = match &self.function_query {
+            Some(q) => q,
+            None => return Vec::new(),
+        };
+        let mut cursor = QueryCursor::new();
+        let mut functions = Vec::new();
+        let mut matches = cursor.matches(query, tree.root_node(), source);
+        while let Some(m) = matches.next() {
+            let name_node = match capture_node(query, m, "function.name") {
+                Some(n) => n,
+                None => continue,
+            };
+            let def_node = capture_node(query, m, "function.def").unwrap_or(name_node);
+            let params = capture_node(query, m, "function.params")
+                .map(|p| extract_param_names(p, source))
+                .unwrap_or_default();
+            functions.push(FunctionInfo {
+                name: node_text(name_node, source).to_string(),
+                start_line: def_node.start_position().row + 1,
+                end_line: def_node.end_position().row + 1,
+                params: params.into_iter().map(ParamInfo::simple).collect(),
+                is_exported: is_inside_export(def_node),
+                complexity: 1, // 查询配置里没有分支节点类别，暂不计算圈复杂度
+                return_type: None,
+                type_parameters: None,
+                metrics: super::compute_symbol_metrics(def_node, source),
+                decorators: Vec::new(),
+                doc: None,
+            });
+        }
+        functions
+    }
+
+    fn extract_imports(&self, tree: &Tree, source: &[u8]) -> Vec<ImportInfo> {
+        let query = match &self.import_query {
+            Some(q) => q,
+            None => return Vec::new(),
+        };
+        let mut cursor = QueryCursor::new();
+        let mut imports = Vec::new();
+        let mut matches = cursor.matches(query, tree.root_node(), source);
+        while let Some(m) = matches.next() {
+            let source_node = match capture_node(query, m, "import.source") {
+                Some(n) => n,
+                None => continue,
+            };
+            let src = strip_quotes(node_text(source_node, source));
+            let symbol = src.rsplit('/').next().unwrap_or(&src).to_string();
+            imports.push(ImportInfo {
+                source: src,
+                names: vec![symbol],
+                is_default: false,
+                dynamic: false,
+            });
+        }
+        imports
+    }
+
+    fn extract_exports(&self, tree: &Tree, source: &[u8]) -> Vec<ExportInfo> {
+        let query = match &self.export_query {
+            Some(q) => q,
+            None => return Vec::new(),
+        };
+        let mut cursor = QueryCursor::new();
+        let mut exports = Vec::new();
+        let mut matches = cursor.matches(query, tree.root_node(), source);
+        while let Some(m) = matches.next() {
+            let name_node = match capture_node(query, m, "export.name") {
+                Some(n) => n,
+                None => continue,
+            };
+            exports.push(ExportInfo {
+                name: node_text(name_node, source).to_string(),
+                kind: "export".into(),
+                doc: None,
+                reexport_source: None,
+                star: false,
+            });
+        }
+        exports
+    }
+
+    fn extract_classes(&self, tree: &Tree, source: &[u8]) -> Vec<ClassInfo> {
+        let query = match &self.class_query {
+            Some(q) => q,
+            None => return Vec::new(),
+        };
+        let mut cursor = QueryCursor::new();
+        let mut classes = Vec::new();
+        let mut matches = cursor.matches(query, tree.root_node(), source);
+        while let Some(m) = matches.next() {
+            let name_node = match capture_node(query, m, "class.name") {
+                Some(n) => n,
+                None => continue,
+            };
+            let def_node = capture_node(query, m, "class.def").unwrap_or(name_node);
+            classes.push(ClassInfo {
+                name: node_text(name_node, source).to_string(),
+                start_line: def_node.start_position().row + 1,
+                end_line: def_node.end_position().row + 1,
+                methods: Vec::new(),
+                kind: "class".into(),
+                metrics: super::compute_symbol_metrics(def_node, source),
+                decorators: Vec::new(),
+                doc: None,
+                members: Vec::new(),
+            });
+        }
+        classes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn go_config() -> QueryAdapterConfig {
+        QueryAdapterConfig {
+            language: tree_sitter_go::LANGUAGE.into(),
+            function_query: Some(
+                "(function_declaration name: (identifier) @function.name parameters: (parameter_list) @function.params) @function.def".to_string(),
+            ),
+            class_query: Some(
+                "(type_spec name: (type_identifier) @class.name type: (struct_type)) @class.def".to_string(),
+            ),
+            import_query: Some(
+                "(import_spec path: (interpreted_string_literal) @import.source) @import.def".to_string(),
+            ),
+            export_query: None,
+        }
+    }
+
+    fn parse(source: &str) -> Tree {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&tree_sitter_go::LANGUAGE.into()).unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    #[test]
+    fn test_query_adapter_extracts_go_functions() {
+        let src = r#"package main
+
+func Hello(name string) string {
+    return name
+}
+"#;
+        let tree = parse(src);
+        let adapter = QueryAdapter::new(go_config());
+        let fns = adapter.extract_functions(&tree, src.as_bytes());
+        assert_eq!(fns.len(), 1);
+        assert_eq!(fns[0].name, "Hello");
+        assert_eq!(fns[0].params.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(), vec!["name"]);
+    }
+
+    #[test]
+    fn test_query_adapter_extracts_go_structs() {
+        let src = r#"package main
+
+type Server struct {
+    host string
+}
+"#;
+        let tree = parse(src);
+        let adapter = QueryAdapter::new(go_config());
+        let classes = adapter.extract_classes(&tree, src.as_bytes());
+        assert_eq!(classes.len(), 1);
+        assert_eq!(classes[0].name, "Server");
+    }
+
+    #[test]
+    fn test_query_adapter_extracts_go_imports() {
+        let src = r#"package main
+
+import "net/http"
+"#;
+        let tree = parse(src);
+        let adapter = QueryAdapter::new(go_config());
+        let imports = adapter.extract_imports(&tree, src.as_bytes());
+        assert_eq!(imports.len(), 1);
+        assert_eq!(imports[0].source, "net/http");
+    }
+
+    #[test]
+    fn test_query_adapter_missing_query_returns_empty() {
+        let config = QueryAdapterConfig {
+            language: tree_sitter_go::LANGUAGE.into(),
+            function_query: None,
+            class_query: None,
+            import_query: None,
+            export_query: None,
+        };
+        let adapter = QueryAdapter::new(config);
+        let tree = parse("package main\n");
+        assert!(adapter.extract_functions(&tree, b"package main\n").is_empty());
+        assert!(adapter.extract_classes(&tree, b"package main\n").is_empty());
+    }
+}
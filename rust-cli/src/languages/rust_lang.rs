@@ -1,9 +1,25 @@
 use tree_sitter::{Language, Tree};
 use super::{
-    ClassInfo, ExportInfo, FunctionInfo, ImportInfo, LanguageAdapter,
-    node_text, walk_nodes,
+    CallInfo, ClassInfo, ExportInfo, FunctionInfo, ImportInfo, LanguageAdapter,
+    compute_complexity, node_text, walk_nodes,
 };
 
+/// 嵌套函数/闭包的 kind：遇到时停止向下累计复杂度
+const STOP_KINDS: &[&str] = &["function_item", "closure_expression"];
+
+/// 判断节点是否是一个计入圈复杂度的分支节点
+fn is_branch_node(node: tree_sitter::Node, source: &[u8]) -> bool {
+    match node.kind() {
+        "if_expression" | "if_let_expression" | "while_expression" | "while_let_expression"
+        | "for_expression" | "loop_expression" | "match_arm" => true,
+        "binary_expression" => node
+            .child_by_field_name("operator")
+            .map(|op| matches!(node_text(op, source), "&&" | "||"))
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
 pub struct RustAdapter;
 
 impl RustAdapter {
@@ -27,22 +43,24 @@ impl LanguageAdapter for RustAdapter {
                 Some(n) => n,
                 None => return,
             };
-            let impl_type = get_impl_type(node, source);
-            let name = if let Some(ref t) = impl_type {
-                format!("{}::{}", t, node_text(name_node, source))
-            } else {
-                node_text(name_node, source).to_string()
-            };
+            let name = qualify_function_name(node, name_node, source);
             let params = node.child_by_field_name("parameters")
                 .map(|p| extract_rust_params(p, source))
                 .unwrap_or_default();
             let is_exported = has_pub_visibility(node, source);
+            let complexity = compute_complexity(node, STOP_KINDS, &mut |n| is_branch_node(n, source));
             functions.push(FunctionInfo {
                 name,
                 start_line: node.start_position().row + 1,
                 end_line: node.end_position().row + 1,
-                params,
+                params: params.into_iter().map(super::ParamInfo::simple).collect(),
                 is_exported,
+                complexity,
+                return_type: None,
+                type_parameters: None,
+                metrics: super::compute_symbol_metrics(node, source),
+                decorators: Vec::new(),
+                doc: None,
             });
         });
         functions
@@ -58,6 +76,7 @@ impl LanguageAdapter for RustAdapter {
                 source: String::new(),
                 names: Vec::new(),
                 is_default: false,
+                dynamic: false,
             };
             parse_use_tree(node, source, &mut result);
             if !result.source.is_empty() {
@@ -70,6 +89,23 @@ impl LanguageAdapter for RustAdapter {
     fn extract_exports(&self, tree: &Tree, source: &[u8]) -> Vec<ExportInfo> {
         let mut exports = Vec::new();
         walk_nodes(tree.root_node(), &mut |node| {
+            if node.kind() == "macro_definition" {
+                // macro_rules! 没有 `pub` 关键字能表达跨 crate 可见性，
+                // 靠紧邻的 `#[macro_export]` 属性（見 `has_macro_export_attribute`）；
+                // `pub macro name { .. }`（macro 2.0 语法）仍然走 `has_pub_visibility`
+                if has_pub_visibility(node, source) || has_macro_export_attribute(node, source) {
+                    if let Some(n) = node.child_by_field_name("name") {
+                        exports.push(ExportInfo {
+                            name: node_text(n, source).to_string(),
+                            kind: "macro".into(),
+                            doc: None,
+                            reexport_source: None,
+                            star: false,
+                        });
+                    }
+                }
+                return;
+            }
             if !has_pub_visibility(node, source) {
                 return;
             }
@@ -89,6 +125,9 @@ impl LanguageAdapter for RustAdapter {
                 exports.push(ExportInfo {
                     name: node_text(n, source).to_string(),
                     kind: kind.into(),
+                    doc: None,
+                    reexport_source: None,
+                    star: false,
                 });
             }
         });
@@ -107,6 +146,10 @@ impl LanguageAdapter for RustAdapter {
                             end_line: node.end_position().row + 1,
                             methods: Vec::new(),
                             kind: "struct".into(),
+                            metrics: super::compute_symbol_metrics(node, source),
+                            decorators: Vec::new(),
+                            doc: None,
+                            members: Vec::new(),
                         });
                     }
                 }
@@ -118,6 +161,10 @@ impl LanguageAdapter for RustAdapter {
                             end_line: node.end_position().row + 1,
                             methods: Vec::new(),
                             kind: "enum".into(),
+                            metrics: super::compute_symbol_metrics(node, source),
+                            decorators: Vec::new(),
+                            doc: None,
+                            members: Vec::new(),
                         });
                     }
                 }
@@ -129,14 +176,200 @@ impl LanguageAdapter for RustAdapter {
                             end_line: node.end_position().row + 1,
                             methods: Vec::new(),
                             kind: "trait".into(),
+                            metrics: super::compute_symbol_metrics(node, source),
+                            decorators: Vec::new(),
+                            doc: None,
+                            members: Vec::new(),
                         });
                     }
                 }
                 _ => {}
             }
         });
+
+        // 第二遍：把 impl 块（含 trait 默认方法）里的方法名挂到对应的 ClassInfo 上
+        let methods_by_type = collect_methods_by_type(tree.root_node(), source);
+        for class in &mut classes {
+            if let Some(methods) = methods_by_type.get(&class.name) {
+                class.methods = methods.clone();
+            }
+        }
         classes
     }
+
+    fn extract_calls(&self, tree: &Tree, source: &[u8]) -> Vec<CallInfo> {
+        let mut calls = Vec::new();
+        walk_nodes(tree.root_node(), &mut |node| {
+            if node.kind() == "macro_invocation" {
+                record_macro_invocation(node, source, &mut calls);
+                return;
+            }
+            let callee = match node.kind() {
+                "call_expression" => node
+                    .child_by_field_name("function")
+                    .and_then(|f| call_expression_callee(f, source)),
+                "method_call_expression" => node
+                    .child_by_field_name("method")
+                    .map(|m| node_text(m, source).to_string()),
+                _ => None,
+            };
+            let Some(callee) = callee else { return; };
+            let Some(caller) = enclosing_function_name(node, source) else { return; };
+            calls.push(CallInfo {
+                caller,
+                callee,
+                line: node.start_position().row + 1,
+            });
+        });
+        calls
+    }
+}
+
+/// 给 `function_item` 的名字套上 `get_impl_type` 找到的容器类型，例如
+/// `impl Foo { fn bar() }` → `Foo::bar`；不在任何 `impl` 块里则保持裸名
+fn qualify_function_name(function_node: tree_sitter::Node, name_node: tree_sitter::Node, source: &[u8]) -> String {
+    match get_impl_type(function_node, source) {
+        Some(t) => format!("{}::{}", t, node_text(name_node, source)),
+        None => node_text(name_node, source).to_string(),
+    }
+}
+
+/// `call_expression` 的 `function` 字段可以是裸标识符、`scoped_identifier`
+/// （取最后一段，如 `std::cmp::max` → `max`）或 `field_expression`（取 `field`，
+/// 即把通过结构体字段保存的函数指针调用当作对该字段名的调用）
+fn call_expression_callee(func_node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    match func_node.kind() {
+        "identifier" => Some(node_text(func_node, source).to_string()),
+        "scoped_identifier" => func_node
+            .child_by_field_name("name")
+            .map(|n| node_text(n, source).to_string()),
+        "field_expression" => func_node
+            .child_by_field_name("field")
+            .map(|n| node_text(n, source).to_string()),
+        _ => None,
+    }
+}
+
+/// 从调用点向上找到最近的 `function_item`，返回它的限定名（见 `qualify_function_name`）；
+/// 不在任何函数体内（例如 `const`/`static` 初始化表达式里的调用）则返回 `None`，
+/// 调用方应跳过这类找不到调用者的边
+fn enclosing_function_name(node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        if n.kind() == "function_item" {
+            let name_node = n.child_by_field_name("name")?;
+            return Some(qualify_function_name(n, name_node, source));
+        }
+        current = n.parent();
+    }
+    None
+}
+
+/// 宏展开后的代码对 `extract_functions`/`extract_calls` 的纯 AST 遍历完全不可见，
+/// `format!`/`vec!`/自定义 derive 经常包着真正的 API 用法，所以把宏调用当成一条调用边
+/// 记下来：`caller` 调用了这个宏（`callee` 是宏名，和 `call_expression_callee` 一样取
+/// 最后一段），不在任何函数体内（模块顶层的 `lazy_static! { .. }` 这类）就用 `"<module>"`
+/// 占位，而不是像普通调用那样直接丢弃——宏调用在模块顶层很常见，不应该对它们视而不见。
+/// 同时把 token tree 里顶层的路径参数也各记一条边，好让 impact 分析顺着宏展开追下去。
+fn record_macro_invocation(node: tree_sitter::Node, source: &[u8], calls: &mut Vec<CallInfo>) {
+    let Some(callee) = node.child_by_field_name("macro").and_then(|m| call_expression_callee(m, source)) else {
+        return;
+    };
+    let caller = enclosing_function_name(node, source).unwrap_or_else(|| "<module>".to_string());
+    let line = node.start_position().row + 1;
+    calls.push(CallInfo { caller: caller.clone(), callee, line });
+
+    if let Some(token_tree) = super::find_child_of_type(node, "token_tree") {
+        for arg in macro_argument_identifiers(token_tree, source) {
+            calls.push(CallInfo { caller: caller.clone(), callee: arg, line });
+        }
+    }
+}
+
+/// 扫描宏调用 token tree 的直接子节点，收集看起来像路径参数的标识符/`a::b::c` 路径
+/// （取最后一段）。只看顶层，不递归进嵌套的分组（`(..)`/`[..]`/`{..}` 在 token tree
+/// 里也会被解析成子节点）——宏的 token tree 语法自由，深入里面并不是稳定的 AST
+/// 结构，只捞顶层参数已经够 impact 分析用了
+fn macro_argument_identifiers(token_tree: tree_sitter::Node, source: &[u8]) -> Vec<String> {
+    let mut idents = Vec::new();
+    let mut cursor = token_tree.walk();
+    for child in token_tree.children(&mut cursor) {
+        match child.kind() {
+            "identifier" => idents.push(node_text(child, source).to_string()),
+            "scoped_identifier" => {
+                if let Some(n) = child.child_by_field_name("name") {
+                    idents.push(node_text(n, source).to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    idents
+}
+
+/// 收集每个类型名能看到的方法名：来自 `impl Type { .. }` / `impl Trait for Type { .. }`
+/// 块（`impl` 的 `type` 字段总是实现目标类型，而不是 trait，见 `get_impl_type`）里
+/// 直接包含的 `function_item`，以及 `trait_item` 自己 body 里定义的默认方法
+/// （也就是带函数体、不止是签名的那些）。只看每个 impl/trait body 的直接子节点，
+/// 不递归进方法体内部（避免把内部嵌套的 `fn` 误当成方法）
+fn collect_methods_by_type(root: tree_sitter::Node, source: &[u8]) -> std::collections::HashMap<String, Vec<super::MethodInfo>> {
+    let mut methods: std::collections::HashMap<String, Vec<super::MethodInfo>> = std::collections::HashMap::new();
+    walk_nodes(root, &mut |node| match node.kind() {
+        "impl_item" => {
+            let Some(type_name) = node
+                .child_by_field_name("type")
+                .map(|t| base_type_name(t, source))
+            else {
+                return;
+            };
+            let Some(body) = node.child_by_field_name("body") else { return; };
+            let mut cursor = body.walk();
+            for child in body.children(&mut cursor) {
+                if child.kind() == "function_item" {
+                    if let Some(name_node) = child.child_by_field_name("name") {
+                        methods.entry(type_name.clone()).or_default().push(super::MethodInfo::simple(node_text(name_node, source).to_string()));
+                    }
+                }
+            }
+        }
+        "trait_item" => {
+            let Some(trait_name_node) = node.child_by_field_name("name") else { return; };
+            let trait_name = node_text(trait_name_node, source).to_string();
+            let Some(body) = node.child_by_field_name("body") else { return; };
+            let mut cursor = body.walk();
+            for child in body.children(&mut cursor) {
+                if child.kind() == "function_item" && child.child_by_field_name("body").is_some() {
+                    if let Some(name_node) = child.child_by_field_name("name") {
+                        methods.entry(trait_name.clone()).or_default().push(super::MethodInfo::simple(node_text(name_node, source).to_string()));
+                    }
+                }
+            }
+        }
+        _ => {}
+    });
+    methods
+}
+
+/// 剥掉泛型参数，取 impl 目标类型的基础类型名，比如 `Foo<T>` → `Foo`，
+/// 这样才能跟 `struct_item`/`enum_item` 的裸名字匹配上
+fn base_type_name(node: tree_sitter::Node, source: &[u8]) -> String {
+    match node.kind() {
+        "generic_type" => node
+            .child_by_field_name("type")
+            .map(|t| base_type_name(t, source))
+            .unwrap_or_else(|| node_text(node, source).to_string()),
+        _ => node_text(node, source).to_string(),
+    }
+}
+
+/// `use` 的 `source` 是否指向当前 crate 内部（`crate::`/`self::`/`super::` 开头，
+/// 或裸 `crate`/`self`/`super`），而不是外部 crate 或标准库——`convert_imports`
+/// 靠这个区分 `is_external`，只有内部 import 才会交给 `resolver::resolve_symbols`
+/// 去跨文件解析
+pub fn is_internal_rust_import(source: &str) -> bool {
+    source == "crate" || source.starts_with("crate::")
+        || source == "self" || source.starts_with("self::")
+        || source == "super" || source.starts_with("super::")
 }
 
 fn get_impl_type(node: tree_sitter::Node, source: &[u8]) -> Option<String> {
@@ -151,6 +384,26 @@ fn get_impl_type(node: tree_sitter::Node, source: &[u8]) -> Option<String> {
     None
 }
 
+/// `macro_definition` 是否带 `#[macro_export]`。tree-sitter 把属性解析成紧邻的
+/// 前一个兄弟节点（`attribute_item`），不是子节点，所以要沿着 prev_sibling 往前找，
+/// 中间允许跳过注释，遇到别的节点类型就说明已经不属于这个宏的属性列表了
+fn has_macro_export_attribute(node: tree_sitter::Node, source: &[u8]) -> bool {
+    let mut sibling = node.prev_sibling();
+    while let Some(n) = sibling {
+        match n.kind() {
+            "attribute_item" => {
+                if node_text(n, source).contains("macro_export") {
+                    return true;
+                }
+            }
+            "line_comment" | "block_comment" => {}
+            _ => break,
+        }
+        sibling = n.prev_sibling();
+    }
+    false
+}
+
 fn has_pub_visibility(node: tree_sitter::Node, source: &[u8]) -> bool {
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
@@ -269,6 +522,34 @@ impl Foo {
         assert!(fns.iter().any(|f| f.name == "Foo::method"));
     }
 
+    #[test]
+    fn test_rust_complexity_counts_branches() {
+        let src = r#"
+fn simple() -> i32 {
+    1
+}
+
+fn branchy(x: i32) -> i32 {
+    if x > 0 && x < 10 {
+        match x {
+            1 => 1,
+            _ => 0,
+        }
+    } else {
+        0
+    }
+}
+"#;
+        let tree = parse(src);
+        let adapter = RustAdapter::new();
+        let fns = adapter.extract_functions(&tree, src.as_bytes());
+        let simple = fns.iter().find(|f| f.name == "simple").unwrap();
+        let branchy = fns.iter().find(|f| f.name == "branchy").unwrap();
+        assert_eq!(simple.complexity, 1);
+        // if + && + 2 match arms = 4 decision points
+        assert_eq!(branchy.complexity, 5);
+    }
+
     #[test]
     fn test_rust_extract_imports() {
         let src = "use std::io::{Read, Write};\nuse crate::utils;\n";
@@ -294,4 +575,80 @@ pub trait Handler {}
         assert!(classes.iter().any(|c| c.name == "Status" && c.kind == "enum"));
         assert!(classes.iter().any(|c| c.name == "Handler" && c.kind == "trait"));
     }
+
+    #[test]
+    fn test_rust_extract_classes_resolves_methods_from_impl_blocks() {
+        let src = r#"
+struct Server {
+    host: String,
+}
+impl Server {
+    pub fn new() -> Self { todo!() }
+    fn listen(&self) {}
+}
+
+trait Handler {
+    fn handle(&self);
+    fn describe(&self) -> String { "handler".to_string() }
+}
+
+struct Router;
+impl Handler for Router {
+    fn handle(&self) {}
+}
+"#;
+        let tree = parse(src);
+        let adapter = RustAdapter::new();
+        let classes = adapter.extract_classes(&tree, src.as_bytes());
+
+        let method_names = |c: &ClassInfo| c.methods.iter().map(|m| m.name.clone()).collect::<Vec<_>>();
+
+        let server = classes.iter().find(|c| c.name == "Server").unwrap();
+        assert_eq!(method_names(server), vec!["new".to_string(), "listen".to_string()]);
+
+        let handler = classes.iter().find(|c| c.name == "Handler").unwrap();
+        assert_eq!(method_names(handler), vec!["describe".to_string()]);
+
+        let router = classes.iter().find(|c| c.name == "Router").unwrap();
+        assert_eq!(method_names(router), vec!["handle".to_string()]);
+    }
+
+    #[test]
+    fn test_rust_extract_exports_includes_exported_macros() {
+        let src = r#"
+#[macro_export]
+macro_rules! log_error {
+    ($msg:expr) => { eprintln!("{}", $msg) };
+}
+
+macro_rules! private_helper {
+    () => {};
+}
+"#;
+        let tree = parse(src);
+        let adapter = RustAdapter::new();
+        let exports = adapter.extract_exports(&tree, src.as_bytes());
+        assert!(exports.iter().any(|e| e.name == "log_error" && e.kind == "macro"));
+        assert!(!exports.iter().any(|e| e.name == "private_helper"));
+    }
+
+    #[test]
+    fn test_rust_extract_calls_includes_macro_invocations_and_path_args() {
+        let src = r#"
+fn handler() {
+    vec![helper, other::thing];
+}
+
+lazy_static! {
+    static ref CONFIG: String = String::new();
+}
+"#;
+        let tree = parse(src);
+        let adapter = RustAdapter::new();
+        let calls = adapter.extract_calls(&tree, src.as_bytes());
+        assert!(calls.iter().any(|c| c.caller == "handler" && c.callee == "vec"));
+        assert!(calls.iter().any(|c| c.caller == "handler" && c.callee == "helper"));
+        assert!(calls.iter().any(|c| c.caller == "handler" && c.callee == "thing"));
+        assert!(calls.iter().any(|c| c.caller == "<module>" && c.callee == "lazy_static"));
+    }
 }
@@ -1,9 +1,30 @@
-use tree_sitter::{Language, Tree};
+use tree_sitter::{Language, Node, Tree};
 use super::{
-    ClassInfo, ExportInfo, FunctionInfo, ImportInfo, LanguageAdapter,
+    CallInfo, ClassInfo, ExportInfo, FunctionInfo, ImportInfo, LanguageAdapter,
+    Member, MemberKind, ParamInfo, ParamKind,
+    compute_complexity, extract_cjs_export, extract_cjs_or_dynamic_import, extract_reexports,
     find_child_of_type, node_text, strip_quotes, walk_nodes,
 };
 
+/// 嵌套函数/闭包定义的 kind：遇到时停止向下累计复杂度，让内层函数有自己的分数
+const STOP_KINDS: &[&str] = &[
+    "function_declaration", "function_expression", "arrow_function",
+    "method_definition", "generator_function_declaration", "generator_function",
+];
+
+/// 判断节点是否是一个计入圈复杂度的分支节点
+fn is_branch_node(node: tree_sitter::Node, source: &[u8]) -> bool {
+    match node.kind() {
+        "if_statement" | "for_statement" | "for_in_statement" | "while_statement"
+        | "do_statement" | "switch_case" | "catch_clause" | "ternary_expression" => true,
+        "binary_expression" => node
+            .child_by_field_name("operator")
+            .map(|op| matches!(node_text(op, source), "&&" | "||"))
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
 pub struct TypeScriptAdapter {
     tsx: bool,
 }
@@ -50,17 +71,27 @@ impl LanguageAdapter for TypeScriptAdapter {
                                     if let Some(name_node) = child.child_by_field_name("name") {
                                         let name = node_text(name_node, source).to_string();
                                         let params = val.child_by_field_name("parameters")
-                                            .map(|p| extract_params_text(p, source))
+                                            .map(|p| extract_ts_params(p, source))
                                             .unwrap_or_default();
+                                        let return_type = parse_type_annotation(val.child_by_field_name("return_type"), source);
+                                        let type_parameters = val.child_by_field_name("type_parameters")
+                                            .map(|n| node_text(n, source).to_string());
                                         let is_exported = node.parent()
                                             .map(|p| p.kind() == "export_statement")
                                             .unwrap_or(false);
+                                        let complexity = compute_complexity(val, STOP_KINDS, &mut |n| is_branch_node(n, source));
                                         functions.push(FunctionInfo {
                                             name,
                                             start_line: node.start_position().row + 1,
                                             end_line: node.end_position().row + 1,
                                             params,
                                             is_exported,
+                                            complexity,
+                                            return_type,
+                                            type_parameters,
+                                            metrics: super::compute_symbol_metrics(val, source),
+                                            decorators: Vec::new(),
+                                            doc: None,
                                         });
                                     }
                                 }
@@ -76,6 +107,11 @@ impl LanguageAdapter for TypeScriptAdapter {
     fn extract_imports(&self, tree: &Tree, source: &[u8]) -> Vec<ImportInfo> {
         let mut imports = Vec::new();
         walk_nodes(tree.root_node(), &mut |node| {
+            // `require('x')` / 动态 `import('x')`，见 [`crate::languages::extract_cjs_or_dynamic_import`]
+            if let Some(import) = extract_cjs_or_dynamic_import(node, source) {
+                imports.push(import);
+                return;
+            }
             if node.kind() != "import_statement" {
                 return;
             }
@@ -112,6 +148,7 @@ impl LanguageAdapter for TypeScriptAdapter {
                 source: src,
                 names,
                 is_default: false,
+                dynamic: false,
             });
         });
         imports
@@ -120,31 +157,44 @@ impl LanguageAdapter for TypeScriptAdapter {
     fn extract_exports(&self, tree: &Tree, source: &[u8]) -> Vec<ExportInfo> {
         let mut exports = Vec::new();
         walk_nodes(tree.root_node(), &mut |node| {
+            // `exports.NAME = ...` / `module.exports = ...`，见
+            // [`crate::languages::extract_cjs_export`]
+            if let Some(cjs_exports) = extract_cjs_export(node, source) {
+                exports.extend(cjs_exports);
+                return;
+            }
             if node.kind() != "export_statement" {
                 return;
             }
+            // barrel 文件的 re-export：`export { a, b } from '../mod'` 或
+            // `export * from './routes'`。tree-sitter 给这类 `export_statement`
+            // 标了 `source` 字段，本地声明的 export 没有，借这个区分两者
+            if let Some(reexports) = extract_reexports(node, source) {
+                exports.extend(reexports);
+                return;
+            }
             // export function foo
             if let Some(func) = find_child_of_type(node, "function_declaration") {
                 if let Some(n) = func.child_by_field_name("name") {
-                    exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "function".into() });
+                    exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "function".into(), doc: None, reexport_source: None, star: false });
                 }
             }
             // export class Foo
             if let Some(cls) = find_child_of_type(node, "class_declaration") {
                 if let Some(n) = cls.child_by_field_name("name") {
-                    exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "class".into() });
+                    exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "class".into(), doc: None, reexport_source: None, star: false });
                 }
             }
             // export interface Foo
             if let Some(iface) = find_child_of_type(node, "interface_declaration") {
                 if let Some(n) = iface.child_by_field_name("name") {
-                    exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "interface".into() });
+                    exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "interface".into(), doc: None, reexport_source: None, star: false });
                 }
             }
             // export type Foo = ...
             if let Some(ta) = find_child_of_type(node, "type_alias_declaration") {
                 if let Some(n) = ta.child_by_field_name("name") {
-                    exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "type".into() });
+                    exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "type".into(), doc: None, reexport_source: None, star: false });
                 }
             }
             // export const/let/var
@@ -153,7 +203,7 @@ impl LanguageAdapter for TypeScriptAdapter {
                 for decl in lex.children(&mut c) {
                     if decl.kind() == "variable_declarator" {
                         if let Some(n) = decl.child_by_field_name("name") {
-                            exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "variable".into() });
+                            exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "variable".into(), doc: None, reexport_source: None, star: false });
                         }
                     }
                 }
@@ -166,7 +216,7 @@ impl LanguageAdapter for TypeScriptAdapter {
                         let n = spec.child_by_field_name("name")
                             .or_else(|| spec.named_child(0));
                         if let Some(n) = n {
-                            exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "variable".into() });
+                            exports.push(ExportInfo { name: node_text(n, source).to_string(), kind: "variable".into(), doc: None, reexport_source: None, star: false });
                         }
                     }
                 }
@@ -188,17 +238,47 @@ impl LanguageAdapter for TypeScriptAdapter {
                             end_line: node.end_position().row + 1,
                             methods,
                             kind: "class".into(),
+                            metrics: super::compute_symbol_metrics(node, source),
+                            decorators: Vec::new(),
+                            doc: None,
+                            members: Vec::new(),
                         });
                     }
                 }
                 "interface_declaration" => {
                     if let Some(n) = node.child_by_field_name("name") {
+                        let members = find_child_of_type(node, "interface_body")
+                            .map(|b| extract_ts_members(b, source))
+                            .unwrap_or_default();
                         classes.push(ClassInfo {
                             name: node_text(n, source).to_string(),
                             start_line: node.start_position().row + 1,
                             end_line: node.end_position().row + 1,
                             methods: Vec::new(),
                             kind: "interface".into(),
+                            metrics: super::compute_symbol_metrics(node, source),
+                            decorators: Vec::new(),
+                            doc: None,
+                            members,
+                        });
+                    }
+                }
+                "type_alias_declaration" => {
+                    if let Some(n) = node.child_by_field_name("name") {
+                        let members = node.child_by_field_name("value")
+                            .filter(|v| v.kind() == "object_type")
+                            .map(|v| extract_ts_members(v, source))
+                            .unwrap_or_default();
+                        classes.push(ClassInfo {
+                            name: node_text(n, source).to_string(),
+                            start_line: node.start_position().row + 1,
+                            end_line: node.end_position().row + 1,
+                            methods: Vec::new(),
+                            kind: "type".into(),
+                            metrics: super::compute_symbol_metrics(node, source),
+                            decorators: Vec::new(),
+                            doc: None,
+                            members,
                         });
                     }
                 }
@@ -207,45 +287,220 @@ impl LanguageAdapter for TypeScriptAdapter {
         });
         classes
     }
+
+    fn extract_calls(&self, tree: &Tree, source: &[u8]) -> Vec<CallInfo> {
+        let mut calls = Vec::new();
+        walk_nodes(tree.root_node(), &mut |node| {
+            if node.kind() != "call_expression" {
+                return;
+            }
+            let Some(func_node) = node.child_by_field_name("function") else { return; };
+            let Some(callee) = call_expression_callee(func_node, source) else { return; };
+            let Some(caller) = enclosing_function_name(node, source) else { return; };
+            calls.push(CallInfo {
+                caller,
+                callee,
+                line: node.start_position().row + 1,
+            });
+        });
+        calls
+    }
+}
+
+/// `call_expression` 的 `function` 字段要么是裸标识符，要么是 `member_expression`
+/// （取 `property`，即 `obj.method()` → `method`），与 JS 适配器一致
+fn call_expression_callee(func_node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    match func_node.kind() {
+        "identifier" => Some(node_text(func_node, source).to_string()),
+        "member_expression" => func_node
+            .child_by_field_name("property")
+            .map(|n| node_text(n, source).to_string()),
+        _ => None,
+    }
+}
+
+/// 从调用点向上找到最近的具名函数/方法（`STOP_KINDS` 里的节点种类），返回其名字；
+/// 匿名函数表达式/箭头函数找不到绑定的变量名时返回 `None`，调用方跳过这类边
+fn enclosing_function_name(node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        match n.kind() {
+            "function_declaration" | "generator_function_declaration" | "generator_function" => {
+                return n.child_by_field_name("name").map(|name| node_text(name, source).to_string());
+            }
+            "method_definition" => {
+                return n.child_by_field_name("name").map(|name| node_text(name, source).to_string());
+            }
+            "arrow_function" | "function_expression" => {
+                // 具名场景：`const foo = (...) => {}` / `const foo = function () {}`
+                if let Some(parent) = n.parent() {
+                    if parent.kind() == "variable_declarator" {
+                        if let Some(name_node) = parent.child_by_field_name("name") {
+                            return Some(node_text(name_node, source).to_string());
+                        }
+                    }
+                }
+                return None;
+            }
+            _ => {}
+        }
+        current = n.parent();
+    }
+    None
 }
 
 fn parse_function_declaration(node: tree_sitter::Node, source: &[u8]) -> Option<FunctionInfo> {
     let name_node = node.child_by_field_name("name")?;
     let name = node_text(name_node, source).to_string();
     let params = node.child_by_field_name("parameters")
-        .map(|p| extract_params_text(p, source))
+        .map(|p| extract_ts_params(p, source))
         .unwrap_or_default();
+    let return_type = parse_type_annotation(node.child_by_field_name("return_type"), source);
+    let type_parameters = node.child_by_field_name("type_parameters")
+        .map(|n| node_text(n, source).to_string());
     let is_exported = node.parent()
         .map(|p| p.kind() == "export_statement")
         .unwrap_or(false);
+    let complexity = compute_complexity(node, STOP_KINDS, &mut |n| is_branch_node(n, source));
     Some(FunctionInfo {
         name,
         start_line: node.start_position().row + 1,
         end_line: node.end_position().row + 1,
         params,
         is_exported,
+        complexity,
+        return_type,
+        type_parameters,
+        metrics: super::compute_symbol_metrics(node, source),
+        decorators: Vec::new(),
+        doc: None,
     })
 }
 
-fn extract_params_text(params_node: tree_sitter::Node, source: &[u8]) -> Vec<String> {
-    let text = node_text(params_node, source);
-    // 简单提取参数名：去掉括号，按逗号分割
-    let inner = text.trim_start_matches('(').trim_end_matches(')');
-    if inner.trim().is_empty() {
-        return Vec::new();
+/// `type_annotation` 节点形如 `: string`（冒号是节点文本的一部分）；取其 `type` 字段拿到
+/// 不含冒号的裸类型文本，拿不到字段时退化为手动去掉前导冒号
+fn parse_type_annotation(node: Option<Node>, source: &[u8]) -> Option<String> {
+    let ann = node?;
+    let text = match ann.child_by_field_name("type") {
+        Some(inner) => node_text(inner, source).to_string(),
+        None => node_text(ann, source).trim_start_matches(':').trim().to_string(),
+    };
+    Some(text)
+}
+
+/// 沿 `formal_parameters` 的直接子节点走一遍，按 `required_parameter`/`optional_parameter`
+/// 分类，并识别出 `pattern` 字段里的 `rest_pattern`（`...args`）。解构参数
+/// （`{ a, b }: Props`）目前原样保留花括号/方括号文本作为参数名，与别的语言适配器对
+/// 解构参数的处理粒度一致（都没有进一步拆解成独立字段）
+fn extract_ts_params(params_node: Node, source: &[u8]) -> Vec<ParamInfo> {
+    let mut params = Vec::new();
+    let mut cursor = params_node.walk();
+    for child in params_node.children(&mut cursor) {
+        let optional = child.kind() == "optional_parameter";
+        if child.kind() != "required_parameter" && !optional {
+            continue;
+        }
+
+        let type_annotation = parse_type_annotation(child.child_by_field_name("type"), source);
+        let default = child.child_by_field_name("value")
+            .map(|n| node_text(n, source).to_string());
+        let Some(pattern) = child.child_by_field_name("pattern") else { continue; };
+
+        if pattern.kind() == "rest_pattern" {
+            let name = pattern.named_child(0)
+                .map(|n| node_text(n, source).to_string())
+                .unwrap_or_else(|| node_text(pattern, source).trim_start_matches("...").to_string());
+            params.push(ParamInfo {
+                name,
+                type_annotation,
+                default,
+                kind: ParamKind::Rest,
+                optional: false,
+            });
+        } else {
+            params.push(ParamInfo {
+                name: node_text(pattern, source).to_string(),
+                type_annotation,
+                default,
+                kind: ParamKind::Positional,
+                optional,
+            });
+        }
     }
-    inner.split(',')
-        .map(|s| s.trim().split(':').next().unwrap_or("").trim().to_string())
-        .filter(|s| !s.is_empty())
-        .collect()
+    params
 }
 
-fn extract_class_methods(class_node: tree_sitter::Node, source: &[u8]) -> Vec<String> {
+/// 判断成员声明是否带有 `?` 可选标记（直接子节点里有没有 `?` 这个匿名 token）
+fn has_question_mark(node: Node) -> bool {
+    let mut cursor = node.walk();
+    node.children(&mut cursor).any(|c| c.kind() == "?")
+}
+
+/// 走一遍 `interface_body`/`object_type` 的直接子节点，提取 `property_signature`
+/// （字段）、`method_signature`（方法，把参数+返回类型渲染成一个签名字符串存进
+/// `type_annotation`）、`index_signature`（`[key: string]: T`，把方括号里的内容
+/// 原样当作成员名）
+fn extract_ts_members(body: Node, source: &[u8]) -> Vec<Member> {
+    let mut members = Vec::new();
+    let mut cursor = body.walk();
+    for child in body.children(&mut cursor) {
+        match child.kind() {
+            "property_signature" => {
+                if let Some(name_node) = child.child_by_field_name("name") {
+                    members.push(Member {
+                        name: node_text(name_node, source).to_string(),
+                        kind: MemberKind::Field,
+                        optional: has_question_mark(child),
+                        type_annotation: parse_type_annotation(child.child_by_field_name("type"), source),
+                    });
+                }
+            }
+            "method_signature" => {
+                if let Some(name_node) = child.child_by_field_name("name") {
+                    let params = child.child_by_field_name("parameters")
+                        .map(|p| extract_ts_params(p, source))
+                        .unwrap_or_default();
+                    let return_type = parse_type_annotation(child.child_by_field_name("return_type"), source);
+                    let params_str = params.iter().map(|p| p.render()).collect::<Vec<_>>().join(", ");
+                    let mut signature = format!("({params_str})");
+                    if let Some(rt) = &return_type {
+                        signature.push_str(": ");
+                        signature.push_str(rt);
+                    }
+                    members.push(Member {
+                        name: node_text(name_node, source).to_string(),
+                        kind: MemberKind::Method,
+                        optional: has_question_mark(child),
+                        type_annotation: Some(signature),
+                    });
+                }
+            }
+            "index_signature" => {
+                let name = node_text(child, source)
+                    .split(':')
+                    .next()
+                    .unwrap_or("")
+                    .trim()
+                    .to_string();
+                members.push(Member {
+                    name,
+                    kind: MemberKind::Field,
+                    optional: false,
+                    type_annotation: parse_type_annotation(child.child_by_field_name("type"), source),
+                });
+            }
+            _ => {}
+        }
+    }
+    members
+}
+
+fn extract_class_methods(class_node: tree_sitter::Node, source: &[u8]) -> Vec<super::MethodInfo> {
     let mut methods = Vec::new();
     walk_nodes(class_node, &mut |node| {
         if node.kind() == "method_definition" {
             if let Some(n) = node.child_by_field_name("name") {
-                methods.push(node_text(n, source).to_string());
+                methods.push(super::MethodInfo::simple(node_text(n, source).to_string()));
             }
         }
     });
@@ -277,10 +532,69 @@ function helper() {}
         assert_eq!(fns.len(), 2);
         assert_eq!(fns[0].name, "greet");
         assert!(fns[0].is_exported);
+        assert_eq!(fns[0].return_type.as_deref(), Some("string"));
+        assert_eq!(fns[0].params[0].type_annotation.as_deref(), Some("string"));
         assert_eq!(fns[1].name, "helper");
         assert!(!fns[1].is_exported);
     }
 
+    #[test]
+    fn test_ts_extract_functions_full_signature() {
+        let src = r#"
+function greet<T>(name: string, opts?: Opts, ...rest: string[]): string {
+    return name;
+}
+const withDefault = (x: number = 1) => x;
+"#;
+        let tree = parse(src, false);
+        let adapter = TypeScriptAdapter::new();
+        let fns = adapter.extract_functions(&tree, src.as_bytes());
+        let greet = fns.iter().find(|f| f.name == "greet").unwrap();
+        assert_eq!(greet.type_parameters.as_deref(), Some("<T>"));
+        assert_eq!(greet.return_type.as_deref(), Some("string"));
+        assert_eq!(greet.params.len(), 3);
+        assert_eq!(greet.params[0].name, "name");
+        assert_eq!(greet.params[0].type_annotation.as_deref(), Some("string"));
+        assert!(!greet.params[0].optional);
+        assert_eq!(greet.params[1].name, "opts");
+        assert!(greet.params[1].optional);
+        assert_eq!(greet.params[1].type_annotation.as_deref(), Some("Opts"));
+        assert_eq!(greet.params[2].name, "rest");
+        assert_eq!(greet.params[2].kind, super::ParamKind::Rest);
+        assert_eq!(greet.params[2].type_annotation.as_deref(), Some("string[]"));
+
+        let with_default = fns.iter().find(|f| f.name == "withDefault").unwrap();
+        assert_eq!(with_default.params[0].default.as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn test_ts_complexity_counts_branches_and_stops_at_nested_functions() {
+        let src = r#"
+function plain() {
+    return 1;
+}
+function branchy(x: number) {
+    if (x > 0 && x < 10) {
+        for (let i = 0; i < x; i++) {
+            console.log(i);
+        }
+    }
+    const inner = () => {
+        if (x) { return 1; }
+    };
+    return inner();
+}
+"#;
+        let tree = parse(src, false);
+        let adapter = TypeScriptAdapter::new();
+        let fns = adapter.extract_functions(&tree, src.as_bytes());
+        let plain = fns.iter().find(|f| f.name == "plain").unwrap();
+        let branchy = fns.iter().find(|f| f.name == "branchy").unwrap();
+        assert_eq!(plain.complexity, 1);
+        // if + && + for = 3 decision points inside branchy, not counting the nested arrow function's own if
+        assert_eq!(branchy.complexity, 4);
+    }
+
     #[test]
     fn test_ts_extract_imports() {
         let src = r#"import { foo, bar } from './utils';
@@ -296,6 +610,52 @@ import React from 'react';
         assert_eq!(imports[1].source, "react");
     }
 
+    #[test]
+    fn test_ts_extract_require_and_dynamic_import() {
+        let src = "const fs = require('fs');\nasync function load() {\n    const mod = await import('./lazy');\n}\n";
+        let tree = parse(src, false);
+        let adapter = TypeScriptAdapter::new();
+        let imports = adapter.extract_imports(&tree, src.as_bytes());
+        assert_eq!(imports.len(), 2);
+        assert_eq!(imports[0].source, "fs");
+        assert!(!imports[0].dynamic);
+        assert_eq!(imports[1].source, "./lazy");
+        assert!(imports[1].dynamic);
+    }
+
+    #[test]
+    fn test_ts_extract_cjs_exports() {
+        let src = "exports.greet = function () {};\nmodule.exports.farewell = 1;\n";
+        let tree = parse(src, false);
+        let adapter = TypeScriptAdapter::new();
+        let exports = adapter.extract_exports(&tree, src.as_bytes());
+        assert!(exports.iter().any(|e| e.name == "greet"));
+        assert!(exports.iter().any(|e| e.name == "farewell"));
+    }
+
+    #[test]
+    fn test_ts_extract_module_exports_reexport() {
+        let src = "module.exports = require('./other');\n";
+        let tree = parse(src, false);
+        let adapter = TypeScriptAdapter::new();
+        let exports = adapter.extract_exports(&tree, src.as_bytes());
+        assert_eq!(exports.len(), 1);
+        assert!(exports[0].star);
+        assert_eq!(exports[0].reexport_source.as_deref(), Some("./other"));
+    }
+
+    #[test]
+    fn test_ts_extract_module_exports_object() {
+        let src = "module.exports = { a, b: 2, 'c': 3 };\n";
+        let tree = parse(src, false);
+        let adapter = TypeScriptAdapter::new();
+        let exports = adapter.extract_exports(&tree, src.as_bytes());
+        let names: Vec<_> = exports.iter().map(|e| e.name.as_str()).collect();
+        assert!(names.contains(&"a"));
+        assert!(names.contains(&"b"));
+        assert!(names.contains(&"c"));
+    }
+
     #[test]
     fn test_ts_extract_exports() {
         let src = r#"
@@ -316,6 +676,29 @@ export const MY_CONST = 42;
         assert!(names.contains(&"MY_CONST"));
     }
 
+    #[test]
+    fn test_ts_extract_reexports() {
+        let src = r#"
+export { login, logout } from '../auth/login';
+export * from './routes';
+export * as utils from './utils';
+"#;
+        let tree = parse(src, false);
+        let adapter = TypeScriptAdapter::new();
+        let exports = adapter.extract_exports(&tree, src.as_bytes());
+
+        let named: Vec<&ExportInfo> = exports.iter().filter(|e| !e.star).collect();
+        assert_eq!(named.len(), 2);
+        assert!(named.iter().all(|e| e.reexport_source.as_deref() == Some("../auth/login")));
+        assert!(named.iter().any(|e| e.name == "login"));
+        assert!(named.iter().any(|e| e.name == "logout"));
+
+        let stars: Vec<&ExportInfo> = exports.iter().filter(|e| e.star).collect();
+        assert_eq!(stars.len(), 2);
+        assert!(stars.iter().any(|e| e.reexport_source.as_deref() == Some("./routes")));
+        assert!(stars.iter().any(|e| e.reexport_source.as_deref() == Some("./utils")));
+    }
+
     #[test]
     fn test_ts_extract_classes() {
         let src = r#"
@@ -331,7 +714,22 @@ interface Runnable {}
         assert!(classes.iter().any(|c| c.name == "Animal" && c.kind == "class"));
         assert!(classes.iter().any(|c| c.name == "Runnable" && c.kind == "interface"));
         let animal = classes.iter().find(|c| c.name == "Animal").unwrap();
-        assert!(animal.methods.contains(&"speak".to_string()));
-        assert!(animal.methods.contains(&"move".to_string()));
+        assert!(animal.methods.iter().any(|m| m.name == "speak"));
+        assert!(animal.methods.iter().any(|m| m.name == "move"));
+    }
+
+    #[test]
+    fn test_ts_extract_calls() {
+        let src = r#"
+function outer() {
+    helper();
+    obj.method();
+}
+"#;
+        let tree = parse(src, false);
+        let adapter = TypeScriptAdapter::new();
+        let calls = adapter.extract_calls(&tree, src.as_bytes());
+        assert!(calls.iter().any(|c| c.caller == "outer" && c.callee == "helper"));
+        assert!(calls.iter().any(|c| c.caller == "outer" && c.callee == "method"));
     }
 }
@@ -0,0 +1,317 @@
+//! 最小化的 Language Server Protocol 类型与纯逻辑
+//!
+//! 只实现 `commands::lsp` 需要的三个请求（documentSymbol / workspace symbol /
+//! definition）所涉及的协议类型与查询逻辑，不在这里做任何 stdio/JSON-RPC 传输——
+//! 传输层在 `commands::lsp` 中手写实现。这样纯逻辑可以脱离进程 I/O 单独测试。
+use crate::graph::{CodeGraph, FileEntry};
+use crate::path_utils::{posix_dirname, posix_normalize, strip_extension};
+use serde::{Deserialize, Serialize};
+
+// ── LSP 基础类型（字段名已是 LSP 规范要求的 camelCase）───────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Position {
+    pub line: u32,
+    pub character: u32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Location {
+    pub uri: String,
+    pub range: Range,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentSymbol {
+    pub name: String,
+    pub kind: u32,
+    pub range: Range,
+    #[serde(rename = "selectionRange")]
+    pub selection_range: Range,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<DocumentSymbol>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolInformation {
+    pub name: String,
+    pub kind: u32,
+    pub location: Location,
+    #[serde(rename = "containerName", skip_serializing_if = "Option::is_none")]
+    pub container_name: Option<String>,
+}
+
+// SymbolKind 取值，见 LSP 规范 3.17 `SymbolKind`
+pub const SYMBOL_KIND_CLASS: u32 = 5;
+pub const SYMBOL_KIND_INTERFACE: u32 = 11;
+pub const SYMBOL_KIND_FUNCTION: u32 = 12;
+pub const SYMBOL_KIND_ENUM: u32 = 10;
+pub const SYMBOL_KIND_STRUCT: u32 = 23;
+
+fn symbol_kind_for_type(kind: &str) -> u32 {
+    match kind {
+        "interface" | "trait" => SYMBOL_KIND_INTERFACE,
+        "struct" => SYMBOL_KIND_STRUCT,
+        "enum" => SYMBOL_KIND_ENUM,
+        _ => SYMBOL_KIND_CLASS,
+    }
+}
+
+/// 将 1-based 的 [start_line, end_line] 行号区间转换为 0-based 的 LSP `Range`
+fn line_range(start_line: u32, end_line: u32) -> Range {
+    Range {
+        start: Position { line: start_line.saturating_sub(1), character: 0 },
+        end: Position { line: end_line.saturating_sub(1), character: 0 },
+    }
+}
+
+/// `textDocument/documentSymbol`：把一个文件的 functions/types 翻译成 DocumentSymbol 列表
+pub fn document_symbols(file: &FileEntry) -> Vec<DocumentSymbol> {
+    let mut symbols = Vec::new();
+
+    for f in &file.functions {
+        let range = line_range(f.start_line, f.end_line);
+        symbols.push(DocumentSymbol {
+            name: f.name.clone(),
+            kind: SYMBOL_KIND_FUNCTION,
+            range,
+            selection_range: range,
+            children: Vec::new(),
+        });
+    }
+
+    for t in &file.types {
+        let range = line_range(t.start_line, t.end_line);
+        symbols.push(DocumentSymbol {
+            name: t.name.clone(),
+            kind: symbol_kind_for_type(&t.kind),
+            range,
+            selection_range: range,
+            children: Vec::new(),
+        });
+    }
+
+    symbols
+}
+
+/// `workspace/symbol`：在整个图谱中按名称做大小写不敏感的子串匹配
+///
+/// 结果按文件路径、再按符号名排序，保证多次调用输出一致。
+pub fn workspace_symbols(graph: &CodeGraph, query: &str) -> Vec<SymbolInformation> {
+    let needle = query.to_lowercase();
+    let mut results = Vec::new();
+
+    let mut rel_paths: Vec<&String> = graph.files.keys().collect();
+    rel_paths.sort();
+
+    for rel_path in rel_paths {
+        let file = &graph.files[rel_path];
+        let uri = file_uri(&graph.project.root, rel_path);
+
+        for f in &file.functions {
+            if needle.is_empty() || f.name.to_lowercase().contains(&needle) {
+                results.push(SymbolInformation {
+                    name: f.name.clone(),
+                    kind: SYMBOL_KIND_FUNCTION,
+                    location: Location { uri: uri.clone(), range: line_range(f.start_line, f.end_line) },
+                    container_name: Some(file.module.clone()),
+                });
+            }
+        }
+        for t in &file.types {
+            if needle.is_empty() || t.name.to_lowercase().contains(&needle) {
+                results.push(SymbolInformation {
+                    name: t.name.clone(),
+                    kind: symbol_kind_for_type(&t.kind),
+                    location: Location { uri: uri.clone(), range: line_range(t.start_line, t.end_line) },
+                    container_name: Some(file.module.clone()),
+                });
+            }
+        }
+    }
+
+    results
+}
+
+/// 构造一个 `file://` URI
+pub fn file_uri(root: &str, rel_path: &str) -> String {
+    format!("file://{}/{}", root.trim_end_matches('/'), rel_path)
+}
+
+/// 把相对导入（以 `.` 开头）解析为 `graph.files` 中的键
+///
+/// 逻辑与 `scanner::resolve_import_module` 相同（相对于 importer 所在目录拼接、
+/// 规范化、必要时尝试无扩展名或 `/index` 形式匹配），独立实现一份是因为这里只有
+/// `graph.files` 的键集合可用，没有扫描阶段才会构建的 path→module 查找表。
+fn resolve_relative_import(importer_rel_path: &str, import_source: &str, graph: &CodeGraph) -> Option<String> {
+    let dir = posix_dirname(importer_rel_path);
+    let joined = format!("{}/{}", dir, import_source);
+    let resolved = posix_normalize(&joined);
+
+    if graph.files.contains_key(&resolved) {
+        return Some(resolved);
+    }
+
+    let without_ext = strip_extension(&resolved);
+    if let Some(path) = graph.files.keys().find(|p| strip_extension(p) == without_ext) {
+        return Some(path.clone());
+    }
+
+    let index_path = format!("{}/index", resolved);
+    if let Some(path) = graph.files.keys().find(|p| strip_extension(p) == index_path) {
+        return Some(path.clone());
+    }
+
+    None
+}
+
+/// `textDocument/definition`：在 `file_rel_path` 的 imports 中查找 `identifier`，
+/// 解析出目标模块文件，并确认该标识符确实出现在目标文件的 exports 中
+pub fn find_definition(graph: &CodeGraph, file_rel_path: &str, identifier: &str) -> Option<Location> {
+    let file = graph.files.get(file_rel_path)?;
+    let import = file
+        .imports
+        .iter()
+        .find(|imp| !imp.is_external && imp.symbols.iter().any(|s| s == identifier))?;
+
+    let target_rel_path = resolve_relative_import(file_rel_path, &import.source, graph)?;
+    let target_file = graph.files.get(&target_rel_path)?;
+    if !target_file.exports.iter().any(|e| e == identifier) {
+        return None;
+    }
+
+    Some(Location {
+        uri: file_uri(&graph.project.root, &target_rel_path),
+        range: Range {
+            start: Position { line: 0, character: 0 },
+            end: Position { line: 0, character: 0 },
+        },
+    })
+}
+
+/// 提取光标所在位置的标识符（由连续的字母/数字/下划线组成），供 `textDocument/definition` 使用
+pub fn identifier_at(line_text: &str, character: u32) -> Option<String> {
+    let chars: Vec<char> = line_text.chars().collect();
+    let idx = (character as usize).min(chars.len().saturating_sub(1));
+    if chars.is_empty() {
+        return None;
+    }
+    let is_ident = |c: &char| c.is_alphanumeric() || *c == '_';
+    if !is_ident(chars.get(idx)?) {
+        return None;
+    }
+    let mut start = idx;
+    while start > 0 && is_ident(&chars[start - 1]) {
+        start -= 1;
+    }
+    let mut end = idx;
+    while end + 1 < chars.len() && is_ident(&chars[end + 1]) {
+        end += 1;
+    }
+    Some(chars[start..=end].iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{create_empty_graph, FunctionInfo, ImportInfo, TypeInfo};
+
+    fn sample_graph() -> CodeGraph {
+        let mut graph = create_empty_graph("demo", "/proj");
+        graph.files.insert(
+            "src/auth/login.ts".to_string(),
+            FileEntry {
+                language: "typescript".to_string(),
+                module: "auth".to_string(),
+                hash: "sha256:aaa".to_string(),
+                lines: 10,
+                code_lines: 8,
+                comment_lines: 0,
+                blank_lines: 2,
+                functions: vec![FunctionInfo { name: "login".to_string(), signature: "login()".to_string(), start_line: 3, end_line: 6, complexity: 1 }],
+                classes: vec![],
+                types: vec![TypeInfo { name: "Session".to_string(), kind: "interface".to_string(), start_line: 1, end_line: 2, members: vec![] }],
+                imports: vec![ImportInfo { source: "../utils/helper".to_string(), symbols: vec!["formatDate".to_string()], is_external: false, dynamic: false }],
+                exports: vec!["login".to_string()],
+                reexports: vec![],
+                resolved_reexports: vec![],
+                calls: vec![],
+                is_entry_point: false,
+                entry_point_reason: None,
+                resolved_imports: vec![],
+                imported_by: vec![],
+                parse_diagnostics: vec![],
+            },
+        );
+        graph.files.insert(
+            "src/utils/helper.ts".to_string(),
+            FileEntry {
+                language: "typescript".to_string(),
+                module: "utils".to_string(),
+                hash: "sha256:bbb".to_string(),
+                lines: 4,
+                code_lines: 3,
+                comment_lines: 0,
+                blank_lines: 1,
+                functions: vec![FunctionInfo { name: "formatDate".to_string(), signature: "formatDate()".to_string(), start_line: 1, end_line: 3, complexity: 1 }],
+                classes: vec![],
+                types: vec![],
+                imports: vec![],
+                exports: vec!["formatDate".to_string()],
+                reexports: vec![],
+                resolved_reexports: vec![],
+                calls: vec![],
+                is_entry_point: false,
+                entry_point_reason: None,
+                resolved_imports: vec![],
+                imported_by: vec![],
+                parse_diagnostics: vec![],
+            },
+        );
+        graph
+    }
+
+    #[test]
+    fn test_document_symbols_includes_functions_and_types() {
+        let graph = sample_graph();
+        let file = &graph.files["src/auth/login.ts"];
+        let symbols = document_symbols(file);
+        assert_eq!(symbols.len(), 2);
+        assert!(symbols.iter().any(|s| s.name == "login" && s.kind == SYMBOL_KIND_FUNCTION));
+        assert!(symbols.iter().any(|s| s.name == "Session" && s.kind == SYMBOL_KIND_INTERFACE));
+    }
+
+    #[test]
+    fn test_workspace_symbols_case_insensitive_substring() {
+        let graph = sample_graph();
+        let results = workspace_symbols(&graph, "format");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "formatDate");
+    }
+
+    #[test]
+    fn test_find_definition_resolves_relative_import() {
+        let graph = sample_graph();
+        let loc = find_definition(&graph, "src/auth/login.ts", "formatDate").expect("should resolve");
+        assert_eq!(loc.uri, "file:///proj/src/utils/helper.ts");
+    }
+
+    #[test]
+    fn test_find_definition_unknown_identifier_returns_none() {
+        let graph = sample_graph();
+        assert!(find_definition(&graph, "src/auth/login.ts", "nope").is_none());
+    }
+
+    #[test]
+    fn test_identifier_at_extracts_word_under_cursor() {
+        let line = "  const x = formatDate(now);";
+        let idx = line.find("formatDate").unwrap() as u32 + 2;
+        assert_eq!(identifier_at(line, idx), Some("formatDate".to_string()));
+    }
+}
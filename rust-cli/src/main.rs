@@ -1,17 +1,28 @@
 use clap::{Parser, Subcommand};
 
 mod commands;
+mod coverage;
 mod differ;
+pub mod duplication;
 mod grammar_tests;
 mod graph;
 pub mod impact;
 pub mod languages;
+pub mod lsp;
+mod metrics;
+mod module_mapping;
+mod parse_cache;
 mod parser;
 mod path_utils;
+mod progress;
+mod project_discovery;
 pub mod query;
+pub mod resolver;
 mod scanner;
 mod slicer;
+mod source_cache;
 mod traverser;
+mod verify;
 
 #[derive(Parser)]
 #[command(name = "codegraph", about = "AST-based code graph generator", version = "0.2.0")]
@@ -34,6 +45,14 @@ enum Commands {
     Status(commands::status::StatusArgs),
     /// Output module slice or overview as JSON
     Slice(commands::slice::SliceArgs),
+    /// Start a Language Server Protocol server over stdio
+    Lsp(commands::lsp::LspArgs),
+    /// Export the code graph as GraphViz DOT or node-link JSON
+    Export(commands::export::ExportArgs),
+    /// Verify language adapter output against a golden fixture expectations manifest
+    Verify(commands::verify::VerifyArgs),
+    /// Report per-kind extraction coverage and unparsed declarations for a source file
+    Coverage(commands::coverage::CoverageArgs),
 }
 
 fn main() {
@@ -46,5 +65,9 @@ fn main() {
         Commands::Impact(args) => commands::impact::run(args),
         Commands::Status(args) => commands::status::run(args),
         Commands::Slice(args) => commands::slice::run(args),
+        Commands::Lsp(args) => commands::lsp::run(args),
+        Commands::Export(args) => commands::export::run(args),
+        Commands::Verify(args) => commands::verify::run(args),
+        Commands::Coverage(args) => commands::coverage::run(args),
     }
 }
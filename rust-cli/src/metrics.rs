@@ -0,0 +1,169 @@
+//! 跨 commit 的历史指标时间线
+//!
+//! `Overview` 已经带着 `commitHash`/`scannedAt`/`summary` 这些跑一次扫描就有的
+//! 聚合统计，差的只是把它们存下来而不是每次都覆盖。这里把每次运行的聚合数字
+//! （总文件/函数/类/行数、模块数、逐模块 `ModuleStats`）追加成 `metrics.json`
+//! 里的一条记录，让用户能在两个 commit 之间 diff 模块体积变化，或者把这个序列
+//! 喂给图表。
+use crate::graph::{language_breakdown, CodeGraph, LanguageLineStats};
+use crate::slicer::{generate_overview, ModuleStats};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// 一次扫描在某个 commit 上的聚合快照
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MetricsRecord {
+    #[serde(rename = "commitHash")]
+    pub commit_hash: Option<String>,
+    #[serde(rename = "scannedAt")]
+    pub scanned_at: String,
+    #[serde(rename = "totalFiles")]
+    pub total_files: u32,
+    #[serde(rename = "totalFunctions")]
+    pub total_functions: u32,
+    #[serde(rename = "totalClasses")]
+    pub total_classes: u32,
+    #[serde(rename = "totalLines")]
+    pub total_lines: u32,
+    #[serde(rename = "moduleCount")]
+    pub module_count: u32,
+    /// 模块名 -> 该模块当次快照的统计，用于逐模块体积对比
+    pub modules: std::collections::BTreeMap<String, ModuleStats>,
+    /// 语言名 -> 该语言当次快照的逐语言行数统计（tokei 风格），由
+    /// [`crate::graph::language_breakdown`] 算出
+    pub languages: std::collections::BTreeMap<String, LanguageLineStats>,
+}
+
+fn record_from_graph(graph: &CodeGraph) -> MetricsRecord {
+    let overview = generate_overview(graph);
+    let total_lines = overview
+        .summary
+        .total_code_lines
+        .saturating_add(overview.summary.total_comment_lines)
+        .saturating_add(overview.summary.total_blank_lines);
+
+    MetricsRecord {
+        commit_hash: overview.commit_hash,
+        scanned_at: overview.scanned_at,
+        total_files: overview.summary.total_files,
+        total_functions: overview.summary.total_functions,
+        total_classes: overview.summary.total_classes,
+        total_lines,
+        module_count: overview.modules.len() as u32,
+        modules: overview
+            .modules
+            .into_iter()
+            .map(|m| (m.name, m.stats))
+            .collect(),
+        languages: language_breakdown(graph),
+    }
+}
+
+/// 把这次扫描的聚合统计追加进 `metrics_path` 指向的时间线文件
+///
+/// 按 `commitHash` 去重：已有同一 commit 的记录就地替换，而不是重复追加；没有
+/// 旧文件就从空数组开始。写回前按 `scannedAt` 排序，这样消费方不用自己再排一遍。
+pub fn append_metrics(metrics_path: &Path, graph: &CodeGraph) -> anyhow::Result<()> {
+    let mut records: Vec<MetricsRecord> = std::fs::read_to_string(metrics_path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+
+    let new_record = record_from_graph(graph);
+    records.retain(|r| r.commit_hash != new_record.commit_hash || new_record.commit_hash.is_none());
+    records.push(new_record);
+    records.sort_by(|a, b| a.scanned_at.cmp(&b.scanned_at));
+
+    if let Some(parent) = metrics_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(metrics_path, serde_json::to_string_pretty(&records)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::create_empty_graph;
+
+    fn temp_file(name: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("codemap-metrics-test-{}.json", name));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn graph_with_commit(commit: Option<&str>) -> CodeGraph {
+        let mut graph = create_empty_graph("testproject", "/tmp/testproject");
+        graph.commit_hash = commit.map(|s| s.to_string());
+        graph.scanned_at = format!("2026-01-0{}T00:00:00Z", commit.map(|_| 1).unwrap_or(1));
+        graph
+    }
+
+    #[test]
+    fn test_append_metrics_starts_empty_file() {
+        let path = temp_file("fresh");
+        let graph = graph_with_commit(Some("abc123"));
+        append_metrics(&path, &graph).unwrap();
+
+        let records: Vec<MetricsRecord> =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].commit_hash, Some("abc123".to_string()));
+    }
+
+    #[test]
+    fn test_append_metrics_replaces_record_for_same_commit() {
+        let path = temp_file("replace");
+        let mut graph = graph_with_commit(Some("abc123"));
+        append_metrics(&path, &graph).unwrap();
+
+        graph.summary.total_files = 42;
+        append_metrics(&path, &graph).unwrap();
+
+        let records: Vec<MetricsRecord> =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].total_files, 42);
+    }
+
+    #[test]
+    fn test_append_metrics_includes_per_language_breakdown() {
+        use crate::graph::FileEntry;
+
+        let path = temp_file("languages");
+        let mut graph = graph_with_commit(Some("abc123"));
+        graph.files.insert("a.rs".to_string(), FileEntry {
+            language: "rust".into(), module: "a".into(), hash: "sha256:a".into(),
+            lines: 3, code_lines: 2, comment_lines: 0, blank_lines: 1,
+            functions: vec![], classes: vec![], types: vec![], imports: vec![], exports: vec![], reexports: vec![], resolved_reexports: vec![], calls: vec![], is_entry_point: false,
+            entry_point_reason: None,
+            resolved_imports: vec![],
+            imported_by: vec![],
+            parse_diagnostics: vec![],
+        });
+        append_metrics(&path, &graph).unwrap();
+
+        let records: Vec<MetricsRecord> =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(records[0].languages["rust"].file_count, 1);
+        assert_eq!(records[0].languages["rust"].code_lines, 2);
+    }
+
+    #[test]
+    fn test_append_metrics_appends_distinct_commits_sorted_by_scanned_at() {
+        let path = temp_file("distinct");
+        let mut first = graph_with_commit(Some("commit-a"));
+        first.scanned_at = "2026-01-02T00:00:00Z".to_string();
+        append_metrics(&path, &first).unwrap();
+
+        let mut second = graph_with_commit(Some("commit-b"));
+        second.scanned_at = "2026-01-01T00:00:00Z".to_string();
+        append_metrics(&path, &second).unwrap();
+
+        let records: Vec<MetricsRecord> =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].commit_hash, Some("commit-b".to_string()));
+        assert_eq!(records[1].commit_hash, Some("commit-a".to_string()));
+    }
+}
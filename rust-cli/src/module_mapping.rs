@@ -0,0 +1,207 @@
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// 约定的模块映射入口文件名，放在项目根目录下
+const ENTRY_FILE: &str = "codemap.modules";
+
+/// 一条映射规则：要么把命中的路径赋给某个模块，要么清除之前层叠加的赋值
+enum MappingOp {
+    Assign { matcher: Gitignore, module: String },
+    Unset { matcher: Gitignore },
+}
+
+/// 由 `codemap.modules`（及其 `%include` 链）解析出的分层模块映射
+///
+/// 每一行要么是 `glob = module`（把匹配的文件赋给该模块）、`%unset glob`
+/// （清除之前层给匹配文件赋的模块，回退到目录推断的默认值）、要么是
+/// `%include relative/path`（按写这行 `%include` 的文件所在目录解析相对路径，递归展开）。
+/// 规则按声明顺序层叠应用：后出现的规则覆盖先出现的规则。
+#[derive(Default)]
+pub struct ModuleMapping {
+    ops: Vec<MappingOp>,
+}
+
+impl ModuleMapping {
+    /// 从项目根目录加载 `codemap.modules`；文件不存在时视为没有覆盖配置
+    pub fn load(root_dir: &Path) -> Self {
+        let entry = root_dir.join(ENTRY_FILE);
+        if !entry.is_file() {
+            return Self::default();
+        }
+
+        let mut mapping = Self::default();
+        let mut visited = HashSet::new();
+        load_file(&entry, root_dir, &mut mapping, &mut visited);
+        mapping
+    }
+
+    /// 按仓库根相对路径（posix 风格）解析覆盖后的模块名；没有规则命中时返回
+    /// `None`，调用方应回退到目录推断的默认模块名
+    pub fn resolve(&self, rel_path: &str) -> Option<String> {
+        let path = Path::new(rel_path);
+        let mut current: Option<String> = None;
+        for op in &self.ops {
+            match op {
+                MappingOp::Assign { matcher, module } => {
+                    if matcher.matched(path, false).is_ignore() {
+                        current = Some(module.clone());
+                    }
+                }
+                MappingOp::Unset { matcher } => {
+                    if matcher.matched(path, false).is_ignore() {
+                        current = None;
+                    }
+                }
+            }
+        }
+        current
+    }
+}
+
+fn load_file(path: &Path, root_dir: &Path, mapping: &mut ModuleMapping, visited: &mut HashSet<PathBuf>) {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if !visited.insert(canonical) {
+        return; // 已经加载过，避免 %include 形成环
+    }
+
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Warning: could not read module-mapping file '{}': {}", path.display(), e);
+            return;
+        }
+    };
+
+    let base_dir = path.parent().unwrap_or(root_dir);
+
+    for raw_line in content.lines() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("%include") {
+            let target = rest.trim();
+            if !target.is_empty() {
+                load_file(&base_dir.join(target), root_dir, mapping, visited);
+            }
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("%unset") {
+            let pattern = rest.trim();
+            if !pattern.is_empty() {
+                if let Some(matcher) = build_single_matcher(root_dir, pattern) {
+                    mapping.ops.push(MappingOp::Unset { matcher });
+                }
+            }
+            continue;
+        }
+
+        match line.split_once('=') {
+            Some((pattern, module)) => {
+                let pattern = pattern.trim();
+                let module = module.trim();
+                if pattern.is_empty() || module.is_empty() {
+                    eprintln!("Warning: ignoring malformed module-mapping line: {}", raw_line);
+                    continue;
+                }
+                if let Some(matcher) = build_single_matcher(root_dir, pattern) {
+                    mapping.ops.push(MappingOp::Assign { matcher, module: module.to_string() });
+                }
+            }
+            None => eprintln!("Warning: ignoring unrecognized module-mapping line: {}", raw_line),
+        }
+    }
+}
+
+fn build_single_matcher(root_dir: &Path, pattern: &str) -> Option<Gitignore> {
+    let mut builder = GitignoreBuilder::new(root_dir);
+    if let Err(e) = builder.add_line(None, pattern) {
+        eprintln!("Warning: ignoring invalid module-mapping pattern '{}': {}", pattern, e);
+        return None;
+    }
+    builder.build().ok()
+}
+
+// ── 测试 ──────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write(dir: &Path, name: &str, content: &str) {
+        fs::write(dir.join(name), content).unwrap();
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("codemap-module-mapping-test-{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_no_entry_file_means_empty_mapping() {
+        let dir = temp_dir("no-entry");
+        let mapping = ModuleMapping::load(&dir);
+        assert_eq!(mapping.resolve("src/app.ts"), None);
+    }
+
+    #[test]
+    fn test_simple_glob_assignment() {
+        let dir = temp_dir("simple");
+        write(&dir, "codemap.modules", "src/legacy/** = core\n");
+        let mapping = ModuleMapping::load(&dir);
+        assert_eq!(mapping.resolve("src/legacy/old.ts"), Some("core".to_string()));
+        assert_eq!(mapping.resolve("src/fresh/new.ts"), None);
+    }
+
+    #[test]
+    fn test_later_assignment_overrides_earlier() {
+        let dir = temp_dir("override");
+        write(
+            &dir,
+            "codemap.modules",
+            "src/legacy/** = core\nsrc/legacy/payments/** = billing\n",
+        );
+        let mapping = ModuleMapping::load(&dir);
+        assert_eq!(mapping.resolve("src/legacy/payments/charge.ts"), Some("billing".to_string()));
+        assert_eq!(mapping.resolve("src/legacy/auth/login.ts"), Some("core".to_string()));
+    }
+
+    #[test]
+    fn test_unset_peels_back_to_default() {
+        let dir = temp_dir("unset");
+        write(
+            &dir,
+            "codemap.modules",
+            "src/legacy/** = core\n%unset src/legacy/keep-default/**\n",
+        );
+        let mapping = ModuleMapping::load(&dir);
+        assert_eq!(mapping.resolve("src/legacy/old.ts"), Some("core".to_string()));
+        assert_eq!(mapping.resolve("src/legacy/keep-default/thing.ts"), None);
+    }
+
+    #[test]
+    fn test_include_resolved_relative_to_including_file() {
+        let dir = temp_dir("include");
+        fs::create_dir_all(dir.join("team-a")).unwrap();
+        write(&dir, "codemap.modules", "%include ./team-a/extra.mapping\n");
+        write(&dir.join("team-a"), "extra.mapping", "widgets/** = widgets\n");
+
+        let mapping = ModuleMapping::load(&dir);
+        assert_eq!(mapping.resolve("widgets/button.ts"), Some("widgets".to_string()));
+    }
+
+    #[test]
+    fn test_include_cycle_does_not_loop_forever() {
+        let dir = temp_dir("cycle");
+        write(&dir, "codemap.modules", "%include ./codemap.modules\nsrc/** = core\n");
+
+        let mapping = ModuleMapping::load(&dir);
+        assert_eq!(mapping.resolve("src/app.ts"), Some("core".to_string()));
+    }
+}
@@ -0,0 +1,287 @@
+//! 增量重解析缓存
+//!
+//! 为编辑器/watch 模式场景保留每个文件最近一次解析得到的 `Tree` 与源码字节。
+//! 编辑到来时调用 `tree.edit` 标记变更区间，再用 `parser.parse(new_source, Some(&old_tree))`
+//! 让 tree-sitter 复用未变更的子树，而不必像一次性扫描那样每次都从零解析整份文件。
+use crate::languages::{self, ClassInfo, FunctionInfo};
+use crate::traverser::Language;
+use std::collections::HashMap;
+use tree_sitter::{InputEdit, Parser, Point, Tree};
+
+/// 一次编辑涉及的字节区间：`[start_byte, old_end_byte)` 是旧源码中被替换的部分，
+/// `new_end_byte` 是替换后在新源码中对应区间的结束偏移
+pub struct Edit {
+    pub start_byte: usize,
+    pub old_end_byte: usize,
+    pub new_end_byte: usize,
+}
+
+struct CacheEntry {
+    tree: Tree,
+    source: Vec<u8>,
+    language: Language,
+}
+
+/// 按文件路径缓存解析结果，支持基于字节编辑的增量重解析
+pub struct ParseCache {
+    entries: HashMap<String, CacheEntry>,
+}
+
+impl ParseCache {
+    pub fn new() -> Self {
+        Self { entries: HashMap::new() }
+    }
+
+    /// 解析一个文件并建立缓存基线（首次打开文件，或放弃增量改用全量重解析时调用）
+    pub fn insert(&mut self, path: &str, language: Language, source: &[u8]) -> anyhow::Result<()> {
+        let tree = parse_with(language, source, None)?;
+        self.entries.insert(
+            path.to_string(),
+            CacheEntry { tree, source: source.to_vec(), language },
+        );
+        Ok(())
+    }
+
+    /// 对已缓存文件应用一次编辑并增量重解析
+    ///
+    /// `edit` 描述旧源码中被替换的字节区间，`new_source` 是编辑后的完整新源码。
+    /// 起止 Point 分别按旧源码（start/old_end）与新源码（new_end）计算，保证在调用
+    /// `tree.edit` 前位置与对应源码保持一致——这是 tree-sitter 的不变量，违反会 panic。
+    pub fn apply_edit(&mut self, path: &str, edit: Edit, new_source: &[u8]) -> anyhow::Result<()> {
+        let entry = self
+            .entries
+            .get_mut(path)
+            .ok_or_else(|| anyhow::anyhow!("no cached parse for {}", path))?;
+
+        entry.tree.edit(&InputEdit {
+            start_byte: edit.start_byte,
+            old_end_byte: edit.old_end_byte,
+            new_end_byte: edit.new_end_byte,
+            start_position: point_at(&entry.source, edit.start_byte),
+            old_end_position: point_at(&entry.source, edit.old_end_byte),
+            new_end_position: point_at(new_source, edit.new_end_byte),
+        });
+
+        let new_tree = parse_with(entry.language, new_source, Some(&entry.tree))?;
+        entry.tree = new_tree;
+        entry.source = new_source.to_vec();
+        Ok(())
+    }
+
+    /// 返回与 `[start_byte, end_byte)` 编辑区间重叠的函数/类，供调用方只重新处理受影响
+    /// 的符号，而不必对整份文件重跑一遍所有 adapter 方法
+    pub fn affected_symbols(
+        &self,
+        path: &str,
+        start_byte: usize,
+        end_byte: usize,
+    ) -> Option<(Vec<FunctionInfo>, Vec<ClassInfo>)> {
+        let entry = self.entries.get(path)?;
+        let adapter = languages::get_adapter(entry.language);
+        let start_row = point_at(&entry.source, start_byte).row;
+        let end_row = point_at(&entry.source, end_byte).row;
+
+        let functions = adapter
+            .extract_functions(&entry.tree, &entry.source)
+            .into_iter()
+            .filter(|f| line_span_overlaps_rows(f.start_line, f.end_line, start_row, end_row))
+            .collect();
+        let classes = adapter
+            .extract_classes(&entry.tree, &entry.source)
+            .into_iter()
+            .filter(|c| line_span_overlaps_rows(c.start_line, c.end_line, start_row, end_row))
+            .collect();
+
+        Some((functions, classes))
+    }
+}
+
+impl Default for ParseCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_with(language: Language, source: &[u8], old_tree: Option<&Tree>) -> anyhow::Result<Tree> {
+    let adapter = languages::get_adapter(language);
+    let mut parser = Parser::new();
+    parser
+        .set_language(&adapter.language())
+        .map_err(|e| anyhow::anyhow!("set_language failed for {:?}: {}", language, e))?;
+    parser
+        .parse(source, old_tree)
+        .ok_or_else(|| anyhow::anyhow!("parse returned None for {:?}", language))
+}
+
+/// 根据字节偏移计算 0-based 行列 `Point`（列按字节数而非字符数计，与 tree-sitter 一致）
+fn point_at(source: &[u8], byte_offset: usize) -> Point {
+    let offset = byte_offset.min(source.len());
+    let mut row = 0usize;
+    let mut line_start = 0usize;
+    for (i, &b) in source[..offset].iter().enumerate() {
+        if b == b'\n' {
+            row += 1;
+            line_start = i + 1;
+        }
+    }
+    Point { row, column: offset - line_start }
+}
+
+/// 判断 1-based 行号区间 `[line_start, line_end]` 是否与 0-based 行号区间
+/// `[start_row, end_row]` 重叠
+///
+/// `pub(crate)`：`differ::update_graph_incremental` 合并增量重解析结果时复用同一套
+/// 重叠判定，不再重新实现一遍
+pub(crate) fn line_span_overlaps_rows(line_start: usize, line_end: usize, start_row: usize, end_row: usize) -> bool {
+    let span_start_row = line_start.saturating_sub(1);
+    let span_end_row = line_end.saturating_sub(1);
+    span_start_row <= end_row && start_row <= span_end_row
+}
+
+/// 计算把 `old` 变成 `new` 的编辑区间：从两端分别找最长公共前缀/后缀，中间没被
+/// 前缀/后缀覆盖的部分就是这次编辑改动的字节区间。这是最简单的一种 diff（不求
+/// 真正最小的编辑脚本/LCS），但 `Tree::edit` 只需要知道“哪个区间被替换成了什么”，
+/// 不需要语义上最优的 diff，够用。`old == new` 时返回 `None`（没有编辑）。
+pub fn diff_edit(old: &[u8], new: &[u8]) -> Option<InputEdit> {
+    if old == new {
+        return None;
+    }
+    let common_prefix = old.iter().zip(new.iter()).take_while(|(a, b)| a == b).count();
+    let max_suffix = (old.len() - common_prefix).min(new.len() - common_prefix);
+    let common_suffix = (0..max_suffix)
+        .take_while(|&i| old[old.len() - 1 - i] == new[new.len() - 1 - i])
+        .count();
+
+    let start_byte = common_prefix;
+    let old_end_byte = old.len() - common_suffix;
+    let new_end_byte = new.len() - common_suffix;
+
+    Some(InputEdit {
+        start_byte,
+        old_end_byte,
+        new_end_byte,
+        start_position: point_at(old, start_byte),
+        old_end_position: point_at(old, old_end_byte),
+        new_end_position: point_at(new, new_end_byte),
+    })
+}
+
+/// 跨进程调用版的增量重解析：不像 [`ParseCache`] 那样有常驻内存里的旧 `Tree` 可以
+/// 直接 `edit`，只有旧源码字节本身（由调用方从磁盘缓存里读出）——所以这里把旧源码
+/// 解析成一棵完整的树作为编辑基线，用 [`diff_edit`] 算出的区间 `edit` 它，再
+/// `Parser::parse(new_source, Some(&old_tree))` 让 tree-sitter 复用编辑区间之外
+/// 未变化的子树。返回新树，以及新旧树之间真正发生变化的字节/行区间
+/// （`Tree::changed_ranges`），调用方据此只合并重新抽取的、落在这些区间内的符号，
+/// 区间外的符号沿用上一次抽取的结果。`old_source == new_source` 时变化区间为空。
+pub fn reparse_incremental(
+    language: Language,
+    old_source: &[u8],
+    new_source: &[u8],
+) -> anyhow::Result<(Tree, Vec<tree_sitter::Range>)> {
+    let mut old_tree = parse_with(language, old_source, None)?;
+    let Some(edit) = diff_edit(old_source, new_source) else {
+        return Ok((old_tree, Vec::new()));
+    };
+    old_tree.edit(&edit);
+    let new_tree = parse_with(language, new_source, Some(&old_tree))?;
+    let changed = old_tree.changed_ranges(&new_tree).collect();
+    Ok((new_tree, changed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_full_reparse() {
+        let mut cache = ParseCache::new();
+        let src = b"fn foo() {}\nfn bar() {}\n";
+        cache.insert("a.rs", Language::Rust, src).unwrap();
+        let (functions, _) = cache.affected_symbols("a.rs", 0, src.len()).unwrap();
+        assert_eq!(functions.len(), 2);
+    }
+
+    #[test]
+    fn test_apply_edit_reuses_tree_and_reextracts() {
+        let mut cache = ParseCache::new();
+        let src_text = "fn foo() {}\nfn bar() {}\n";
+        cache.insert("a.rs", Language::Rust, src_text.as_bytes()).unwrap();
+
+        // 把第二行的 "bar" 重命名为 "baz1"
+        let start_byte = src_text.find("bar").unwrap();
+        let old_end_byte = start_byte + "bar".len();
+        let new_end_byte = start_byte + "baz1".len();
+        let new_src = format!("{}baz1{}", &src_text[..start_byte], &src_text[old_end_byte..]);
+
+        cache
+            .apply_edit(
+                "a.rs",
+                Edit { start_byte, old_end_byte, new_end_byte },
+                new_src.as_bytes(),
+            )
+            .unwrap();
+
+        let (functions, _) = cache.affected_symbols("a.rs", start_byte, new_end_byte).unwrap();
+        assert!(functions.iter().any(|f| f.name == "baz1"));
+        assert!(!functions.iter().any(|f| f.name == "foo"));
+    }
+
+    #[test]
+    fn test_affected_symbols_filters_to_overlapping_range() {
+        let mut cache = ParseCache::new();
+        let src = b"fn foo() {}\nfn bar() {}\n".to_vec();
+        cache.insert("a.rs", Language::Rust, &src).unwrap();
+
+        // 只圈定第一行 (foo) 对应的字节区间
+        let first_line_end = src.iter().position(|&b| b == b'\n').unwrap();
+        let (functions, _) = cache.affected_symbols("a.rs", 0, first_line_end).unwrap();
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].name, "foo");
+    }
+
+    #[test]
+    fn test_point_at_computes_row_and_column() {
+        let src = b"abc\ndef\nghi";
+        assert_eq!(point_at(src, 0), Point { row: 0, column: 0 });
+        assert_eq!(point_at(src, 5), Point { row: 1, column: 1 });
+        assert_eq!(point_at(src, src.len()), Point { row: 2, column: 3 });
+    }
+
+    #[test]
+    fn test_diff_edit_identical_sources_is_none() {
+        let src = b"fn foo() {}";
+        assert!(diff_edit(src, src).is_none());
+    }
+
+    #[test]
+    fn test_diff_edit_finds_minimal_replaced_region() {
+        let old = b"fn foo() {}\nfn bar() {}\n";
+        let new = b"fn foo() {}\nfn baz1() {}\n";
+        let edit = diff_edit(old, new).unwrap();
+        assert_eq!(&old[edit.start_byte..edit.old_end_byte], b"r");
+        assert_eq!(&new[edit.start_byte..edit.new_end_byte], b"z1");
+    }
+
+    #[test]
+    fn test_reparse_incremental_reports_changed_range_and_reextracts() {
+        let old_src = "fn foo() {}\nfn bar() {}\n";
+        let new_src = "fn foo() {}\nfn baz1() {}\n";
+        let (new_tree, changed) =
+            reparse_incremental(Language::Rust, old_src.as_bytes(), new_src.as_bytes()).unwrap();
+
+        assert!(!changed.is_empty());
+        let adapter = languages::get_adapter(Language::Rust);
+        let functions = adapter.extract_functions(&new_tree, new_src.as_bytes());
+        assert!(functions.iter().any(|f| f.name == "baz1"));
+        assert!(functions.iter().any(|f| f.name == "foo"));
+        // 没动过的第一行不在任何变化区间里
+        assert!(changed.iter().all(|r| r.start_point.row >= 1));
+    }
+
+    #[test]
+    fn test_reparse_incremental_identical_source_has_no_changed_ranges() {
+        let src = "fn foo() {}\n";
+        let (_, changed) = reparse_incremental(Language::Rust, src.as_bytes(), src.as_bytes()).unwrap();
+        assert!(changed.is_empty());
+    }
+}
@@ -27,6 +27,9 @@ pub struct ParseResult {
     pub classes: Vec<ClassInfo>,
     pub types: Vec<TypeInfo>,
     pub lines: u32,
+    pub code_lines: u32,
+    pub comment_lines: u32,
+    pub blank_lines: u32,
 }
 
 // ── 默认适配器（已被 languages/ 下的具体适配器取代，仅测试使用）────────────
@@ -90,6 +93,7 @@ pub fn parse_file(
     let classes = adapter.extract_classes(&tree, source);
     let types = adapter.extract_types(&tree, source);
     let lines = source.iter().filter(|&&b| b == b'\n').count() as u32 + 1;
+    let (code_lines, comment_lines, blank_lines) = classify_lines(&tree, source);
 
     Ok(ParseResult {
         functions,
@@ -98,9 +102,61 @@ pub fn parse_file(
         classes,
         types,
         lines,
+        code_lines,
+        comment_lines,
+        blank_lines,
     })
 }
 
+/// 按行分类为代码/注释/空行，逻辑与 scanner.rs 的同名函数一致（此文件仅测试使用，未共享实现）
+fn classify_lines(tree: &Tree, source: &[u8]) -> (u32, u32, u32) {
+    let mut comment_ranges = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if node.kind().ends_with("comment") {
+            comment_ranges.push((node.start_byte(), node.end_byte()));
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            stack.push(child);
+        }
+    }
+
+    let text = String::from_utf8_lossy(source);
+    let mut code_lines = 0u32;
+    let mut comment_lines = 0u32;
+    let mut blank_lines = 0u32;
+    let mut byte_offset = 0usize;
+    // 文件末尾换行符之后的空串不算一行
+    for line in text.split_terminator('\n') {
+        let line_start = byte_offset;
+        byte_offset += line.len() + 1;
+
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            blank_lines += 1;
+            continue;
+        }
+
+        let leading_ws = line.len() - line.trim_start().len();
+        let trailing_ws = line.len() - line.trim_end().len();
+        let span_start = line_start + leading_ws;
+        let span_end = line_start + line.len() - trailing_ws;
+
+        let fully_in_comment = comment_ranges
+            .iter()
+            .any(|&(cs, ce)| cs <= span_start && span_end <= ce);
+
+        if fully_in_comment {
+            comment_lines += 1;
+        } else {
+            code_lines += 1;
+        }
+    }
+
+    (code_lines, comment_lines, blank_lines)
+}
+
 /// 根据语言枚举获取对应的 tree-sitter Language（仅测试使用）
 #[allow(dead_code)]
 pub fn get_ts_language(language: Language) -> tree_sitter::Language {
@@ -0,0 +1,94 @@
+//! 扫描/切片过程中的增量事件（NDJSON）
+//!
+//! `scan_project`/`save_slices` 默认仍然是“跑完再返回”的阻塞调用，但大仓库上这一步
+//! 完全不透明。这里提供一个可选的 `ProgressSink`：调用方可以传入一个会把每个
+//! `ScanEvent` 写成一行 JSON 的 sink，随扫描/切片进度实时拿到事件流；不关心进度的
+//! 调用方继续用默认的 `NoopSink`，行为不变。
+
+use serde::Serialize;
+use std::io::Write;
+
+/// 扫描/切片过程中产生的一个事件，序列化为 `{"kind": ..., ...}` 的单行 JSON
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ScanEvent {
+    /// 一个文件解析完成
+    FileScanned { path: String, functions: u32 },
+    /// 一个模块的切片已生成
+    ModuleSliced { module: String, files: u32 },
+    /// 扫描/切片全部完成
+    Summary {
+        #[serde(rename = "totalFiles")]
+        total_files: u32,
+        #[serde(rename = "totalFunctions")]
+        total_functions: u32,
+        #[serde(rename = "totalModules")]
+        total_modules: u32,
+    },
+}
+
+/// 事件接收端。默认实现（`NoopSink`）不做任何事，已有调用方不受影响
+pub trait ProgressSink {
+    fn emit(&mut self, event: ScanEvent);
+}
+
+/// 什么都不做的 sink，`scan_project`/`save_slices` 的默认行为
+#[derive(Default)]
+pub struct NoopSink;
+
+impl ProgressSink for NoopSink {
+    fn emit(&mut self, _event: ScanEvent) {}
+}
+
+/// 把每个事件序列化为一行 JSON 写入底层 `Write`（NDJSON）
+///
+/// 序列化/写入失败时静默丢弃该事件，不影响扫描本身——进度上报是锦上添花，
+/// 不应该让主流程因为一个坏掉的管道而失败。
+pub struct NdjsonSink<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> NdjsonSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+}
+
+impl<W: Write> ProgressSink for NdjsonSink<W> {
+    fn emit(&mut self, event: ScanEvent) {
+        if let Ok(line) = serde_json::to_string(&event) {
+            let _ = writeln!(self.writer, "{}", line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_noop_sink_does_nothing() {
+        let mut sink = NoopSink;
+        sink.emit(ScanEvent::FileScanned { path: "a.rs".to_string(), functions: 1 });
+    }
+
+    #[test]
+    fn test_ndjson_sink_writes_one_line_per_event() {
+        let mut buf: Vec<u8> = Vec::new();
+        {
+            let mut sink = NdjsonSink::new(&mut buf);
+            sink.emit(ScanEvent::FileScanned { path: "a.rs".to_string(), functions: 2 });
+            sink.emit(ScanEvent::ModuleSliced { module: "auth".to_string(), files: 3 });
+            sink.emit(ScanEvent::Summary { total_files: 1, total_functions: 2, total_modules: 1 });
+        }
+        let text = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], r#"{"kind":"fileScanned","path":"a.rs","functions":2}"#);
+        assert_eq!(lines[1], r#"{"kind":"moduleSliced","module":"auth","files":3}"#);
+        assert_eq!(
+            lines[2],
+            r#"{"kind":"summary","totalFiles":1,"totalFunctions":2,"totalModules":1}"#
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! 工作区感知的项目发现
+//!
+//! 扫描根目录往往不是整个项目的唯一代码根：它可能本身就是一个 workspace
+//! 的子 crate（需要往上找 workspace 根的 `Cargo.toml`），也可能是把代码放在
+//! 一个子目录里的 monorepo（需要往下找一层的 `rust/Cargo.toml`）。这里找出
+//! 所有和扫描根相关的 `Cargo.toml`/`package.json`（含 workspace 成员），
+//! 把它们换算成相对扫描根的 `src/`（Rust）或包（JS）目录，供
+//! `resolver::resolve_symbols_for_project` 做跨 crate/跨包的 import 解析。
+use std::path::{Path, PathBuf};
+
+/// 往上找 workspace 根的层数上限——再往上大概率已经出了项目边界
+const SEARCH_UP_LEVELS: usize = 4;
+
+/// 一次清单发现的结果，路径都已经换算成相对扫描根目录的 posix 字符串
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectManifests {
+    /// 每个 Rust crate 的 `src/` 目录（""表示扫描根自己就是 crate 根）
+    pub rust_src_dirs: Vec<String>,
+    /// 每个 JS/TS 包的目录（`package.json` 所在目录）
+    pub js_package_dirs: Vec<String>,
+}
+
+/// 在 `scan_root` 本身、它的祖先目录（最多 `SEARCH_UP_LEVELS` 层）、以及它的
+/// 直接子目录（一层）里找 `Cargo.toml`/`package.json`。不受 `scan_root` 管辖的
+/// 祖先 crate（比如 workspace 里的兄弟 crate）会被发现但换算不出相对路径，
+/// 静默丢弃——反正它们不在这次扫描出来的 `graph.files` 里，解析不到也没用。
+pub fn discover(scan_root: &Path) -> ProjectManifests {
+    let mut candidate_dirs = Vec::new();
+
+    let mut dir = Some(scan_root.to_path_buf());
+    for _ in 0..=SEARCH_UP_LEVELS {
+        let Some(d) = dir else { break };
+        candidate_dirs.push(d.clone());
+        dir = d.parent().map(Path::to_path_buf);
+    }
+
+    if let Ok(entries) = std::fs::read_dir(scan_root) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                candidate_dirs.push(path);
+            }
+        }
+    }
+
+    let mut rust_src_dirs = Vec::new();
+    let mut js_package_dirs = Vec::new();
+
+    for dir in &candidate_dirs {
+        if let Ok(content) = std::fs::read_to_string(dir.join("Cargo.toml")) {
+            collect_rust_crate_src_dirs(dir, &content, scan_root, &mut rust_src_dirs);
+        }
+        if dir.join("package.json").is_file() {
+            if let Some(rel) = relative_posix(scan_root, dir) {
+                js_package_dirs.push(rel);
+            }
+        }
+    }
+
+    rust_src_dirs.sort();
+    rust_src_dirs.dedup();
+    js_package_dirs.sort();
+    js_package_dirs.dedup();
+
+    ProjectManifests { rust_src_dirs, js_package_dirs }
+}
+
+/// 把一个 `Cargo.toml` 自己的 `src/` 目录（存在时）、以及它 `[workspace] members` 里每个
+/// 成员（含 `crates/*` 这样的单层 glob）的 `src/` 目录，都换算成相对路径后收集起来
+fn collect_rust_crate_src_dirs(manifest_dir: &Path, content: &str, scan_root: &Path, out: &mut Vec<String>) {
+    let own_src = manifest_dir.join("src");
+    if own_src.is_dir() {
+        if let Some(rel) = relative_posix(scan_root, &own_src) {
+            out.push(rel);
+        }
+    }
+    for member in parse_workspace_members(content) {
+        for member_dir in expand_member_pattern(manifest_dir, &member) {
+            if let Some(rel) = relative_posix(scan_root, &member_dir.join("src")) {
+                out.push(rel);
+            }
+        }
+    }
+}
+
+/// 手写扫出 `[workspace]` 小节里 `members = [...]` 数组的字符串字面量，数组可以
+/// 跨多行。和 `graph::cargo_toml_bin_paths` 对 `[[bin]]` 的扫描方式一样，不引入
+/// 完整 TOML 解析库
+fn parse_workspace_members(content: &str) -> Vec<String> {
+    let mut members = Vec::new();
+    let mut in_workspace_section = false;
+    let mut in_members_array = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') && !in_members_array {
+            in_workspace_section = trimmed == "[workspace]";
+            continue;
+        }
+        if !in_workspace_section {
+            continue;
+        }
+
+        if !in_members_array {
+            let Some(rest) = trimmed.strip_prefix("members") else { continue };
+            let Some(rest) = rest.trim_start().strip_prefix('=') else { continue };
+            in_members_array = true;
+            collect_quoted_strings(rest.trim(), &mut members);
+            if rest.contains(']') {
+                in_members_array = false;
+            }
+        } else {
+            collect_quoted_strings(trimmed, &mut members);
+            if trimmed.contains(']') {
+                in_members_array = false;
+            }
+        }
+    }
+
+    members
+}
+
+/// 从一行里逐个取出 `"..."` 字面量（不处理转义，workspace member 路径不会用到）
+fn collect_quoted_strings(line: &str, out: &mut Vec<String>) {
+    let mut rest = line;
+    while let Some(start) = rest.find('"') {
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('"') else { break };
+        out.push(after[..end].to_string());
+        rest = &after[end + 1..];
+    }
+}
+
+/// 展开一个 workspace member pattern：末段是 `*` 时列出该目录下的每个子目录
+/// （只支持这一种单层 glob，够用且和 `cargo_toml_bin_paths` 一样不求大而全），
+/// 否则就是一个字面路径
+fn expand_member_pattern(base: &Path, pattern: &str) -> Vec<PathBuf> {
+    if let Some(parent_pattern) = pattern.strip_suffix("/*") {
+        let parent_dir = base.join(parent_pattern);
+        let mut dirs = Vec::new();
+        if let Ok(entries) = std::fs::read_dir(&parent_dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    dirs.push(path);
+                }
+            }
+        }
+        dirs.sort();
+        dirs
+    } else {
+        vec![base.join(pattern)]
+    }
+}
+
+/// `path` 相对 `root` 的 posix 字符串；`path` 不在 `root` 之下时返回 `None`
+/// （典型情况：往上找到的 workspace 兄弟 crate，不属于这次扫描的范围）
+fn relative_posix(root: &Path, path: &Path) -> Option<String> {
+    let rel = path.strip_prefix(root).ok()?;
+    let s = rel.to_string_lossy().replace('\\', "/");
+    Some(if s.is_empty() { ".".to_string() } else { s })
+}
+
+// ── 测试 ──────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("codemap-project-discovery-test-{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_single_crate_at_scan_root() {
+        let dir = temp_dir("single-crate");
+        fs::write(dir.join("Cargo.toml"), "[package]\nname = \"demo\"\n").unwrap();
+        fs::create_dir_all(dir.join("src")).unwrap();
+
+        let manifests = discover(&dir);
+        assert_eq!(manifests.rust_src_dirs, vec!["src".to_string()]);
+    }
+
+    #[test]
+    fn test_subdirectory_crate_found_one_level_down() {
+        let dir = temp_dir("subdir-crate");
+        fs::create_dir_all(dir.join("rust").join("src")).unwrap();
+        fs::write(dir.join("rust").join("Cargo.toml"), "[package]\nname = \"demo\"\n").unwrap();
+
+        let manifests = discover(&dir);
+        assert_eq!(manifests.rust_src_dirs, vec!["rust/src".to_string()]);
+    }
+
+    #[test]
+    fn test_workspace_root_found_by_walking_upward() {
+        let workspace_root = temp_dir("workspace-upward");
+        let member_dir = workspace_root.join("crates").join("core");
+        fs::create_dir_all(member_dir.join("src")).unwrap();
+        fs::write(
+            workspace_root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/core\"]\n",
+        )
+        .unwrap();
+        fs::write(member_dir.join("Cargo.toml"), "[package]\nname = \"core\"\n").unwrap();
+
+        // 扫描根就是 member 目录自身——往上一级能看到 workspace 根，
+        // 但 workspace 根自己没有 src/，member 的 src/ 在扫描根范围内
+        let manifests = discover(&member_dir);
+        assert_eq!(manifests.rust_src_dirs, vec!["src".to_string()]);
+    }
+
+    #[test]
+    fn test_workspace_member_glob_expands_to_sibling_dirs() {
+        let workspace_root = temp_dir("workspace-glob");
+        fs::create_dir_all(workspace_root.join("crates").join("a").join("src")).unwrap();
+        fs::create_dir_all(workspace_root.join("crates").join("b").join("src")).unwrap();
+        fs::write(
+            workspace_root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+
+        let manifests = discover(&workspace_root);
+        assert_eq!(
+            manifests.rust_src_dirs,
+            vec!["crates/a/src".to_string(), "crates/b/src".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_js_package_json_discovered() {
+        let dir = temp_dir("js-package");
+        fs::create_dir_all(dir.join("packages").join("app")).unwrap();
+        fs::write(dir.join("packages").join("app").join("package.json"), "{}").unwrap();
+
+        let manifests = discover(&dir.join("packages").join("app"));
+        assert_eq!(manifests.js_package_dirs, vec![".".to_string()]);
+    }
+
+    #[test]
+    fn test_no_manifest_found_returns_empty() {
+        let dir = temp_dir("no-manifest");
+        let manifests = discover(&dir);
+        assert!(manifests.rust_src_dirs.is_empty());
+        assert!(manifests.js_package_dirs.is_empty());
+    }
+}
@@ -2,7 +2,8 @@
 ///
 /// 在 CodeGraph 中按名称搜索函数、类、类型，支持模糊匹配和类型过滤。
 /// 逻辑与 ccplugin/cli/src/query.js 保持一致。
-use crate::graph::{CodeGraph, FileEntry};
+use crate::graph::{CodeGraph, FileEntry, ModuleEntry};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 // ── 查询结果结构 ──────────────────────────────────────────────────────────────
 
@@ -24,6 +25,9 @@ pub struct SymbolResult {
     pub file_imports: Vec<String>,
     /// 导入了该符号的其他文件（"module:file" 格式）
     pub imported_by: Vec<String>,
+    /// [`match_symbol`] 给出的相关性分数，同一个 [`MatchMode`] 内部越大越相关；
+    /// 跨 mode 之间的绝对值没有可比性
+    pub score: i32,
 }
 
 #[derive(Debug, Clone)]
@@ -34,19 +38,62 @@ pub struct ModuleResult {
     pub depended_by: Vec<String>,
 }
 
+/// 一个可达模块及其到查询起点的跳数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReachableModule {
+    pub name: String,
+    pub distance: u32,
+}
+
+/// 模块传递可达性查询结果
+#[derive(Debug, Clone)]
+pub struct TransitiveModuleResult {
+    pub name: String,
+    /// 沿 dependsOn 边的正向闭包：该模块（传递地）依赖的一切
+    pub reaches: Vec<ReachableModule>,
+    /// 沿 dependedBy 边的反向闭包：该模块变更会（传递地）影响的一切
+    pub impacted_by_change: Vec<ReachableModule>,
+    /// 该模块是否处于循环依赖环中——环内模块之间没有单一的"谁先谁后"，
+    /// 上面两个闭包列出的跳数仅反映 BFS 首次到达的最短路径
+    pub in_cycle: bool,
+}
+
 // ── 查询选项 ──────────────────────────────────────────────────────────────────
 
+/// 符号名称的匹配方式，见 [`match_symbol`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchMode {
+    /// 名称完全等于查询串
+    Exact,
+    /// 名称以查询串开头
+    Prefix,
+    /// 名称等于或包含查询串（历史默认行为）
+    Substring,
+    /// CamelHump 风格的子序列匹配，见 [`fuzzy_match_score`]
+    Fuzzy,
+}
+
+impl Default for MatchMode {
+    fn default() -> Self {
+        MatchMode::Substring
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct QueryOptions {
     /// 限制搜索类型："function" | "class" | "type"，None 表示全部
     pub type_filter: Option<String>,
+    /// 符号名称的匹配方式，默认 [`MatchMode::Substring`] 保持历史行为不变
+    pub match_mode: MatchMode,
 }
 
 // ── 核心查询函数 ──────────────────────────────────────────────────────────────
 
 /// 在图谱中搜索匹配的符号（函数、类、类型）。
 ///
-/// 匹配规则：符号名称等于 symbol_name，或包含 symbol_name（子串匹配）。
+/// 匹配方式由 `opts.match_mode` 决定，见 [`MatchMode`]/[`match_symbol`]。结果按
+/// 相关性降序排列：名称与查询串完全相等的结果总是排最前，其次按分数降序，
+/// 再按名称、文件路径排序以保证同分时输出稳定。
 pub fn query_symbol(graph: &CodeGraph, symbol_name: &str, opts: &QueryOptions) -> Vec<SymbolResult> {
     let mut results = Vec::new();
     let type_filter = opts.type_filter.as_deref();
@@ -55,7 +102,7 @@ pub fn query_symbol(graph: &CodeGraph, symbol_name: &str, opts: &QueryOptions) -
         // 搜索函数
         if type_filter.is_none() || type_filter == Some("function") {
             for func in &file_data.functions {
-                if matches_symbol(&func.name, symbol_name) {
+                if let Some(score) = match_symbol(&func.name, symbol_name, opts.match_mode) {
                     let file_imports = collect_file_imports(file_data, &func.name);
                     let imported_by = find_callers(graph, file_path, &func.name);
                     results.push(SymbolResult {
@@ -67,6 +114,7 @@ pub fn query_symbol(graph: &CodeGraph, symbol_name: &str, opts: &QueryOptions) -
                         lines: LineRange { start: func.start_line, end: func.end_line },
                         file_imports,
                         imported_by,
+                        score,
                     });
                 }
             }
@@ -75,7 +123,7 @@ pub fn query_symbol(graph: &CodeGraph, symbol_name: &str, opts: &QueryOptions) -
         // 搜索类
         if type_filter.is_none() || type_filter == Some("class") {
             for cls in &file_data.classes {
-                if matches_symbol(&cls.name, symbol_name) {
+                if let Some(score) = match_symbol(&cls.name, symbol_name, opts.match_mode) {
                     let imported_by = find_callers(graph, file_path, &cls.name);
                     results.push(SymbolResult {
                         kind: "class".into(),
@@ -86,6 +134,7 @@ pub fn query_symbol(graph: &CodeGraph, symbol_name: &str, opts: &QueryOptions) -
                         lines: LineRange { start: cls.start_line, end: cls.end_line },
                         file_imports: vec![],
                         imported_by,
+                        score,
                     });
                 }
             }
@@ -94,7 +143,7 @@ pub fn query_symbol(graph: &CodeGraph, symbol_name: &str, opts: &QueryOptions) -
         // 搜索类型
         if type_filter.is_none() || type_filter == Some("type") {
             for tp in &file_data.types {
-                if matches_symbol(&tp.name, symbol_name) {
+                if let Some(score) = match_symbol(&tp.name, symbol_name, opts.match_mode) {
                     let imported_by = find_callers(graph, file_path, &tp.name);
                     results.push(SymbolResult {
                         kind: "type".into(),
@@ -105,14 +154,22 @@ pub fn query_symbol(graph: &CodeGraph, symbol_name: &str, opts: &QueryOptions) -
                         lines: LineRange { start: tp.start_line, end: tp.end_line },
                         file_imports: vec![],
                         imported_by,
+                        score,
                     });
                 }
             }
         }
     }
 
-    // 按文件路径排序，保证输出稳定
-    results.sort_by(|a, b| a.file.cmp(&b.file).then(a.name.cmp(&b.name)));
+    results.sort_by(|a, b| {
+        let a_exact = a.name == symbol_name;
+        let b_exact = b.name == symbol_name;
+        b_exact
+            .cmp(&a_exact)
+            .then(b.score.cmp(&a.score))
+            .then(a.name.cmp(&b.name))
+            .then(a.file.cmp(&b.file))
+    });
     results
 }
 
@@ -141,11 +198,85 @@ pub fn query_dependencies(graph: &CodeGraph, module_name: &str) -> Vec<String> {
         .unwrap_or_default()
 }
 
+/// 查询模块的完整传递可达性："这个模块能到达什么"（沿 dependsOn 的正向闭包）
+/// 和 "改了这个模块会影响什么"（沿 dependedBy 的反向闭包），每个可达模块都带
+/// 上到起点的跳数。借助 [`crate::impact::detect_cycles`] 标记起点是否处于循环
+/// 依赖环中，提醒调用方跳数只是 BFS 首次到达的最短路径，环内没有单一方向。
+pub fn query_module_transitive(graph: &CodeGraph, module_name: &str) -> Option<TransitiveModuleResult> {
+    if !graph.modules.contains_key(module_name) {
+        return None;
+    }
+
+    let reaches = bfs_module_closure(&graph.modules, module_name, |m| &m.depends_on);
+    let impacted_by_change = bfs_module_closure(&graph.modules, module_name, |m| &m.depended_by);
+    let in_cycle = crate::impact::detect_cycles(graph)
+        .iter()
+        .any(|scc| scc.iter().any(|m| m == module_name));
+
+    Some(TransitiveModuleResult {
+        name: module_name.to_string(),
+        reaches,
+        impacted_by_change,
+        in_cycle,
+    })
+}
+
 // ── 内部辅助函数 ──────────────────────────────────────────────────────────────
 
-/// 符号名称匹配：精确匹配或子串包含
-fn matches_symbol(name: &str, query: &str) -> bool {
-    name == query || name.contains(query)
+/// 按 `mode` 匹配符号名称，命中返回一个相关性分数（越大越相关），不命中返回
+/// `None`。分数只在同一次查询、同一个 `mode` 内部有意义，不同 mode 之间不可比。
+fn match_symbol(name: &str, query: &str, mode: MatchMode) -> Option<i32> {
+    match mode {
+        MatchMode::Exact => (name == query).then_some(1000),
+        // 越短的名称前缀匹配越精确，用负的多余长度当分数
+        MatchMode::Prefix => name.starts_with(query).then(|| 1000 - (name.len() - query.len()) as i32),
+        MatchMode::Substring => {
+            if name == query {
+                Some(1000)
+            } else {
+                name.find(query).map(|pos| 500 - pos as i32)
+            }
+        }
+        MatchMode::Fuzzy => fuzzy_match_score(name, query),
+    }
+}
+
+/// CamelHump 风格的子序列匹配：按顺序在 `name` 里（大小写不敏感地）找到 `query`
+/// 的每一个字符，找不到就返回 `None`。打分规则：
+/// - 匹配位置越靠近名称开头，基础分越高；
+/// - 连续命中（上一个匹配字符的下一位就命中）有连续奖励，跳过的字符数按长度扣分；
+/// - 命中落在词边界（名称开头、`_` 之后、或小写到大写的 camelCase 转折处）有额外奖励。
+fn fuzzy_match_score(name: &str, query: &str) -> Option<i32> {
+    let name_chars: Vec<char> = name.chars().collect();
+    let mut score = 0i32;
+    let mut search_from = 0usize;
+    let mut last_matched: Option<usize> = None;
+
+    for qc in query.chars() {
+        let qc_lower = qc.to_ascii_lowercase();
+        let idx = (search_from..name_chars.len())
+            .find(|&i| name_chars[i].to_ascii_lowercase() == qc_lower)?;
+
+        score += 10 - (idx as i32).min(10);
+
+        match last_matched {
+            Some(last) if idx == last + 1 => score += 15,
+            Some(last) => score -= (idx - last - 1) as i32,
+            None => {}
+        }
+
+        let is_boundary = idx == 0
+            || name_chars[idx - 1] == '_'
+            || (name_chars[idx - 1].is_lowercase() && name_chars[idx].is_uppercase());
+        if is_boundary {
+            score += 10;
+        }
+
+        last_matched = Some(idx);
+        search_from = idx + 1;
+    }
+
+    Some(score)
 }
 
 /// 收集同文件中导入的符号（排除自身）
@@ -158,6 +289,38 @@ fn collect_file_imports(file_data: &FileEntry, self_name: &str) -> Vec<String> {
         .collect()
 }
 
+/// 沿 `neighbors` 选出的边（dependsOn 或 dependedBy）对模块图做 BFS，
+/// 返回除起点外所有可达模块及其跳数，按 (跳数, 名称) 排序以保证确定性。
+/// `seen` 集合保证每个模块只入队一次，循环依赖图下也能正常终止。
+fn bfs_module_closure<F>(
+    modules: &HashMap<String, ModuleEntry>,
+    start: &str,
+    neighbors: F,
+) -> Vec<ReachableModule>
+where
+    F: Fn(&ModuleEntry) -> &Vec<String>,
+{
+    let mut seen: HashSet<String> = HashSet::new();
+    seen.insert(start.to_string());
+    let mut queue: VecDeque<(String, u32)> = VecDeque::new();
+    queue.push_back((start.to_string(), 0));
+    let mut result = Vec::new();
+
+    while let Some((current, distance)) = queue.pop_front() {
+        if let Some(entry) = modules.get(&current) {
+            for next in neighbors(entry) {
+                if seen.insert(next.clone()) {
+                    result.push(ReachableModule { name: next.clone(), distance: distance + 1 });
+                    queue.push_back((next.clone(), distance + 1));
+                }
+            }
+        }
+    }
+
+    result.sort_by(|a, b| a.distance.cmp(&b.distance).then(a.name.cmp(&b.name)));
+    result
+}
+
 /// 查找导入了指定符号的其他文件，返回 "module:file" 格式列表
 fn find_callers(graph: &CodeGraph, source_file: &str, symbol_name: &str) -> Vec<String> {
     let mut callers = Vec::new();
@@ -220,6 +383,32 @@ pub fn format_module_result(result: &ModuleResult) -> String {
     out.trim_end().to_string()
 }
 
+/// 将模块传递可达性查询结果格式化为人类可读的文本
+pub fn format_transitive_result(result: &TransitiveModuleResult) -> String {
+    let mut out = format!("module: {}\n", result.name);
+
+    out.push_str(&format!("  reaches ({}):\n", result.reaches.len()));
+    for m in &result.reaches {
+        out.push_str(&format!("    {} (distance {})\n", m.name, m.distance));
+    }
+
+    out.push_str(&format!(
+        "  impacted by change ({}):\n",
+        result.impacted_by_change.len()
+    ));
+    for m in &result.impacted_by_change {
+        out.push_str(&format!("    {} (distance {})\n", m.name, m.distance));
+    }
+
+    if result.in_cycle {
+        out.push_str(
+            "  warning: module is part of a circular dependency; distances above are shortest-path only\n",
+        );
+    }
+
+    out.trim_end().to_string()
+}
+
 // ── 测试 ──────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -242,18 +431,23 @@ mod tests {
                 module: "auth".into(),
                 hash: "sha256:abc".into(),
                 lines: 30,
+                code_lines: 25,
+                comment_lines: 2,
+                blank_lines: 3,
                 functions: vec![
                     FunctionInfo {
                         name: "login".into(),
                         signature: "login(user: string, pass: string): boolean".into(),
                         start_line: 5,
                         end_line: 15,
+                        complexity: 1,
                     },
                     FunctionInfo {
                         name: "logout".into(),
                         signature: "logout(): void".into(),
                         start_line: 17,
                         end_line: 20,
+                        complexity: 1,
                     },
                 ],
                 classes: vec![ClassInfo {
@@ -266,14 +460,23 @@ mod tests {
                     kind: "type".into(),
                     start_line: 2,
                     end_line: 2,
+                    members: vec![],
                 }],
                 imports: vec![ImportInfo {
                     source: "./utils".into(),
                     symbols: vec!["hashPassword".into()],
                     is_external: false,
+                    dynamic: false,
                 }],
                 exports: vec!["login".into(), "logout".into(), "AuthService".into()],
+                reexports: vec![],
+                resolved_reexports: vec![],
+                calls: vec![],
                 is_entry_point: false,
+                entry_point_reason: None,
+                resolved_imports: vec![],
+                imported_by: vec![],
+                parse_diagnostics: vec![],
             },
         );
 
@@ -285,17 +488,28 @@ mod tests {
                 module: "utils".into(),
                 hash: "sha256:def".into(),
                 lines: 10,
+                code_lines: 8,
+                comment_lines: 0,
+                blank_lines: 2,
                 functions: vec![FunctionInfo {
                     name: "hashPassword".into(),
                     signature: "hashPassword(pw: string): string".into(),
                     start_line: 1,
                     end_line: 8,
+                    complexity: 1,
                 }],
                 classes: vec![],
                 types: vec![],
                 imports: vec![],
                 exports: vec!["hashPassword".into()],
+                reexports: vec![],
+                resolved_reexports: vec![],
+                calls: vec![],
                 is_entry_point: false,
+                entry_point_reason: None,
+                resolved_imports: vec![],
+                imported_by: vec![],
+                parse_diagnostics: vec![],
             },
         );
 
@@ -306,6 +520,9 @@ mod tests {
                 files: vec!["auth/login.ts".into()],
                 depends_on: vec!["utils".into()],
                 depended_by: vec![],
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
             },
         );
         modules.insert(
@@ -314,6 +531,9 @@ mod tests {
                 files: vec!["utils/helper.ts".into()],
                 depends_on: vec![],
                 depended_by: vec!["auth".into()],
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
             },
         );
 
@@ -322,17 +542,24 @@ mod tests {
             project: ProjectInfo { name: "test".into(), root: "/test".into() },
             scanned_at: "2026-01-01T00:00:00.000Z".into(),
             commit_hash: None,
-            config: GraphConfig { languages: vec![], exclude_patterns: vec![] },
+            config: GraphConfig { languages: vec![], exclude_patterns: vec![], include_patterns: vec![] },
             summary: GraphSummary {
                 total_files: 2,
                 total_functions: 3,
                 total_classes: 1,
+                total_code_lines: 0,
+                total_comment_lines: 0,
+                total_blank_lines: 0,
                 languages: HashMap::new(),
                 modules: vec!["auth".into(), "utils".into()],
                 entry_points: vec![],
+                complexity_hotspots: vec![],
+                circular_dependencies: vec![],
+                total_parse_diagnostics: 0,
             },
             modules,
             files,
+            include_diagnostics: vec![],
         }
     }
 
@@ -361,7 +588,7 @@ mod tests {
     #[test]
     fn test_query_type_filter_function() {
         let graph = make_graph();
-        let opts = QueryOptions { type_filter: Some("function".into()) };
+        let opts = QueryOptions { type_filter: Some("function".into()), ..Default::default() };
         let results = query_symbol(&graph, "Auth", &opts);
         // "AuthService" 是 class，过滤后不应出现
         assert!(results.is_empty());
@@ -370,7 +597,7 @@ mod tests {
     #[test]
     fn test_query_type_filter_class() {
         let graph = make_graph();
-        let opts = QueryOptions { type_filter: Some("class".into()) };
+        let opts = QueryOptions { type_filter: Some("class".into()), ..Default::default() };
         let results = query_symbol(&graph, "Auth", &opts);
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].name, "AuthService");
@@ -380,7 +607,7 @@ mod tests {
     #[test]
     fn test_query_type_filter_type() {
         let graph = make_graph();
-        let opts = QueryOptions { type_filter: Some("type".into()) };
+        let opts = QueryOptions { type_filter: Some("type".into()), ..Default::default() };
         let results = query_symbol(&graph, "Token", &opts);
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].name, "UserToken");
@@ -394,6 +621,114 @@ mod tests {
         assert!(results.is_empty());
     }
 
+    /// 一个专门用来测试 match mode/排序的小图谱：三个函数名互为彼此的
+    /// 前缀/子串/重排，刚好能区分 Exact/Prefix/Substring/Fuzzy 四种模式
+    fn make_match_mode_graph() -> CodeGraph {
+        let mut files = HashMap::new();
+        files.insert(
+            "src/parser.ts".to_string(),
+            FileEntry {
+                language: "typescript".into(),
+                module: "parser".into(),
+                hash: "sha256:p".into(),
+                lines: 10,
+                code_lines: 10,
+                comment_lines: 0,
+                blank_lines: 0,
+                functions: vec![
+                    FunctionInfo { name: "parse".into(), signature: "parse(): void".into(), start_line: 1, end_line: 2, complexity: 1 },
+                    FunctionInfo { name: "parseToken".into(), signature: "parseToken(): void".into(), start_line: 3, end_line: 4, complexity: 1 },
+                    FunctionInfo { name: "reparse".into(), signature: "reparse(): void".into(), start_line: 5, end_line: 6, complexity: 1 },
+                ],
+                classes: vec![],
+                types: vec![],
+                imports: vec![],
+                exports: vec![],
+                reexports: vec![],
+                resolved_reexports: vec![],
+                calls: vec![],
+                is_entry_point: false,
+                entry_point_reason: None,
+                resolved_imports: vec![],
+                imported_by: vec![],
+                parse_diagnostics: vec![],
+            },
+        );
+
+        let mut modules = HashMap::new();
+        modules.insert(
+            "parser".into(),
+            ModuleEntry { files: vec!["src/parser.ts".into()], depends_on: vec![], depended_by: vec![], code_lines: 0, comment_lines: 0, blank_lines: 0 },
+        );
+
+        CodeGraph {
+            version: "1.0".into(),
+            project: ProjectInfo { name: "test".into(), root: "/test".into() },
+            scanned_at: "2026-01-01T00:00:00.000Z".into(),
+            commit_hash: None,
+            config: GraphConfig { languages: vec![], exclude_patterns: vec![], include_patterns: vec![] },
+            summary: GraphSummary {
+                total_files: 1,
+                total_functions: 3,
+                total_classes: 0,
+                total_code_lines: 0,
+                total_comment_lines: 0,
+                total_blank_lines: 0,
+                languages: HashMap::new(),
+                modules: vec!["parser".into()],
+                entry_points: vec![],
+                complexity_hotspots: vec![],
+                circular_dependencies: vec![],
+                total_parse_diagnostics: 0,
+            },
+            modules,
+            files,
+            include_diagnostics: vec![],
+        }
+    }
+
+    #[test]
+    fn test_query_match_mode_exact_requires_full_equality() {
+        let graph = make_match_mode_graph();
+        let opts = QueryOptions { match_mode: MatchMode::Exact, ..Default::default() };
+        let results = query_symbol(&graph, "parse", &opts);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "parse");
+    }
+
+    #[test]
+    fn test_query_match_mode_prefix_excludes_non_prefix_substring_hits() {
+        let graph = make_match_mode_graph();
+        let opts = QueryOptions { match_mode: MatchMode::Prefix, ..Default::default() };
+        let results = query_symbol(&graph, "parse", &opts);
+        // "reparse" 包含 "parse" 但不是以它开头，Prefix 模式下不应该命中
+        let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+        assert!(names.contains(&"parse"));
+        assert!(names.contains(&"parseToken"));
+        assert!(!names.contains(&"reparse"));
+    }
+
+    #[test]
+    fn test_query_match_mode_fuzzy_finds_camelhump_subsequence() {
+        let graph = make_match_mode_graph();
+        let opts = QueryOptions { match_mode: MatchMode::Fuzzy, ..Default::default() };
+        // "pTk" 在 "parseToken" 里按顺序出现（p...T...k），Substring/Prefix 都找不到
+        let results = query_symbol(&graph, "pTk", &opts);
+        let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["parseToken"]);
+    }
+
+    #[test]
+    fn test_query_results_rank_exact_match_first_then_by_score() {
+        let graph = make_match_mode_graph();
+        // 默认 Substring 模式：三个函数都命中，"parse" 是精确匹配，应该排第一；
+        // 之后按子串出现位置打分，"parseToken"（命中在第 0 位）排在
+        // "reparse"（命中在第 2 位）前面
+        let results = query_symbol(&graph, "parse", &QueryOptions::default());
+        let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["parse", "parseToken", "reparse"]);
+    }
+
     #[test]
     fn test_find_callers() {
         let graph = make_graph();
@@ -431,6 +766,49 @@ mod tests {
         assert!(deps.contains(&"utils".to_string()));
     }
 
+    #[test]
+    fn test_query_module_transitive_forward_and_reverse() {
+        let graph = make_graph();
+        // auth depends_on utils, utils depended_by auth (no transitive hops beyond that)
+        let auth = query_module_transitive(&graph, "auth").unwrap();
+        assert_eq!(auth.reaches, vec![ReachableModule { name: "utils".into(), distance: 1 }]);
+        assert!(auth.impacted_by_change.is_empty());
+        assert!(!auth.in_cycle);
+
+        let utils = query_module_transitive(&graph, "utils").unwrap();
+        assert!(utils.reaches.is_empty());
+        assert_eq!(utils.impacted_by_change, vec![ReachableModule { name: "auth".into(), distance: 1 }]);
+        assert!(!utils.in_cycle);
+    }
+
+    #[test]
+    fn test_query_module_transitive_not_found() {
+        let graph = make_graph();
+        assert!(query_module_transitive(&graph, "nonexistent").is_none());
+    }
+
+    #[test]
+    fn test_query_module_transitive_detects_cycle() {
+        let mut graph = make_graph();
+        // 手动制造一个循环依赖：auth <-> utils
+        graph.modules.get_mut("utils").unwrap().depends_on.push("auth".into());
+        graph.modules.get_mut("auth").unwrap().depended_by.push("utils".into());
+
+        let auth = query_module_transitive(&graph, "auth").unwrap();
+        assert!(auth.in_cycle);
+    }
+
+    #[test]
+    fn test_format_transitive_result() {
+        let graph = make_graph();
+        let result = query_module_transitive(&graph, "auth").unwrap();
+        let out = format_transitive_result(&result);
+        assert!(out.contains("reaches (1):"));
+        assert!(out.contains("utils (distance 1)"));
+        assert!(out.contains("impacted by change (0):"));
+        assert!(!out.contains("warning:"));
+    }
+
     #[test]
     fn test_format_symbol_results_empty() {
         let out = format_symbol_results(&[]);
@@ -0,0 +1,562 @@
+//! 跨文件符号解析
+//!
+//! 各语言适配器只产出单文件内的 `ImportInfo`/`ExportInfo`，彼此并不关联。这里在整张
+//! `CodeGraph` 之上做一次后处理：把每个非 external 的 import 解析到具体文件里的具体
+//! export，得到一张 (文件, 符号) → (文件, 符号) 的有向图，解析不到的 import 则记为诊断，
+//! 而不是静默丢弃。
+use crate::graph::CodeGraph;
+use crate::path_utils::{posix_dirname, posix_normalize, strip_extension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// 把 project_discovery 发现的各 crate/包根目录、以及（若有）项目根目录本身
+/// 打包在一起传给内部解析函数，省得到处单独传三个参数。`root_dir` 为 `None`
+/// 时（[`resolve_symbols`] 的默认路径）不会去读任何 `package.json` 文件内容——
+/// 纯内存的 [`CodeGraph`] 后处理，不访问文件系统。
+#[derive(Default)]
+struct ProjectContext {
+    rust_src_dirs: Vec<String>,
+    js_package_dirs: Vec<String>,
+    root_dir: Option<PathBuf>,
+}
+
+/// 一个文件内的符号引用
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymbolRef {
+    pub file: String,
+    pub symbol: String,
+}
+
+/// 一条已解析的 import → export 边
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportEdge {
+    pub from: SymbolRef,
+    pub to: SymbolRef,
+}
+
+/// 无法解析的 import，附带原因，供诊断展示
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnresolvedImport {
+    pub file: String,
+    pub source: String,
+    pub symbol: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResolutionResult {
+    pub edges: Vec<ImportEdge>,
+    pub unresolved: Vec<UnresolvedImport>,
+}
+
+/// 对整张图做符号级别的跨文件解析
+///
+/// 输出按 `(from.file, from.symbol, to.file, to.symbol)` 排序，结果在同一张图上多次
+/// 调用时保持一致。Rust 的 `crate::` import 按单 crate 布局解析（crate 根固定为
+/// `src/`）；workspace 或代码在子目录里的项目应该用 [`resolve_symbols_for_project`]。
+pub fn resolve_symbols(graph: &CodeGraph) -> ResolutionResult {
+    resolve_symbols_in_context(graph, &ProjectContext::default())
+}
+
+/// 先跑一遍 [`crate::project_discovery::discover`] 找出项目里每个 Rust crate 的
+/// `src/` 根目录（workspace 成员、代码在子目录里的 monorepo 都覆盖）和每个 JS/TS
+/// 包的目录，再做符号解析：`crate::`/`super::`/`self::` import 相对正确的 crate
+/// 根解析，relative JS import 落在一个包目录上时会去读该包 `package.json` 的
+/// `main`/`exports` 找真正的入口文件，而不是只猜 `index`。
+pub fn resolve_symbols_for_project(graph: &CodeGraph, root_dir: &Path) -> ResolutionResult {
+    let manifests = crate::project_discovery::discover(root_dir);
+    let ctx = ProjectContext {
+        rust_src_dirs: manifests.rust_src_dirs,
+        js_package_dirs: manifests.js_package_dirs,
+        root_dir: Some(root_dir.to_path_buf()),
+    };
+    resolve_symbols_in_context(graph, &ctx)
+}
+
+fn resolve_symbols_in_context(graph: &CodeGraph, ctx: &ProjectContext) -> ResolutionResult {
+    let mut edges = Vec::new();
+    let mut unresolved = Vec::new();
+
+    let mut rel_paths: Vec<&String> = graph.files.keys().collect();
+    rel_paths.sort();
+
+    for rel_path in rel_paths {
+        let file = &graph.files[rel_path];
+        for imp in &file.imports {
+            if imp.is_external {
+                continue;
+            }
+            let target = resolve_import_target(graph, rel_path, &file.language, &imp.source, ctx);
+            let target_file = match target {
+                Some(t) if graph.files.contains_key(&t) => t,
+                _ => {
+                    for symbol in &imp.symbols {
+                        unresolved.push(UnresolvedImport {
+                            file: rel_path.clone(),
+                            source: imp.source.clone(),
+                            symbol: symbol.clone(),
+                            reason: "target file not found".to_string(),
+                        });
+                    }
+                    continue;
+                }
+            };
+
+            for symbol in &imp.symbols {
+                if symbol == "*" {
+                    // 通配符导入：目标文件的每个 export 都算作一条边
+                    for export in &graph.files[&target_file].exports {
+                        edges.push(ImportEdge {
+                            from: SymbolRef { file: rel_path.clone(), symbol: "*".to_string() },
+                            to: SymbolRef { file: target_file.clone(), symbol: export.clone() },
+                        });
+                    }
+                    continue;
+                }
+
+                match resolve_export(graph, &target_file, symbol, ctx, &mut HashSet::new()) {
+                    Some(resolved_file) => edges.push(ImportEdge {
+                        from: SymbolRef { file: rel_path.clone(), symbol: symbol.clone() },
+                        to: SymbolRef { file: resolved_file, symbol: symbol.clone() },
+                    }),
+                    None => unresolved.push(UnresolvedImport {
+                        file: rel_path.clone(),
+                        source: imp.source.clone(),
+                        symbol: symbol.clone(),
+                        reason: format!("'{}' is not exported by {}", symbol, target_file),
+                    }),
+                }
+            }
+        }
+    }
+
+    edges.sort_by(|a, b| {
+        (&a.from.file, &a.from.symbol, &a.to.file, &a.to.symbol)
+            .cmp(&(&b.from.file, &b.from.symbol, &b.to.file, &b.to.symbol))
+    });
+    unresolved.sort_by(|a, b| (&a.file, &a.symbol).cmp(&(&b.file, &b.symbol)));
+
+    ResolutionResult { edges, unresolved }
+}
+
+/// 在 `file` 的 exports 中查找 `symbol`；找不到但 `file` 是一个包的 `__init__`
+/// 重导出（自身又从别处 import 了同名符号）时，顺着它的 import 链继续找，直到命中
+/// 或无路可走。`visited` 防止重导出成环导致死循环。
+fn resolve_export(
+    graph: &CodeGraph,
+    file: &str,
+    symbol: &str,
+    ctx: &ProjectContext,
+    visited: &mut HashSet<String>,
+) -> Option<String> {
+    if !visited.insert(file.to_string()) {
+        return None;
+    }
+    let entry = graph.files.get(file)?;
+    if entry.exports.iter().any(|e| e == symbol) {
+        return Some(file.to_string());
+    }
+
+    // __init__.py 常见的重导出模式：自己没有定义该符号，但从子模块 import 了它
+    let reexport = entry.imports.iter().find(|imp| {
+        !imp.is_external && imp.symbols.iter().any(|s| s == symbol)
+    })?;
+    let next_target = resolve_import_target(graph, file, &entry.language, &reexport.source, ctx)?;
+    resolve_export(graph, &next_target, symbol, ctx, visited)
+}
+
+/// 把一条相对 import 的 `source` 解析为 `graph.files` 中的具体文件键
+fn resolve_import_target(
+    graph: &CodeGraph,
+    importer_rel_path: &str,
+    language: &str,
+    import_source: &str,
+    ctx: &ProjectContext,
+) -> Option<String> {
+    if language == "python" {
+        resolve_python_relative_import(graph, importer_rel_path, import_source)
+    } else if language == "rust" {
+        resolve_rust_import(graph, importer_rel_path, import_source, &ctx.rust_src_dirs)
+    } else {
+        resolve_path_style_import(graph, importer_rel_path, import_source, ctx)
+    }
+}
+
+/// Rust 风格的 `crate::`/`self::`/`super::` import：按目录布局近似模拟模块树
+/// （和 `resolve_path_style_import`/`resolve_python_relative_import` 一样，不做
+/// 真正的 `mod` 声明图解析，只是最常见布局下足够用的启发式）。`crate::` 相对
+/// [`rust_crate_root_for`] 选出的 crate 根解析；`self::`/`super::` 相对 importer
+/// 自身所在目录解析。
+fn resolve_rust_import(
+    graph: &CodeGraph,
+    importer_rel_path: &str,
+    import_source: &str,
+    rust_src_dirs: &[String],
+) -> Option<String> {
+    let module_path = if let Some(rest) = import_source.strip_prefix("crate::") {
+        format!("{}/{}", rust_crate_root_for(importer_rel_path, rust_src_dirs), rest.replace("::", "/"))
+    } else if import_source == "crate" {
+        rust_crate_root_for(importer_rel_path, rust_src_dirs)
+    } else if let Some(rest) = import_source.strip_prefix("self::") {
+        format!("{}/{}", posix_dirname(importer_rel_path), rest.replace("::", "/"))
+    } else if import_source == "self" {
+        posix_dirname(importer_rel_path).to_string()
+    } else if let Some(rest) = import_source.strip_prefix("super::") {
+        format!("{}/{}", posix_dirname(posix_dirname(importer_rel_path)), rest.replace("::", "/"))
+    } else if import_source == "super" {
+        posix_dirname(posix_dirname(importer_rel_path)).to_string()
+    } else {
+        return None;
+    };
+
+    find_rust_module_file(graph, &posix_normalize(&module_path))
+}
+
+/// `importer_rel_path` 所属的 crate 根 `src/` 目录：取 `rust_src_dirs` 里能前缀
+/// 匹配 importer 的最长那个；`rust_src_dirs` 为空（没跑过 project_discovery，见
+/// `resolve_symbols`）或没有任何匹配时，退化为最常见的单 crate 布局 `"src"`。
+fn rust_crate_root_for(importer_rel_path: &str, rust_src_dirs: &[String]) -> String {
+    rust_src_dirs
+        .iter()
+        .filter(|root| {
+            importer_rel_path == root.as_str() || importer_rel_path.starts_with(&format!("{}/", root))
+        })
+        .max_by_key(|root| root.len())
+        .cloned()
+        .unwrap_or_else(|| "src".to_string())
+}
+
+/// 把一个 `crate::`/`self::`/`super::` 模块路径换算成具体文件：要么是
+/// `<path>.rs`，要么是 `<path>/mod.rs`
+fn find_rust_module_file(graph: &CodeGraph, module_path: &str) -> Option<String> {
+    let rs_path = format!("{}.rs", module_path);
+    if graph.files.contains_key(&rs_path) {
+        return Some(rs_path);
+    }
+    let mod_path = format!("{}/mod.rs", module_path);
+    if graph.files.contains_key(&mod_path) {
+        return Some(mod_path);
+    }
+    None
+}
+
+/// JS/TS 风格的相对路径 import（`./foo`、`../bar/baz`）。`resolved` 落在一个已知
+/// 的 JS 包目录上（见 `ctx.js_package_dirs`，来自 `project_discovery`）时，优先用
+/// 该包 `package.json` 的 `main`/`exports` 字段找入口文件，找不到或没有 `root_dir`
+/// （纯内存调用 `resolve_symbols`，见 `ProjectContext`）时才退回猜 `index`。
+fn resolve_path_style_import(
+    graph: &CodeGraph,
+    importer_rel_path: &str,
+    import_source: &str,
+    ctx: &ProjectContext,
+) -> Option<String> {
+    let dir = posix_dirname(importer_rel_path);
+    let joined = format!("{}/{}", dir, import_source);
+    let resolved = posix_normalize(&joined);
+
+    if graph.files.contains_key(&resolved) {
+        return Some(resolved);
+    }
+    let without_ext = strip_extension(&resolved);
+    if let Some(p) = graph.files.keys().find(|p| strip_extension(p) == without_ext) {
+        return Some(p.clone());
+    }
+
+    if ctx.js_package_dirs.iter().any(|d| d == &resolved) {
+        if let Some(p) = resolve_package_json_entry(ctx, &resolved).and_then(|entry| {
+            let without_ext = strip_extension(&entry);
+            graph.files.keys().find(|p| strip_extension(p) == without_ext).cloned()
+        }) {
+            return Some(p);
+        }
+    }
+
+    let index_path = format!("{}/index", resolved);
+    if let Some(p) = graph.files.keys().find(|p| strip_extension(p) == index_path) {
+        return Some(p.clone());
+    }
+    None
+}
+
+/// 读 `package_dir_rel`（相对扫描根）下 `package.json` 的 `main`/`bin`/`exports`，
+/// 取第一个条目，换算成相对扫描根的路径。没有 `root_dir`（见 `ProjectContext`）、
+/// 文件不存在或解析失败时返回 `None`。
+fn resolve_package_json_entry(ctx: &ProjectContext, package_dir_rel: &str) -> Option<String> {
+    let root_dir = ctx.root_dir.as_ref()?;
+    let content = std::fs::read_to_string(root_dir.join(package_dir_rel).join("package.json")).ok()?;
+    let entry = crate::graph::package_json_entry_paths(&content).into_iter().next()?;
+    Some(posix_normalize(&format!("{}/{}", package_dir_rel, entry)))
+}
+
+/// Python 风格的相对 import：开头的每个 `.` 表示向上一级包（第一个 `.` 表示“当前包”，
+/// 之后每多一个 `.` 再上一级），剩余部分（若有）是以 `.` 分隔的子模块路径。
+/// 例如从 `pkg/sub/mod.py` 解析：
+/// - `.`        → `pkg/sub`（当前包的 `__init__.py`）
+/// - `.utils`   → `pkg/sub/utils`
+/// - `..`       → `pkg`
+/// - `..other`  → `pkg/other`
+fn resolve_python_relative_import(
+    graph: &CodeGraph,
+    importer_rel_path: &str,
+    import_source: &str,
+) -> Option<String> {
+    if !import_source.starts_with('.') {
+        return None;
+    }
+
+    let dots = import_source.chars().take_while(|c| *c == '.').count();
+    let rest = &import_source[dots..];
+
+    // importer 所在包目录，再额外上溯 (dots - 1) 级
+    let mut dir = posix_dirname(importer_rel_path).to_string();
+    for _ in 0..dots.saturating_sub(1) {
+        dir = posix_dirname(&dir).to_string();
+    }
+
+    let base = if rest.is_empty() {
+        dir
+    } else {
+        let sub_path = rest.replace('.', "/");
+        if dir == "." { sub_path } else { format!("{}/{}", dir, sub_path) }
+    };
+    let base = posix_normalize(&base);
+
+    // 直接是一个模块文件
+    if let Some(p) = graph.files.keys().find(|p| strip_extension(p) == base) {
+        return Some(p.clone());
+    }
+    // 或者是一个包（目录），落到它的 __init__
+    let init_path = format!("{}/__init__", base);
+    if let Some(p) = graph.files.keys().find(|p| strip_extension(p) == init_path) {
+        return Some(p.clone());
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{create_empty_graph, FileEntry, FunctionInfo, ImportInfo};
+
+    fn make_file(language: &str, exports: Vec<&str>, imports: Vec<ImportInfo>) -> FileEntry {
+        FileEntry {
+            language: language.to_string(),
+            module: "m".to_string(),
+            hash: "sha256:x".to_string(),
+            lines: 1,
+            code_lines: 1,
+            comment_lines: 0,
+            blank_lines: 0,
+            functions: Vec::<FunctionInfo>::new(),
+            classes: vec![],
+            types: vec![],
+            imports,
+            exports: exports.into_iter().map(String::from).collect(),
+            reexports: vec![],
+            resolved_reexports: vec![],
+            calls: vec![],
+            is_entry_point: false,
+            entry_point_reason: None,
+            resolved_imports: vec![],
+            imported_by: vec![],
+            parse_diagnostics: vec![],
+        }
+    }
+
+    #[test]
+    fn test_resolves_js_relative_import() {
+        let mut graph = create_empty_graph("demo", "/proj");
+        graph.files.insert("src/utils.ts".to_string(), make_file("typescript", vec!["formatDate"], vec![]));
+        graph.files.insert(
+            "src/app.ts".to_string(),
+            make_file(
+                "typescript",
+                vec![],
+                vec![ImportInfo { source: "./utils".to_string(), symbols: vec!["formatDate".to_string()], is_external: false, dynamic: false }],
+            ),
+        );
+
+        let result = resolve_symbols(&graph);
+        assert_eq!(result.edges.len(), 1);
+        assert_eq!(result.edges[0].from, SymbolRef { file: "src/app.ts".to_string(), symbol: "formatDate".to_string() });
+        assert_eq!(result.edges[0].to, SymbolRef { file: "src/utils.ts".to_string(), symbol: "formatDate".to_string() });
+        assert!(result.unresolved.is_empty());
+    }
+
+    #[test]
+    fn test_resolves_python_relative_import_from_package() {
+        let mut graph = create_empty_graph("demo", "/proj");
+        graph.files.insert("pkg/utils.py".to_string(), make_file("python", vec!["helper"], vec![]));
+        graph.files.insert(
+            "pkg/sub/mod.py".to_string(),
+            make_file(
+                "python",
+                vec![],
+                vec![ImportInfo { source: "..utils".to_string(), symbols: vec!["helper".to_string()], is_external: false, dynamic: false }],
+            ),
+        );
+
+        let result = resolve_symbols(&graph);
+        assert_eq!(result.edges.len(), 1);
+        assert_eq!(result.edges[0].to.file, "pkg/utils.py");
+    }
+
+    #[test]
+    fn test_resolves_through_init_reexport() {
+        let mut graph = create_empty_graph("demo", "/proj");
+        graph.files.insert("pkg/impl.py".to_string(), make_file("python", vec!["Widget"], vec![]));
+        graph.files.insert(
+            "pkg/__init__.py".to_string(),
+            make_file(
+                "python",
+                vec![],
+                vec![ImportInfo { source: ".impl".to_string(), symbols: vec!["Widget".to_string()], is_external: false, dynamic: false }],
+            ),
+        );
+        graph.files.insert(
+            "main.py".to_string(),
+            make_file(
+                "python",
+                vec![],
+                vec![ImportInfo { source: ".pkg".to_string(), symbols: vec!["Widget".to_string()], is_external: false, dynamic: false }],
+            ),
+        );
+
+        let result = resolve_symbols(&graph);
+        let edge = result.edges.iter().find(|e| e.from.file == "main.py").expect("should resolve through __init__.py");
+        assert_eq!(edge.to.file, "pkg/impl.py");
+    }
+
+    #[test]
+    fn test_unresolved_when_symbol_missing() {
+        let mut graph = create_empty_graph("demo", "/proj");
+        graph.files.insert("src/utils.ts".to_string(), make_file("typescript", vec!["formatDate"], vec![]));
+        graph.files.insert(
+            "src/app.ts".to_string(),
+            make_file(
+                "typescript",
+                vec![],
+                vec![ImportInfo { source: "./utils".to_string(), symbols: vec!["missingFn".to_string()], is_external: false, dynamic: false }],
+            ),
+        );
+
+        let result = resolve_symbols(&graph);
+        assert!(result.edges.is_empty());
+        assert_eq!(result.unresolved.len(), 1);
+        assert_eq!(result.unresolved[0].symbol, "missingFn");
+    }
+
+    #[test]
+    fn test_unresolved_when_target_file_missing() {
+        let mut graph = create_empty_graph("demo", "/proj");
+        graph.files.insert(
+            "src/app.ts".to_string(),
+            make_file(
+                "typescript",
+                vec![],
+                vec![ImportInfo { source: "./does-not-exist".to_string(), symbols: vec!["x".to_string()], is_external: false, dynamic: false }],
+            ),
+        );
+
+        let result = resolve_symbols(&graph);
+        assert!(result.edges.is_empty());
+        assert_eq!(result.unresolved[0].reason, "target file not found");
+    }
+
+    #[test]
+    fn test_resolves_rust_crate_path_against_default_src_root() {
+        let mut graph = create_empty_graph("demo", "/proj");
+        graph.files.insert("src/utils/mod.rs".to_string(), make_file("rust", vec!["helper"], vec![]));
+        graph.files.insert(
+            "src/main.rs".to_string(),
+            make_file(
+                "rust",
+                vec![],
+                vec![ImportInfo { source: "crate::utils".to_string(), symbols: vec!["helper".to_string()], is_external: false, dynamic: false }],
+            ),
+        );
+
+        let result = resolve_symbols(&graph);
+        assert_eq!(result.edges.len(), 1);
+        assert_eq!(result.edges[0].to.file, "src/utils/mod.rs");
+    }
+
+    #[test]
+    fn test_resolves_rust_super_path() {
+        let mut graph = create_empty_graph("demo", "/proj");
+        graph.files.insert("src/utils.rs".to_string(), make_file("rust", vec!["helper"], vec![]));
+        graph.files.insert(
+            "src/sub/mod.rs".to_string(),
+            make_file(
+                "rust",
+                vec![],
+                vec![ImportInfo { source: "super::utils".to_string(), symbols: vec!["helper".to_string()], is_external: false, dynamic: false }],
+            ),
+        );
+
+        let result = resolve_symbols(&graph);
+        assert_eq!(result.edges.len(), 1);
+        assert_eq!(result.edges[0].to.file, "src/utils.rs");
+    }
+
+    #[test]
+    fn test_resolves_rust_crate_path_for_workspace_member_via_project_context() {
+        let mut graph = create_empty_graph("demo", "/proj");
+        graph.files.insert("crates/core/src/utils.rs".to_string(), make_file("rust", vec!["helper"], vec![]));
+        graph.files.insert(
+            "crates/core/src/main.rs".to_string(),
+            make_file(
+                "rust",
+                vec![],
+                vec![ImportInfo { source: "crate::utils".to_string(), symbols: vec!["helper".to_string()], is_external: false, dynamic: false }],
+            ),
+        );
+
+        // 没有 project_discovery 信息时，`crate::` 假定单 crate 布局（根为 "src"），解析不到
+        let default_result = resolve_symbols(&graph);
+        assert!(default_result.edges.is_empty());
+
+        // 带上正确的 crate 根之后才能解析
+        let ctx = ProjectContext {
+            rust_src_dirs: vec!["crates/core/src".to_string()],
+            js_package_dirs: vec![],
+            root_dir: None,
+        };
+        let result = resolve_symbols_in_context(&graph, &ctx);
+        assert_eq!(result.edges.len(), 1);
+        assert_eq!(result.edges[0].to.file, "crates/core/src/utils.rs");
+    }
+
+    #[test]
+    fn test_resolves_js_package_dir_via_package_json_main() {
+        let dir = std::env::temp_dir().join("codemap-resolver-test-js-package-main");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("packages/widgets/dist")).unwrap();
+        std::fs::write(
+            dir.join("packages/widgets/package.json"),
+            r#"{"main": "dist/index.js"}"#,
+        ).unwrap();
+
+        let mut graph = create_empty_graph("demo", "/proj");
+        graph.files.insert("packages/widgets/dist/index.js".to_string(), make_file("javascript", vec!["Button"], vec![]));
+        graph.files.insert(
+            "src/app.js".to_string(),
+            make_file(
+                "javascript",
+                vec![],
+                vec![ImportInfo { source: "../packages/widgets".to_string(), symbols: vec!["Button".to_string()], is_external: false, dynamic: false }],
+            ),
+        );
+
+        let ctx = ProjectContext {
+            rust_src_dirs: vec![],
+            js_package_dirs: vec!["packages/widgets".to_string()],
+            root_dir: Some(dir),
+        };
+        let result = resolve_symbols_in_context(&graph, &ctx);
+        assert_eq!(result.edges.len(), 1);
+        assert_eq!(result.edges[0].to.file, "packages/widgets/dist/index.js");
+    }
+}
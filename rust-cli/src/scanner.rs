@@ -1,11 +1,14 @@
 use crate::graph::{
-    compute_file_hash, create_empty_graph, is_entry_point, save_graph, CodeGraph, FileEntry,
+    compute_file_hash, create_empty_graph, save_graph, CodeGraph, FileEntry,
     FunctionInfo as GraphFunctionInfo, ClassInfo as GraphClassInfo,
-    TypeInfo as GraphTypeInfo, ImportInfo as GraphImportInfo, ModuleEntry,
+    TypeInfo as GraphTypeInfo, ImportInfo as GraphImportInfo, CallInfo as GraphCallInfo,
+    ModuleEntry,
 };
+use crate::differ::ImportResolver as _;
 use crate::languages;
 use crate::path_utils::{normalize_path, strip_extension};
 use crate::traverser::{detect_language, effective_language, has_cpp_source_files, traverse_files, Language};
+use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
@@ -15,16 +18,19 @@ use std::path::{Path, PathBuf};
 
 pub fn convert_functions(lang_functions: &[languages::FunctionInfo]) -> Vec<GraphFunctionInfo> {
     lang_functions.iter().map(|f| {
-        let sig = if f.params.is_empty() {
-            format!("{}()", f.name)
-        } else {
-            format!("{}({})", f.name, f.params.join(", "))
-        };
+        let params_str = f.params.iter().map(|p| p.render()).collect::<Vec<_>>().join(", ");
+        let generics = f.type_parameters.clone().unwrap_or_default();
+        let mut sig = format!("{}{}({})", f.name, generics, params_str);
+        if let Some(return_type) = &f.return_type {
+            sig.push_str(" -> ");
+            sig.push_str(return_type);
+        }
         GraphFunctionInfo {
             name: f.name.clone(),
             signature: sig,
             start_line: f.start_line as u32,
             end_line: f.end_line as u32,
+            complexity: f.complexity,
         }
     }).collect()
 }
@@ -49,19 +55,72 @@ pub fn convert_types(lang_classes: &[languages::ClassInfo], lang: Language) -> V
             kind: c.kind.clone(),
             start_line: c.start_line as u32,
             end_line: c.end_line as u32,
+            members: c.members.iter().map(|m| crate::graph::TypeMember {
+                name: m.name.clone(),
+                kind: match m.kind {
+                    languages::MemberKind::Field => "field".to_string(),
+                    languages::MemberKind::Method => "method".to_string(),
+                },
+                optional: m.optional,
+                type_annotation: m.type_annotation.clone(),
+            }).collect(),
         }).collect()
 }
 
-pub fn convert_imports(lang_imports: &[languages::ImportInfo]) -> Vec<GraphImportInfo> {
-    lang_imports.iter().map(|i| GraphImportInfo {
-        source: i.source.clone(),
-        symbols: i.names.clone(),
-        is_external: !i.source.starts_with('.'),
+pub fn convert_imports(
+    lang_imports: &[languages::ImportInfo],
+    lang: Language,
+    go_module_path: Option<&str>,
+) -> Vec<GraphImportInfo> {
+    lang_imports.iter().map(|i| {
+        let is_external = if lang == Language::Go {
+            use languages::go_lang::{classify_go_import, GoImportOrigin};
+            classify_go_import(&i.source, go_module_path) != GoImportOrigin::Internal
+        } else if lang == Language::Rust {
+            !languages::rust_lang::is_internal_rust_import(&i.source)
+        } else {
+            !i.source.starts_with('.')
+        };
+        GraphImportInfo {
+            source: i.source.clone(),
+            symbols: i.names.clone(),
+            is_external,
+            dynamic: i.dynamic,
+        }
     }).collect()
 }
 
-pub fn convert_exports(lang_exports: &[languages::ExportInfo]) -> Vec<String> {
-    lang_exports.iter().map(|e| e.name.clone()).collect()
+/// 把适配器产出的 `ExportInfo` 拆成本地声明的导出名和 barrel re-export 两份：
+/// `reexport_source` 为 `None` 的条目是本地声明，按老样子落进 `exports`；
+/// 带 `reexport_source` 的条目（`export { a } from '../mod'`/`export * from './x'`）
+/// 不计入 `exports`（此时它还不是"这个文件真正声明的符号"），改落进 `reexports`，
+/// 真正的符号列表要等 [`crate::scanner::resolve_file_imports`] 解析出目标文件、
+/// 再由 [`crate::slicer`] 的 re-export 解析阶段折叠进消费方看到的导出列表
+pub fn convert_exports(lang_exports: &[languages::ExportInfo]) -> (Vec<String>, Vec<crate::graph::ReexportInfo>) {
+    let mut exports = Vec::new();
+    let mut reexports = Vec::new();
+    for e in lang_exports {
+        match &e.reexport_source {
+            Some(source) => reexports.push(crate::graph::ReexportInfo {
+                name: e.name.clone(),
+                source: source.clone(),
+                star: e.star,
+            }),
+            None => exports.push(e.name.clone()),
+        }
+    }
+    (exports, reexports)
+}
+
+pub fn convert_calls(lang_calls: &[languages::CallInfo]) -> Vec<GraphCallInfo> {
+    lang_calls.iter().map(|c| GraphCallInfo {
+        caller: c.caller.clone(),
+        callee: c.callee.clone(),
+        line: c.line as u32,
+        // 是否命中已知函数要等全量 graph.files 都组装完才能判断，先落 false，
+        // 由装配阶段之后跑的 resolve_calls 回填，见该函数
+        resolved: false,
+    }).collect()
 }
 
 /// 根目录级别的常见目录名，跳过这些层级来确定模块名
@@ -105,102 +164,296 @@ pub fn detect_module_name(file_path: &Path, root_dir: &Path) -> String {
 
 /// 扫描整个项目，构建 CodeGraph
 pub fn scan_project(root_dir: &Path, exclude: &[String]) -> anyhow::Result<CodeGraph> {
+    scan_project_with_filter(root_dir, &crate::traverser::ScanFilter::new(vec![], exclude.to_vec()))
+}
+
+/// 扫描整个项目，构建 CodeGraph，`filter` 额外支持 include 白名单（见
+/// [`crate::traverser::ScanFilter`]），`scan_project` 是它只用 exclude 的快捷方式
+pub fn scan_project_with_filter(root_dir: &Path, filter: &crate::traverser::ScanFilter) -> anyhow::Result<CodeGraph> {
+    scan_project_with_progress(root_dir, filter, &mut crate::progress::NoopSink)
+}
+
+// Step 2（解析每个文件）产出的中间结果：每个文件的解析互不依赖，是扫描里最耗时的部分，
+// 因此 `scan_project_with_progress`/`scan_project_incremental` 都用 rayon 的 par_iter
+// 并行跑；tree_sitter::Parser 不是 Sync，所以每个任务各自 new 一个，不跨任务共享。
+// Step 3 起的模块/依赖解析仍是顺序的，等并行结果全部收集齐之后再做。
+struct FileInfo {
+    rel_path: String,
+    language: String,
+    module_name: String,
+    hash: String,
+    lines: u32,
+    code_lines: u32,
+    comment_lines: u32,
+    blank_lines: u32,
+    functions: Vec<crate::graph::FunctionInfo>,
+    classes: Vec<crate::graph::ClassInfo>,
+    types: Vec<crate::graph::TypeInfo>,
+    imports: Vec<crate::graph::ImportInfo>,
+    exports: Vec<String>,
+    reexports: Vec<crate::graph::ReexportInfo>,
+    calls: Vec<crate::graph::CallInfo>,
+    is_entry_point: bool,
+    entry_point_reason: Option<String>,
+    parse_diagnostics: Vec<crate::graph::ParseDiagnostic>,
+}
+
+/// 对单个文件做一次完整的 tree-sitter 解析 + 适配器抽取 + graph 类型转换
+///
+/// `scan_project_with_progress` 的全量扫描和 `scan_project_incremental` 里哈希不匹配
+/// 的文件都走这里；解析失败（语言探测失败、读文件失败、tree-sitter 建树失败）返回 `None`，
+/// 调用方用 `filter_map` 直接跳过该文件。
+fn parse_file_info(
+    abs_path: &Path,
+    root_dir: &Path,
+    has_cpp: bool,
+    go_module_path: Option<&str>,
+    manifest_hints: &crate::graph::ManifestHints,
+) -> Option<FileInfo> {
+    let base_lang = detect_language(abs_path)?;
+    let lang = effective_language(abs_path, base_lang, has_cpp);
+
+    let content = std::fs::read(abs_path).ok()?;
+
+    let hash = compute_file_hash(&content);
+    let adapter = languages::get_adapter(lang);
+
+    // 用语言适配器解析
+    let mut ts_parser = tree_sitter::Parser::new();
+    ts_parser.set_language(&adapter.language()).ok();
+    let tree = ts_parser.parse(&content, None)?;
+
+    let lang_functions = adapter.extract_functions(&tree, &content);
+    let lang_imports = adapter.extract_imports(&tree, &content);
+    let lang_exports = adapter.extract_exports(&tree, &content);
+    let lang_classes = adapter.extract_classes(&tree, &content);
+    let lang_calls = adapter.extract_calls(&tree, &content);
+    let lines = content.iter().filter(|&&b| b == b'\n').count() as u32 + 1;
+    let (code_lines, comment_lines, blank_lines) = classify_lines(&tree, &content);
+    let parse_diagnostics = collect_parse_diagnostics(&tree, &content);
+
+    // 转换为 graph 数据结构
+    let functions = convert_functions(&lang_functions);
+    let classes = convert_classes(&lang_classes);
+    let types = convert_types(&lang_classes, lang);
+    let imports = convert_imports(&lang_imports, lang, go_module_path);
+    let (exports, reexports) = convert_exports(&lang_exports);
+    let calls = convert_calls(&lang_calls);
+
+    let module_name = detect_module_name(abs_path, root_dir);
+
+    let rel_path = abs_path
+        .strip_prefix(root_dir)
+        .unwrap_or(abs_path)
+        .to_string_lossy()
+        .replace('\\', "/");
+
+    let lang_str = lang.as_str().to_string();
+
+    // detect_entry_point 只看 language/functions 字段，借一个临时 FileEntry 调用它，
+    // 避免在这里重新实现一遍判定逻辑
+    let probe_entry = FileEntry {
+        language: lang_str.clone(),
+        module: module_name.clone(),
+        hash: hash.clone(),
+        lines,
+        code_lines,
+        comment_lines,
+        blank_lines,
+        functions: functions.clone(),
+        classes: classes.clone(),
+        types: types.clone(),
+        imports: imports.clone(),
+        exports: exports.clone(),
+        reexports: reexports.clone(),
+        resolved_reexports: vec![],
+        calls: calls.clone(),
+        is_entry_point: false,
+        entry_point_reason: None,
+        resolved_imports: vec![],
+        imported_by: vec![],
+        parse_diagnostics: parse_diagnostics.clone(),
+    };
+    let reason = crate::graph::detect_entry_point(&probe_entry, abs_path, manifest_hints);
+
+    Some(FileInfo {
+        rel_path,
+        language: lang_str,
+        module_name,
+        hash,
+        lines,
+        code_lines,
+        comment_lines,
+        blank_lines,
+        functions,
+        classes,
+        types,
+        imports,
+        exports,
+        reexports,
+        calls,
+        is_entry_point: reason.is_some(),
+        entry_point_reason: reason.map(|r| r.as_str().to_string()),
+        parse_diagnostics,
+    })
+}
+
+/// 从上一次扫描的 `FileEntry` 原样克隆出一份 `FileInfo`，不做任何重新解析
+///
+/// 供 `scan_project_incremental` 在哈希未变时复用：拼出来的字段与 `parse_file_info`
+/// 走一遍全量解析的结果应当完全一致（因为源文件内容根本没变）。
+fn file_info_from_prev_entry(rel_path: String, prev: &FileEntry) -> FileInfo {
+    FileInfo {
+        rel_path,
+        language: prev.language.clone(),
+        module_name: prev.module.clone(),
+        hash: prev.hash.clone(),
+        lines: prev.lines,
+        code_lines: prev.code_lines,
+        comment_lines: prev.comment_lines,
+        blank_lines: prev.blank_lines,
+        functions: prev.functions.clone(),
+        classes: prev.classes.clone(),
+        types: prev.types.clone(),
+        imports: prev.imports.clone(),
+        exports: prev.exports.clone(),
+        reexports: prev.reexports.clone(),
+        calls: prev.calls.clone(),
+        is_entry_point: prev.is_entry_point,
+        entry_point_reason: prev.entry_point_reason.clone(),
+        parse_diagnostics: prev.parse_diagnostics.clone(),
+    }
+}
+
+/// 扫描整个项目，构建 CodeGraph，同时把进度事件交给 `sink`
+///
+/// 行为与 `scan_project` 完全一致，只是在每个文件解析完成后以及最后都会调用
+/// `sink.emit(...)`；传入 `NoopSink` 等价于 `scan_project`。
+pub fn scan_project_with_progress(
+    root_dir: &Path,
+    filter: &crate::traverser::ScanFilter,
+    sink: &mut dyn crate::progress::ProgressSink,
+) -> anyhow::Result<CodeGraph> {
     let project_name = root_dir
         .file_name()
         .and_then(|n| n.to_str())
         .unwrap_or("unknown");
     let root_str = root_dir.to_string_lossy().replace('\\', "/");
     let mut graph = create_empty_graph(project_name, &root_str);
+    graph.commit_hash = crate::differ::git_head_commit(root_dir);
+    let go_module_path = languages::go_lang::read_module_path(root_dir);
+    let manifest_hints = crate::graph::read_manifest_hints(root_dir);
 
     // Step 1: 遍历文件
+    let files = crate::traverser::traverse_files_filtered(root_dir, filter);
+    let has_cpp = has_cpp_source_files(&files);
+
+    // Step 2: 解析每个文件（见 parse_file_info）
+    let mut file_infos: Vec<(PathBuf, FileInfo)> = files
+        .par_iter()
+        .filter_map(|abs_path| {
+            let info = parse_file_info(abs_path, root_dir, has_cpp, go_module_path.as_deref(), &manifest_hints)?;
+            Some((abs_path.clone(), info))
+        })
+        .collect();
+
+    // 并行收集的顺序不确定，按相对路径排序以保证输出确定性
+    file_infos.sort_by(|a, b| a.1.rel_path.cmp(&b.1.rel_path));
+
+    Ok(assemble_graph(graph, file_infos, go_module_path.as_deref(), filter, sink))
+}
+
+/// 增量扫描：哈希未变的文件直接克隆 `prev_graph` 里的 `FileEntry`，跳过 tree-sitter 解析；
+/// 只有新增文件、哈希对不上的文件才会真正走 `parse_file_info`
+///
+/// 对应 Deno LSP 按文档 fs version 复用已解析状态的思路：文件列表本身的遍历、读取内容、
+/// 算哈希都不算贵，真正贵的是 tree-sitter 建树 + 适配器抽取，所以只在哈希不匹配时才做。
+/// 模块依赖图和 summary 在文件一遍过后整体重新计算（边可能因为任何一个文件的变化而改变，
+/// 没法只增量更新其中一部分）；磁盘上已经消失的文件因为不在 `files` 遍历结果里，
+/// 自然不会进入新的 `file_infos`，等同于被丢弃。
+pub fn scan_project_incremental(
+    root_dir: &Path,
+    prev_graph: &CodeGraph,
+    exclude: &[String],
+) -> anyhow::Result<CodeGraph> {
+    let project_name = root_dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown");
+    let root_str = root_dir.to_string_lossy().replace('\\', "/");
+    let mut graph = create_empty_graph(project_name, &root_str);
+    graph.commit_hash = crate::differ::git_head_commit(root_dir);
+    let go_module_path = languages::go_lang::read_module_path(root_dir);
+    let manifest_hints = crate::graph::read_manifest_hints(root_dir);
+
     let files = traverse_files(root_dir, exclude);
     let has_cpp = has_cpp_source_files(&files);
 
-    // Step 2: 解析每个文件
-    struct FileInfo {
-        rel_path: String,
-        language: String,
-        module_name: String,
-        hash: String,
-        lines: u32,
-        functions: Vec<crate::graph::FunctionInfo>,
-        classes: Vec<crate::graph::ClassInfo>,
-        types: Vec<crate::graph::TypeInfo>,
-        imports: Vec<crate::graph::ImportInfo>,
-        exports: Vec<String>,
-        is_entry_point: bool,
-    }
-
-    let mut file_infos: Vec<(PathBuf, FileInfo)> = Vec::new();
-    let mut language_counts: HashMap<String, u32> = HashMap::new();
-    let mut total_functions = 0u32;
-    let mut total_classes = 0u32;
-    let mut module_set: HashSet<String> = HashSet::new();
+    let mut file_infos: Vec<(PathBuf, FileInfo)> = files
+        .par_iter()
+        .filter_map(|abs_path| {
+            if detect_language(abs_path).is_none() {
+                return None;
+            }
+            let rel_path = abs_path
+                .strip_prefix(root_dir)
+                .unwrap_or(abs_path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            let content = std::fs::read(abs_path).ok()?;
+            let hash = compute_file_hash(&content);
 
-    for abs_path in &files {
-        let base_lang = match detect_language(abs_path) {
-            Some(l) => l,
-            None => continue,
-        };
-        let lang = effective_language(abs_path, base_lang, has_cpp);
+            if let Some(prev_entry) = prev_graph.files.get(&rel_path) {
+                if prev_entry.hash == hash {
+                    return Some((abs_path.clone(), file_info_from_prev_entry(rel_path, prev_entry)));
+                }
+            }
 
-        let content = match std::fs::read(abs_path) {
-            Ok(c) => c,
-            Err(_) => continue,
-        };
+            let info = parse_file_info(abs_path, root_dir, has_cpp, go_module_path.as_deref(), &manifest_hints)?;
+            Some((abs_path.clone(), info))
+        })
+        .collect();
 
-        let hash = compute_file_hash(&content);
-        let adapter = languages::get_adapter(lang);
+    file_infos.sort_by(|a, b| a.1.rel_path.cmp(&b.1.rel_path));
 
-        // 用语言适配器解析
-        let mut ts_parser = tree_sitter::Parser::new();
-        ts_parser.set_language(&adapter.language()).ok();
-        let tree = match ts_parser.parse(&content, None) {
-            Some(t) => t,
-            None => continue,
-        };
+    let filter = crate::traverser::ScanFilter::new(vec![], exclude.to_vec());
+    Ok(assemble_graph(graph, file_infos, go_module_path.as_deref(), &filter, &mut crate::progress::NoopSink))
+}
 
-        let lang_functions = adapter.extract_functions(&tree, &content);
-        let lang_imports = adapter.extract_imports(&tree, &content);
-        let lang_exports = adapter.extract_exports(&tree, &content);
-        let lang_classes = adapter.extract_classes(&tree, &content);
-        let lines = content.iter().filter(|&&b| b == b'\n').count() as u32 + 1;
+/// Step 3-6：从解析好的 `file_infos` 里填充模块表、跨模块依赖、`graph.files`、
+/// 文件级导入解析（[`resolve_file_imports`]）和 summary，是 `scan_project_with_progress`
+/// 和 `scan_project_incremental` 共用的收尾逻辑——两者的差异只在 Step 2 怎么拿到
+/// `file_infos`，拿到之后的处理完全一样。
+fn assemble_graph(
+    mut graph: CodeGraph,
+    file_infos: Vec<(PathBuf, FileInfo)>,
+    go_module_path: Option<&str>,
+    filter: &crate::traverser::ScanFilter,
+    sink: &mut dyn crate::progress::ProgressSink,
+) -> CodeGraph {
+    let mut language_counts: HashMap<String, u32> = HashMap::new();
+    let mut total_functions = 0u32;
+    let mut total_classes = 0u32;
+    let mut total_code_lines = 0u32;
+    let mut total_comment_lines = 0u32;
+    let mut total_blank_lines = 0u32;
+    let mut total_parse_diagnostics = 0u32;
+    let mut module_set: HashSet<String> = HashSet::new();
 
-        // 转换为 graph 数据结构
-        let functions = convert_functions(&lang_functions);
-        let classes = convert_classes(&lang_classes);
-        let types = convert_types(&lang_classes, lang);
-        let imports = convert_imports(&lang_imports);
-        let exports = convert_exports(&lang_exports);
-
-        let module_name = detect_module_name(abs_path, root_dir);
-        module_set.insert(module_name.clone());
-
-        let lang_str = lang.as_str().to_string();
-        *language_counts.entry(lang_str.clone()).or_insert(0) += 1;
-        total_functions += functions.len() as u32;
-        total_classes += classes.len() as u32;
-
-        let rel_path = abs_path
-            .strip_prefix(root_dir)
-            .unwrap_or(abs_path)
-            .to_string_lossy()
-            .replace('\\', "/");
-
-        let entry = FileInfo {
-            rel_path,
-            language: lang_str,
-            module_name,
-            hash,
-            lines,
-            functions,
-            classes,
-            types,
-            imports,
-            exports,
-            is_entry_point: is_entry_point(abs_path),
-        };
-        file_infos.push((abs_path.clone(), entry));
+    for (_, info) in &file_infos {
+        *language_counts.entry(info.language.clone()).or_insert(0) += 1;
+        total_functions += info.functions.len() as u32;
+        total_classes += info.classes.len() as u32;
+        total_code_lines += info.code_lines;
+        total_comment_lines += info.comment_lines;
+        total_blank_lines += info.blank_lines;
+        total_parse_diagnostics += info.parse_diagnostics.len() as u32;
+        module_set.insert(info.module_name.clone());
+
+        sink.emit(crate::progress::ScanEvent::FileScanned {
+            path: info.rel_path.clone(),
+            functions: info.functions.len() as u32,
+        });
     }
 
     // Step 3: 初始化模块表
@@ -212,12 +465,17 @@ pub fn scan_project(root_dir: &Path, exclude: &[String]) -> anyhow::Result<CodeG
                 files: vec![],
                 depends_on: vec![],
                 depended_by: vec![],
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
             },
         );
     }
 
     // 构建路径 → 模块名的查找表（O(1) 导入解析）
     let mut path_lookup: HashMap<String, String> = HashMap::new();
+    // 相对路径版本，供 Go 的 `ImportResolver`（按 go.mod 模块路径剥离前缀）复用
+    let mut rel_path_lookup: HashMap<String, String> = HashMap::new();
     for (abs_path, info) in &file_infos {
         let norm = abs_path.to_string_lossy().replace('\\', "/");
         path_lookup.insert(norm.clone(), info.module_name.clone());
@@ -226,6 +484,12 @@ pub fn scan_project(root_dir: &Path, exclude: &[String]) -> anyhow::Result<CodeG
         path_lookup
             .entry(without_ext)
             .or_insert_with(|| info.module_name.clone());
+
+        rel_path_lookup.insert(info.rel_path.clone(), info.module_name.clone());
+        let rel_without_ext = strip_extension(&info.rel_path);
+        rel_path_lookup
+            .entry(rel_without_ext)
+            .or_insert_with(|| info.module_name.clone());
     }
 
     // Step 4: 填充 graph.files 并解析跨模块依赖
@@ -237,14 +501,22 @@ pub fn scan_project(root_dir: &Path, exclude: &[String]) -> anyhow::Result<CodeG
     }
 
     for (abs_path, info) in &file_infos {
-        // 解析导入依赖
+        // 解析导入依赖：优先走该语言的 `ImportResolver`（JS/TS、Python、Rust、Go、Java 都有），
+        // 没有解析器的语言（C/C++）回退到旧的 JS 风格相对路径启发式
+        let resolver = crate::differ::resolver_for_language(&info.language, go_module_path);
+        let importer_dir = crate::path_utils::posix_dirname(&info.rel_path).to_string();
+
         for imp in &info.imports {
-            if imp.is_external {
-                continue;
-            }
-            if let Some(target_mod) =
-                resolve_import_module(abs_path, &imp.source, &path_lookup, &info.module_name)
-            {
+            let target = match &resolver {
+                Some(resolver) => resolver
+                    .resolve_import_candidates(&importer_dir, imp)
+                    .iter()
+                    .find_map(|candidate| {
+                        crate::differ::lookup_module(&rel_path_lookup, candidate, resolver.index_stems())
+                    }),
+                None => resolve_import_module(abs_path, &imp.source, &path_lookup, &info.module_name),
+            };
+            if let Some(target_mod) = target {
                 if target_mod != info.module_name {
                     depends_on_map
                         .entry(info.module_name.clone())
@@ -266,12 +538,22 @@ pub fn scan_project(root_dir: &Path, exclude: &[String]) -> anyhow::Result<CodeG
                 module: info.module_name.clone(),
                 hash: info.hash.clone(),
                 lines: info.lines,
+                code_lines: info.code_lines,
+                comment_lines: info.comment_lines,
+                blank_lines: info.blank_lines,
                 functions: info.functions.clone(),
                 classes: info.classes.clone(),
                 types: info.types.clone(),
                 imports: info.imports.clone(),
                 exports: info.exports.clone(),
+                reexports: info.reexports.clone(),
+                resolved_reexports: vec![],
+                calls: info.calls.clone(),
                 is_entry_point: info.is_entry_point,
+                entry_point_reason: info.entry_point_reason.clone(),
+                resolved_imports: vec![],
+                imported_by: vec![],
+                parse_diagnostics: info.parse_diagnostics.clone(),
             },
         );
 
@@ -298,11 +580,30 @@ pub fn scan_project(root_dir: &Path, exclude: &[String]) -> anyhow::Result<CodeG
         mod_entry.depended_by = dep_by;
     }
     graph.modules = modules;
+    crate::graph::recalculate_module_line_stats(&mut graph);
+
+    // Step 5.5: 文件级导入解析（别名/相对导入 → graph.files 的具体键），见 resolve_file_imports；
+    // 别名表只在项目根目录读一次，和 manifest_hints/go_module_path 是同一套思路
+    let alias_map = load_alias_map(Path::new(&graph.project.root));
+    resolve_file_imports(&mut graph, &alias_map);
+
+    // Step 5.6: C/C++ #include 解析补充一轮（上一步覆盖不到的用 search path 再找一遍），
+    // 找不到的记诊断，见 resolve_c_includes
+    let root_path = PathBuf::from(&graph.project.root);
+    let c_search_paths = default_c_search_paths(&root_path);
+    graph.include_diagnostics = resolve_c_includes(&mut graph, &root_path, &c_search_paths);
+
+    // Step 5.7: 调用边解析——callee 是否命中已知函数，见 resolve_calls
+    resolve_calls(&mut graph);
 
     // Step 6: 构建 summary
     graph.summary.total_files = file_infos.len() as u32;
     graph.summary.total_functions = total_functions;
     graph.summary.total_classes = total_classes;
+    graph.summary.total_code_lines = total_code_lines;
+    graph.summary.total_comment_lines = total_comment_lines;
+    graph.summary.total_blank_lines = total_blank_lines;
+    graph.summary.total_parse_diagnostics = total_parse_diagnostics;
     graph.summary.languages = language_counts.clone();
     let mut mod_list: Vec<String> = module_set.into_iter().collect();
     mod_list.sort();
@@ -314,20 +615,148 @@ pub fn scan_project(root_dir: &Path, exclude: &[String]) -> anyhow::Result<CodeG
         .map(|(p, _)| p.clone())
         .collect();
     graph.summary.entry_points.sort();
+    graph.summary.complexity_hotspots =
+        crate::graph::top_complexity_hotspots(&graph.files, crate::graph::COMPLEXITY_HOTSPOT_LIMIT);
+    graph.summary.circular_dependencies = crate::impact::detect_cycles(&graph);
     graph.config.languages = {
         let mut langs: Vec<String> = language_counts.into_keys().collect();
         langs.sort();
         langs
     };
+    graph.config.exclude_patterns = filter.exclude.clone();
+    graph.config.include_patterns = filter.include.clone();
 
-    Ok(graph)
+    sink.emit(crate::progress::ScanEvent::Summary {
+        total_files: graph.summary.total_files,
+        total_functions: graph.summary.total_functions,
+        total_modules: graph.summary.modules.len() as u32,
+    });
+
+    graph
+}
+
+/// 收集语法树中所有注释节点的字节范围（kind() 以 "comment" 结尾）
+fn collect_comment_ranges(tree: &tree_sitter::Tree) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if node.kind().ends_with("comment") {
+            ranges.push((node.start_byte(), node.end_byte()));
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            stack.push(child);
+        }
+    }
+    ranges
+}
+
+/// 将每一行分类为代码/注释/空行（code_lines, comment_lines, blank_lines）
+///
+/// 规则：去除首尾空白后为空 → 空行；非空白字符的字节跨度完整落在某个注释节点内 → 注释行
+/// （多行块注释中间的行即使没有注释符号也算注释）；其余情况（含"代码 + 行尾注释"的混合行）一律算代码行。
+///
+/// 这套规则靠 tree-sitter 语法树识别注释节点，天然覆盖了块注释嵌套（`/* /* */ */`
+/// 在语法层面就是一个注释节点）和逐语言的单行/块注释语法——不需要每个语言适配器
+/// 再手写一份注释前缀表和括号深度计数器去重新实现同一件事。
+pub(crate) fn classify_lines(tree: &tree_sitter::Tree, source: &[u8]) -> (u32, u32, u32) {
+    let comment_ranges = collect_comment_ranges(tree);
+    let text = String::from_utf8_lossy(source);
+
+    let mut code_lines = 0u32;
+    let mut comment_lines = 0u32;
+    let mut blank_lines = 0u32;
+
+    let mut byte_offset = 0usize;
+    // 文件末尾换行符之后的空串不算一行
+    for line in text.split_terminator('\n') {
+        let line_start = byte_offset;
+        byte_offset += line.len() + 1;
+
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            blank_lines += 1;
+            continue;
+        }
+
+        let leading_ws = line.len() - line.trim_start().len();
+        let trailing_ws = line.len() - line.trim_end().len();
+        let span_start = line_start + leading_ws;
+        let span_end = line_start + line.len() - trailing_ws;
+
+        let fully_in_comment = comment_ranges
+            .iter()
+            .any(|&(cs, ce)| cs <= span_start && span_end <= ce);
+
+        if fully_in_comment {
+            comment_lines += 1;
+        } else {
+            code_lines += 1;
+        }
+    }
+
+    (code_lines, comment_lines, blank_lines)
+}
+
+/// 单行化并截断节点文本，只供诊断展示用（e.g. 一个 `ERROR` 节点可能横跨几十行）
+const PARSE_DIAGNOSTIC_SNIPPET_MAX_LEN: usize = 60;
+
+fn snippet_for(node: tree_sitter::Node, source: &[u8]) -> String {
+    let text = languages::node_text(node, source).replace(['\n', '\r'], " ");
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() > PARSE_DIAGNOSTIC_SNIPPET_MAX_LEN {
+        let truncated: String = collapsed.chars().take(PARSE_DIAGNOSTIC_SNIPPET_MAX_LEN).collect();
+        format!("{truncated}…")
+    } else {
+        collapsed
+    }
+}
+
+/// 扫描语法树里所有 `ERROR`/`MISSING` 节点，作为这份源码语法有问题的诊断信息
+///
+/// `node.is_error()` 对应解析器完全认不出的一段语法（tree-sitter 产出一个通用的
+/// `ERROR` 节点兜底），`node.is_missing()` 对应解析器能认出期望的结构、但缺了某个
+/// 必须的子节点（比如少了一个右括号，解析器会插入一个缺省的空节点占位）。两者都代表
+/// 源码本身有语法错误，而不是适配器抽取逻辑的问题——即使产出了树，也只是"尽力而为"。
+pub(crate) fn collect_parse_diagnostics(
+    tree: &tree_sitter::Tree,
+    source: &[u8],
+) -> Vec<crate::graph::ParseDiagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if node.is_missing() {
+            let pos = node.start_position();
+            diagnostics.push(crate::graph::ParseDiagnostic {
+                line: pos.row as u32,
+                column: pos.column as u32,
+                kind: crate::graph::ParseDiagnosticKind::Missing,
+                snippet: snippet_for(node, source),
+            });
+        } else if node.is_error() {
+            let pos = node.start_position();
+            diagnostics.push(crate::graph::ParseDiagnostic {
+                line: pos.row as u32,
+                column: pos.column as u32,
+                kind: crate::graph::ParseDiagnosticKind::Error,
+                snippet: snippet_for(node, source),
+            });
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            stack.push(child);
+        }
+    }
+    diagnostics.sort_by_key(|d| (d.line, d.column));
+    diagnostics
 }
 
 /// 解析相对导入，返回目标模块名
 ///
-/// 注意：当前仅支持 JS/TS 的相对路径导入（以 `.` 开头）。
-/// Go/Rust/Java/C/C++ 的 import 不以 `.` 开头，会被标记为 external 并跳过，
-/// 因此这些语言的模块间依赖关系暂不解析。
+/// 只处理以 `.` 开头的相对路径写法。调用处只把它当作 JS/TS/Python/Rust/Go/Java
+/// 都没有专属 `differ::ImportResolver` 时的兜底（目前即 C/C++ 的 `#include "..."`）；
+/// 这些语言的 import 语法有专门的解析器（见 `differ::resolver_for_language`），
+/// 不会走到这里。
 fn resolve_import_module(
     importer_path: &Path,
     import_source: &str,
@@ -358,10 +787,400 @@ fn resolve_import_module(
     None
 }
 
+/// 扩展名重试列表，用于 [`resolve_file_imports`] 把一条相对导入补全成 `graph.files`
+/// 里实际存在的键：直接匹配失败后依次尝试追加这些扩展名，再尝试 `/index.{ext}`
+const RESOLVABLE_EXTENSIONS: &[&str] = &[
+    "ts", "tsx", "js", "jsx", "mjs", "cjs", "py", "go", "rs", "java", "c", "h", "cpp", "cc", "cxx", "hpp", "hh",
+];
+
+/// 导入解析涉及的语言是否用“带引号的相对路径”风格写 import（目前只有 C/C++ 的
+/// `#include "engine.h"`）——这类写法没有 `./`/`../` 前缀，因此不能靠前缀判断是否相对导入，
+/// 只能靠语言本身区分：C/C++ 没有裸的“包名”导入这一说，非尖括号 include 总是路径式的
+fn uses_unprefixed_relative_imports(language: &str) -> bool {
+    matches!(language, "c" | "cpp")
+}
+
+/// 把一个归一化后的候选路径解析成 `graph.files` 里实际存在的键
+///
+/// 像 Deno 的模块解析器一样分三步尝试：直接匹配 → 补扩展名 → `/index.{ext}`，
+/// 全部失败说明这条导入指向的文件不在扫描范围内（或者压根只是目录/包名），
+/// 留给调用方当作未解析处理。供相对导入（[`resolve_relative_import`]）和别名导入
+/// （[`resolve_file_imports`] 里 `AliasMap` 命中之后）共用。
+fn resolve_candidate_path(candidate: &str, files: &HashMap<String, FileEntry>) -> Option<String> {
+    if files.contains_key(candidate) {
+        return Some(candidate.to_string());
+    }
+    for ext in RESOLVABLE_EXTENSIONS {
+        let with_ext = format!("{}.{}", candidate, ext);
+        if files.contains_key(&with_ext) {
+            return Some(with_ext);
+        }
+    }
+    for ext in RESOLVABLE_EXTENSIONS {
+        let index_path = format!("{}/index.{}", candidate, ext);
+        if files.contains_key(&index_path) {
+            return Some(index_path);
+        }
+    }
+    None
+}
+
+/// 把一条相对导入的 `source` 解析成 `graph.files` 里实际存在的键（见 [`resolve_candidate_path`]）
+fn resolve_relative_import(importer_rel: &str, source: &str, files: &HashMap<String, FileEntry>) -> Option<String> {
+    let base = crate::path_utils::posix_dirname(importer_rel);
+    let candidate = crate::path_utils::posix_normalize(&format!("{}/{}", base, source));
+    resolve_candidate_path(&candidate, files)
+}
+
+/// 路径别名 / import map：把 `@app/auth/login` 这类配置式的导入前缀改写成项目内的
+/// 真实路径，再按相对导入同样的规则去匹配 `graph.files`。
+///
+/// 按 key 的字符串长度从长到短排序存放，[`AliasMap::resolve`] 第一个命中的 key
+/// 就是最长前缀，不需要额外再比较。支持两种 key 形态：
+/// - 精确 key（`"react"`）：import source 与 key 完全相等才命中，整体替换成 target；
+/// - 通配 key（`"@app/*"`，tsconfig `paths`/Deno import map 的写法）：import source 必须
+///   以 `key` 去掉 `/*` 后的前缀 + `/` 开头，把这之后的部分原样拼到 target 目录后面。
+#[derive(Debug, Clone, Default)]
+pub struct AliasMap {
+    entries: Vec<(String, String)>,
+}
+
+impl AliasMap {
+    pub fn from_entries(mut entries: Vec<(String, String)>) -> Self {
+        entries.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+        Self { entries }
+    }
+
+    /// 按最长前缀匹配把 `source` 改写成目标路径；没有任何别名命中时返回 `None`，
+    /// 调用方应回退到普通的相对导入解析。
+    pub fn resolve(&self, source: &str) -> Option<String> {
+        for (key, target) in &self.entries {
+            if let Some(prefix) = key.strip_suffix("/*") {
+                if let Some(rest) = source.strip_prefix(prefix).and_then(|r| r.strip_prefix('/')) {
+                    let target_dir = target.strip_suffix("/*").unwrap_or(target);
+                    return Some(format!("{}/{}", target_dir, rest));
+                }
+            } else if source == key {
+                return Some(target.clone());
+            }
+        }
+        None
+    }
+}
+
+/// 在项目根目录下寻找一份简单的 JSON 别名表并解析成 [`AliasMap`]
+///
+/// 兼容两种写法：裸的 `{"key": "target"}` / `{"key": ["target", ...]}`（只取第一个候选，
+/// 和 tsconfig `compilerOptions.paths` 一样允许多个候选目标），以及 Deno `import_map.json`
+/// 外层再包一层 `{"imports": {...}}`。文件不存在、不是合法 JSON、或者两种形状都不匹配时
+/// 静默返回空表——等同于项目没有配置别名，不影响其余的相对导入解析。
+pub fn load_alias_map(root_dir: &Path) -> AliasMap {
+    let Ok(content) = std::fs::read_to_string(root_dir.join("import_map.json")) else {
+        return AliasMap::default();
+    };
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return AliasMap::default();
+    };
+    let table = value.get("imports").unwrap_or(&value);
+    let Some(obj) = table.as_object() else {
+        return AliasMap::default();
+    };
+    let entries = obj
+        .iter()
+        .filter_map(|(key, val)| {
+            let target = match val {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Array(arr) => arr.first()?.as_str()?.to_string(),
+                _ => return None,
+            };
+            Some((key.clone(), target))
+        })
+        .collect();
+    AliasMap::from_entries(entries)
+}
+
+/// 把 `graph.files` 里每个文件的相对导入解析到具体的文件键，填充
+/// `FileEntry.resolved_imports`/`imported_by`（类 Deno 模块解析，见 [`resolve_relative_import`]）
+///
+/// 先按 `alias_map` 做最长前缀匹配改写（tsconfig `paths`/import map 风格的别名导入），
+/// 改写成功就归一化路径直接去匹配 `graph.files`；没有别名命中，再回退到只处理以 `.`/`..`
+/// 开头的相对导入，以及 C/C++ 不带尖括号的 `#include "..."`（这类写法没有 `./` 前缀，靠
+/// [`uses_unprefixed_relative_imports`] 按语言识别）。裸导入/包导入（`react`、`<vector>`）
+/// 两条路径都解析不到文件，留在 `resolved_imports` 之外——粗粒度的模块级
+/// `depends_on`/`depended_by` 已经覆盖了这部分。这一步在 `graph.files` 完整落定之后才能跑，
+/// 因此既在 `scan_project_with_progress` 里跑一遍，也在 `differ::merge_graph_update`
+/// 里对合并后的全量 `graph.files` 重跑一遍，保证增量更新后结果同样准确。
+pub fn resolve_file_imports(graph: &mut CodeGraph, alias_map: &AliasMap) {
+    let mut resolved_by_file: HashMap<String, Vec<(String, String)>> = HashMap::new();
+    let mut imported_by_map: HashMap<String, HashSet<String>> = HashMap::new();
+
+    for (rel_path, entry) in &graph.files {
+        let mut resolved = Vec::new();
+        for imp in &entry.imports {
+            let target = if let Some(aliased) = alias_map.resolve(&imp.source) {
+                let candidate = crate::path_utils::strip_extension(&crate::path_utils::posix_normalize(&aliased));
+                resolve_candidate_path(&candidate, &graph.files)
+            } else {
+                let is_relative = imp.source.starts_with('.') || uses_unprefixed_relative_imports(&entry.language);
+                if is_relative {
+                    resolve_relative_import(rel_path, &imp.source, &graph.files)
+                } else {
+                    None
+                }
+            };
+            if let Some(target) = target {
+                if target != *rel_path {
+                    imported_by_map.entry(target.clone()).or_default().insert(rel_path.clone());
+                    resolved.push((imp.source.clone(), target));
+                }
+            }
+        }
+        if !resolved.is_empty() {
+            resolved_by_file.insert(rel_path.clone(), resolved);
+        }
+    }
+
+    for (rel_path, entry) in graph.files.iter_mut() {
+        entry.resolved_imports = resolved_by_file.remove(rel_path).unwrap_or_default();
+        let mut importers: Vec<String> = imported_by_map
+            .remove(rel_path)
+            .map(|s| s.into_iter().collect())
+            .unwrap_or_default();
+        importers.sort();
+        entry.imported_by = importers;
+    }
+
+    resolve_file_reexports(graph, alias_map);
+}
+
+/// 把 `graph.files` 里每个文件的 barrel re-export（`ReexportInfo::source`）解析到
+/// 具体的文件键，填充 `FileEntry.resolved_reexports`；解析规则与 [`resolve_file_imports`]
+/// 完全一致（同一套别名表/相对路径解析），只是作用对象是 `reexports` 而不是 `imports`。
+/// 解析不到具体文件的 re-export（裸包名，理论上 barrel 写法极少见）直接丢弃——
+/// [`crate::slicer`] 的折叠阶段本来就只能处理能定位到文件的 re-export。
+fn resolve_file_reexports(graph: &mut CodeGraph, alias_map: &AliasMap) {
+    let mut resolved_by_file: HashMap<String, Vec<crate::graph::ResolvedReexport>> = HashMap::new();
+
+    for (rel_path, entry) in &graph.files {
+        if entry.reexports.is_empty() {
+            continue;
+        }
+        let mut resolved = Vec::new();
+        for rx in &entry.reexports {
+            let target = if let Some(aliased) = alias_map.resolve(&rx.source) {
+                let candidate = crate::path_utils::strip_extension(&crate::path_utils::posix_normalize(&aliased));
+                resolve_candidate_path(&candidate, &graph.files)
+            } else if rx.source.starts_with('.') {
+                resolve_relative_import(rel_path, &rx.source, &graph.files)
+            } else {
+                None
+            };
+            if let Some(target_file) = target {
+                if target_file != *rel_path {
+                    resolved.push(crate::graph::ResolvedReexport {
+                        name: rx.name.clone(),
+                        target_file,
+                        star: rx.star,
+                    });
+                }
+            }
+        }
+        if !resolved.is_empty() {
+            resolved_by_file.insert(rel_path.clone(), resolved);
+        }
+    }
+
+    for (rel_path, entry) in graph.files.iter_mut() {
+        entry.resolved_reexports = resolved_by_file.remove(rel_path).unwrap_or_default();
+    }
+}
+
+/// 常见的 C/C++ 头文件搜索目录约定（类似编译器 `-I` 选项，镜像 rust-analyzer 加载
+/// project/sysroot 时"按约定找，不强求一定存在"的做法）：只有真实存在于项目根目录下
+/// 的才会被采用，找不到就安静地跳过，不影响其余搜索路径。
+const CONVENTIONAL_C_INCLUDE_DIRS: &[&str] = &["include", "inc", "src"];
+
+pub fn default_c_search_paths(root_dir: &Path) -> Vec<String> {
+    CONVENTIONAL_C_INCLUDE_DIRS
+        .iter()
+        .filter(|dir| root_dir.join(dir).is_dir())
+        .map(|dir| dir.to_string())
+        .collect()
+}
+
+/// 直接在树上找 `preproc_include` 节点，返回 `(去掉引号/尖括号的路径, 是否系统头, 1-based 行号)`
+///
+/// 独立于 `languages::c_lang::extract_c_includes`：后者产出的 `languages::ImportInfo`
+/// 不带行号，而诊断信息需要行号，所以这里单独走一遍同样的节点匹配逻辑。
+fn scan_raw_c_includes(tree: &tree_sitter::Tree, source: &[u8]) -> Vec<(String, bool, u32)> {
+    let mut includes = Vec::new();
+    languages::walk_nodes(tree.root_node(), &mut |node: tree_sitter::Node| {
+        if node.kind() != "preproc_include" {
+            return;
+        }
+        let path_node = languages::find_child_of_type(node, "system_lib_string")
+            .or_else(|| languages::find_child_of_type(node, "string_literal"));
+        let Some(path_n) = path_node else { return };
+        let is_system = path_n.kind() == "system_lib_string";
+        let raw = languages::node_text(path_n, source)
+            .trim_matches(|c| c == '<' || c == '>' || c == '"')
+            .to_string();
+        includes.push((raw, is_system, node.start_position().row as u32 + 1));
+    });
+    includes
+}
+
+/// 在 [`resolve_file_imports`] 之后补一轮 C/C++ `#include` 解析：相对于 including 文件
+/// 目录的写法已经由 `resolve_file_imports`（借助 [`uses_unprefixed_relative_imports`]）
+/// 处理过了，这里只处理那之后仍未解析的 include——依次尝试每个 `search_paths`
+/// （镜像 rust-analyzer 加载 project/sysroot 时配置搜索路径的做法），找到就和相对导入
+/// 一样补进 `resolved_imports`/`imported_by`；都找不到则记一条诊断：引号包含（用户头文件）
+/// 算错误，尖括号包含（系统头）只是提示。
+pub fn resolve_c_includes(graph: &mut CodeGraph, root_dir: &Path, search_paths: &[String]) -> Vec<crate::graph::IncludeDiagnostic> {
+    use crate::graph::{IncludeDiagnostic, IncludeSeverity};
+
+    let mut rel_paths: Vec<String> = graph
+        .files
+        .iter()
+        .filter(|(_, entry)| matches!(entry.language.as_str(), "c" | "cpp"))
+        .map(|(rel_path, _)| rel_path.clone())
+        .collect();
+    rel_paths.sort();
+
+    // `has_cpp_source_files` 要回答的是"整个项目里有没有 C++ 源文件"，不是"这一个文件
+    // 是不是 C++"——所以要喂全部 C/C++ 文件的绝对路径，而不是每次循环内重新拼一个
+    // 只有当前文件的单元素切片（那样永远只会在当前文件本身带 .cpp/.cc 等后缀时才
+    // 返回 true，导致 .h/.c 文件在这里判定为 C，却在 `scan_project`/`scan_project_incremental`
+    // 里因为看到了全量文件列表而判定为 C++，同一个文件两条路径给出不一致的 effective_language）
+    let project_abs_paths: Vec<PathBuf> = rel_paths
+        .iter()
+        .map(|rel_path| root_dir.join(rel_path.replace('/', std::path::MAIN_SEPARATOR_STR)))
+        .collect();
+    let project_has_cpp = has_cpp_source_files(&project_abs_paths);
+
+    let mut diagnostics = Vec::new();
+    let mut newly_resolved: HashMap<String, Vec<(String, String)>> = HashMap::new();
+    let mut newly_imported_by: HashMap<String, HashSet<String>> = HashMap::new();
+
+    for rel_path in &rel_paths {
+        let abs_path = root_dir.join(rel_path.replace('/', std::path::MAIN_SEPARATOR_STR));
+        let Ok(content) = std::fs::read(&abs_path) else { continue };
+        let Some(base_lang) = detect_language(&abs_path) else { continue };
+        let lang = effective_language(&abs_path, base_lang, project_has_cpp);
+        let adapter = languages::get_adapter(lang);
+        let mut parser = tree_sitter::Parser::new();
+        if parser.set_language(&adapter.language()).is_err() {
+            continue;
+        }
+        let Some(tree) = parser.parse(&content, None) else { continue };
+
+        let entry = &graph.files[rel_path];
+        for (source, is_system, line) in scan_raw_c_includes(&tree, &content) {
+            let already_resolved = entry.resolved_imports.iter().any(|(s, _)| s == &source);
+            if already_resolved {
+                continue;
+            }
+
+            let target = search_paths.iter().find_map(|search_path| {
+                let candidate = normalize_path(&PathBuf::from(format!("{}/{}", search_path, source)));
+                resolve_candidate_path(&candidate, &graph.files)
+            });
+
+            match target {
+                Some(target) if target != *rel_path => {
+                    newly_imported_by.entry(target.clone()).or_default().insert(rel_path.clone());
+                    newly_resolved.entry(rel_path.clone()).or_default().push((source, target));
+                }
+                _ => {
+                    diagnostics.push(IncludeDiagnostic {
+                        path: source,
+                        including_file: rel_path.clone(),
+                        line,
+                        severity: if is_system { IncludeSeverity::Info } else { IncludeSeverity::Error },
+                    });
+                }
+            }
+        }
+    }
+
+    for (rel_path, mut additions) in newly_resolved {
+        if let Some(entry) = graph.files.get_mut(&rel_path) {
+            entry.resolved_imports.append(&mut additions);
+        }
+    }
+    for (target, importers) in newly_imported_by {
+        if let Some(entry) = graph.files.get_mut(&target) {
+            let mut merged: HashSet<String> = entry.imported_by.drain(..).collect();
+            merged.extend(importers);
+            let mut merged: Vec<String> = merged.into_iter().collect();
+            merged.sort();
+            entry.imported_by = merged;
+        }
+    }
+
+    diagnostics.sort_by(|a, b| (&a.including_file, a.line).cmp(&(&b.including_file, b.line)));
+    diagnostics
+}
+
+/// 在 [`resolve_file_imports`]（以及 C/C++ 的 [`resolve_c_includes`]）跑完、每个文件的
+/// `resolved_imports` 都已经可用之后执行：把每条 `CallInfo.callee` 和已知函数名对一遍，
+/// 匹配上了（同文件内，或者 `resolved_imports` 指向的某个文件里）就标 `resolved = true`。
+/// 和 `resolve_file_imports` 一样是装配阶段末尾的一次全量重算，不是 per-file 增量。
+pub fn resolve_calls(graph: &mut CodeGraph) {
+    let local_functions: HashMap<String, HashSet<String>> = graph
+        .files
+        .iter()
+        .map(|(rel_path, entry)| (rel_path.clone(), entry.functions.iter().map(|f| f.name.clone()).collect()))
+        .collect();
+
+    let rel_paths: Vec<String> = graph.files.keys().cloned().collect();
+    for rel_path in rel_paths {
+        let resolved_imports = graph.files[&rel_path].resolved_imports.clone();
+        let mut known: HashSet<String> = local_functions.get(&rel_path).cloned().unwrap_or_default();
+        for (_, target) in &resolved_imports {
+            if let Some(fns) = local_functions.get(target) {
+                known.extend(fns.iter().cloned());
+            }
+        }
+        if let Some(entry) = graph.files.get_mut(&rel_path) {
+            for call in entry.calls.iter_mut() {
+                call.resolved = known.contains(&call.callee);
+            }
+        }
+    }
+}
+
 /// 扫描并保存到 .codemap/ 目录
+///
+/// 若 `.codemap/graph.json` 已存在，说明这不是首次扫描，改走
+/// `differ::update_graph_incremental`：按 mtime+size 快筛、哈希定案，只重新解析
+/// 真正变化的文件，未变的 `FileEntry` 原样沿用，模块依赖从合并后的文件集合重建——
+/// 在大仓库、大多数文件未变的情况下，耗时趋近于哈希全量文件的成本，结果应与
+/// 全量扫描完全一致。否则（目录下还没有图谱）退回一次完整的 `scan_project`。
 pub fn scan_and_save(root_dir: &Path, exclude: &[String]) -> anyhow::Result<CodeGraph> {
+    let output_dir = root_dir.join(".codemap");
+    if output_dir.join("graph.json").exists() {
+        let (graph, _changes) = crate::differ::update_graph_incremental(root_dir, exclude)?;
+        return Ok(graph);
+    }
+
     let graph = scan_project(root_dir, exclude)?;
+    save_graph(&output_dir, &graph)?;
+    Ok(graph)
+}
+
+/// `scan_and_save` 的 include 白名单版本：首次扫描（`.codemap/graph.json` 不存在）
+/// 走 `scan_project_with_filter`，完整支持 include/exclude；已有图谱时退回
+/// `update_graph_incremental`，它目前只认 `filter.exclude`——增量路径的 include
+/// 支持留给后续迭代
+pub fn scan_and_save_with_filter(root_dir: &Path, filter: &crate::traverser::ScanFilter) -> anyhow::Result<CodeGraph> {
     let output_dir = root_dir.join(".codemap");
+    if output_dir.join("graph.json").exists() {
+        let (graph, _changes) = crate::differ::update_graph_incremental(root_dir, &filter.exclude)?;
+        return Ok(graph);
+    }
+
+    let graph = scan_project_with_filter(root_dir, filter)?;
     save_graph(&output_dir, &graph)?;
     Ok(graph)
 }
@@ -408,8 +1227,17 @@ mod tests {
                 name: "greet".to_string(),
                 start_line: 1,
                 end_line: 3,
-                params: vec!["name".to_string(), "age".to_string()],
+                params: vec![
+                    crate::languages::ParamInfo::simple("name"),
+                    crate::languages::ParamInfo::simple("age"),
+                ],
                 is_exported: true,
+                complexity: 1,
+                return_type: None,
+                type_parameters: None,
+                metrics: crate::languages::SymbolMetrics::default(),
+                decorators: vec![],
+                doc: None,
             },
             crate::languages::FunctionInfo {
                 name: "noop".to_string(),
@@ -417,6 +1245,12 @@ mod tests {
                 end_line: 6,
                 params: vec![],
                 is_exported: false,
+                complexity: 1,
+                return_type: None,
+                type_parameters: None,
+                metrics: crate::languages::SymbolMetrics::default(),
+                decorators: vec![],
+                doc: None,
             },
         ];
         let result = convert_functions(&lang_fns);
@@ -434,6 +1268,9 @@ mod tests {
                 end_line: 10,
                 methods: vec![],
                 kind: "class".to_string(),
+                metrics: crate::languages::SymbolMetrics::default(),
+                decorators: vec![],
+                doc: None,
             },
             crate::languages::ClassInfo {
                 name: "MyTrait".to_string(),
@@ -441,6 +1278,9 @@ mod tests {
                 end_line: 20,
                 methods: vec![],
                 kind: "trait".to_string(),
+                metrics: crate::languages::SymbolMetrics::default(),
+                decorators: vec![],
+                doc: None,
             },
             crate::languages::ClassInfo {
                 name: "MyStruct".to_string(),
@@ -448,6 +1288,9 @@ mod tests {
                 end_line: 30,
                 methods: vec![],
                 kind: "struct".to_string(),
+                metrics: crate::languages::SymbolMetrics::default(),
+                decorators: vec![],
+                doc: None,
             },
         ];
         let classes = convert_classes(&lang_classes);
@@ -467,6 +1310,9 @@ mod tests {
                 end_line: 10,
                 methods: vec![],
                 kind: "class".to_string(),
+                metrics: crate::languages::SymbolMetrics::default(),
+                decorators: vec![],
+                doc: None,
             },
             crate::languages::ClassInfo {
                 name: "MyEnum".to_string(),
@@ -474,6 +1320,9 @@ mod tests {
                 end_line: 20,
                 methods: vec![],
                 kind: "enum".to_string(),
+                metrics: crate::languages::SymbolMetrics::default(),
+                decorators: vec![],
+                doc: None,
             },
         ];
         // Python: class 不进入 types
@@ -493,15 +1342,487 @@ mod tests {
                 source: "./utils".to_string(),
                 names: vec!["helper".to_string()],
                 is_default: false,
+                dynamic: false,
             },
             crate::languages::ImportInfo {
                 source: "react".to_string(),
                 names: vec!["useState".to_string()],
                 is_default: false,
+                dynamic: false,
             },
         ];
-        let imports = convert_imports(&lang_imports);
+        let imports = convert_imports(&lang_imports, Language::TypeScript, None);
         assert_eq!(imports[0].is_external, false);
         assert_eq!(imports[1].is_external, true);
     }
+
+    #[test]
+    fn test_convert_imports_go_classifies_by_module_path() {
+        let lang_imports = vec![
+            crate::languages::ImportInfo {
+                source: "fmt".to_string(),
+                names: vec!["fmt".to_string()],
+                is_default: false,
+                dynamic: false,
+            },
+            crate::languages::ImportInfo {
+                source: "example.com/app/internal/util".to_string(),
+                names: vec!["util".to_string()],
+                is_default: false,
+                dynamic: false,
+            },
+            crate::languages::ImportInfo {
+                source: "github.com/foo/bar".to_string(),
+                names: vec!["bar".to_string()],
+                is_default: false,
+                dynamic: false,
+            },
+        ];
+        let imports = convert_imports(&lang_imports, Language::Go, Some("example.com/app"));
+        assert_eq!(imports[0].is_external, true);
+        assert_eq!(imports[1].is_external, false);
+        assert_eq!(imports[2].is_external, true);
+    }
+
+    #[test]
+    fn test_convert_imports_rust_classifies_crate_relative_as_internal() {
+        let lang_imports = vec![
+            crate::languages::ImportInfo {
+                source: "crate::utils".to_string(),
+                names: vec!["helper".to_string()],
+                is_default: false,
+                dynamic: false,
+            },
+            crate::languages::ImportInfo {
+                source: "super::sibling".to_string(),
+                names: vec!["thing".to_string()],
+                is_default: false,
+                dynamic: false,
+            },
+            crate::languages::ImportInfo {
+                source: "std::io".to_string(),
+                names: vec!["Read".to_string()],
+                is_default: false,
+                dynamic: false,
+            },
+        ];
+        let imports = convert_imports(&lang_imports, Language::Rust, None);
+        assert_eq!(imports[0].is_external, false);
+        assert_eq!(imports[1].is_external, false);
+        assert_eq!(imports[2].is_external, true);
+    }
+
+    fn parse_rust(source: &str) -> tree_sitter::Tree {
+        let adapter = crate::languages::get_adapter(Language::Rust);
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&adapter.language()).unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    #[test]
+    fn test_classify_lines_basic() {
+        let src = "// full comment line\nfn main() {\n\n    let x = 1; // trailing comment\n}\n";
+        let tree = parse_rust(src);
+        let (code, comment, blank) = classify_lines(&tree, src.as_bytes());
+        assert_eq!(comment, 1);
+        assert_eq!(blank, 1);
+        assert_eq!(code, 3); // fn main() {, let x = 1; // ..., }
+    }
+
+    #[test]
+    fn test_classify_lines_block_comment() {
+        let src = "/*\n * still comment\n */\nfn f() {}\n";
+        let tree = parse_rust(src);
+        let (code, comment, blank) = classify_lines(&tree, src.as_bytes());
+        assert_eq!(comment, 3);
+        assert_eq!(code, 1);
+        assert_eq!(blank, 0);
+    }
+
+    // ── resolve_file_imports ──────────────────────────────────────────────────
+
+    fn make_entry(language: &str, imports: Vec<(&str, bool)>) -> FileEntry {
+        FileEntry {
+            language: language.to_string(),
+            module: "_root".to_string(),
+            hash: "sha256:x".to_string(),
+            lines: 1,
+            code_lines: 1,
+            comment_lines: 0,
+            blank_lines: 0,
+            functions: vec![],
+            classes: vec![],
+            types: vec![],
+            imports: imports
+                .into_iter()
+                .map(|(source, is_external)| crate::graph::ImportInfo {
+                    source: source.to_string(),
+                    symbols: vec![],
+                    is_external,
+                    dynamic: false,
+                })
+                .collect(),
+            exports: vec![],
+            reexports: vec![],
+            resolved_reexports: vec![],
+            calls: vec![],
+            is_entry_point: false,
+            entry_point_reason: None,
+            resolved_imports: vec![],
+            imported_by: vec![],
+            parse_diagnostics: vec![],
+        }
+    }
+
+    #[test]
+    fn test_resolve_file_imports_matches_sibling_with_extension() {
+        let mut graph = crate::graph::create_empty_graph("test", "/tmp/test");
+        graph.files.insert("src/auth/login.ts".to_string(), make_entry("typescript", vec![("./session", false)]));
+        graph.files.insert("src/auth/session.ts".to_string(), make_entry("typescript", vec![]));
+
+        resolve_file_imports(&mut graph, &AliasMap::default());
+
+        assert_eq!(
+            graph.files["src/auth/login.ts"].resolved_imports,
+            vec![("./session".to_string(), "src/auth/session.ts".to_string())]
+        );
+        assert_eq!(graph.files["src/auth/session.ts"].imported_by, vec!["src/auth/login.ts".to_string()]);
+    }
+
+    #[test]
+    fn test_resolve_file_imports_matches_directory_index() {
+        let mut graph = crate::graph::create_empty_graph("test", "/tmp/test");
+        graph.files.insert("src/app.ts".to_string(), make_entry("typescript", vec![("./auth", false)]));
+        graph.files.insert("src/auth/index.ts".to_string(), make_entry("typescript", vec![]));
+
+        resolve_file_imports(&mut graph, &AliasMap::default());
+
+        assert_eq!(
+            graph.files["src/app.ts"].resolved_imports,
+            vec![("./auth".to_string(), "src/auth/index.ts".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_resolve_file_imports_leaves_bare_package_imports_unresolved() {
+        let mut graph = crate::graph::create_empty_graph("test", "/tmp/test");
+        graph.files.insert("src/app.ts".to_string(), make_entry("typescript", vec![("react", true)]));
+
+        resolve_file_imports(&mut graph, &AliasMap::default());
+
+        assert!(graph.files["src/app.ts"].resolved_imports.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_file_imports_resolves_quoted_cpp_include_without_dot_prefix() {
+        let mut graph = crate::graph::create_empty_graph("test", "/tmp/test");
+        graph.files.insert("src/main.cpp".to_string(), make_entry("cpp", vec![("engine.h", false)]));
+        graph.files.insert("src/engine.h".to_string(), make_entry("cpp", vec![]));
+
+        resolve_file_imports(&mut graph, &AliasMap::default());
+
+        assert_eq!(
+            graph.files["src/main.cpp"].resolved_imports,
+            vec![("engine.h".to_string(), "src/engine.h".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_resolve_file_imports_cpp_system_include_has_no_matching_file_stays_unresolved() {
+        let mut graph = crate::graph::create_empty_graph("test", "/tmp/test");
+        graph.files.insert("src/main.cpp".to_string(), make_entry("cpp", vec![("stdio.h", true)]));
+
+        resolve_file_imports(&mut graph, &AliasMap::default());
+
+        assert!(graph.files["src/main.cpp"].resolved_imports.is_empty());
+    }
+
+    // ── resolve_c_includes ────────────────────────────────────────────────────
+
+    fn c_include_temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("codemap-c-includes-test-{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_resolve_c_includes_finds_header_via_search_path() {
+        let dir = c_include_temp_dir("search-path");
+        std::fs::create_dir_all(dir.join("include")).unwrap();
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("src/main.cpp"), "#include \"engine.h\"\n").unwrap();
+        std::fs::write(dir.join("include/engine.h"), "").unwrap();
+
+        let mut graph = crate::graph::create_empty_graph("test", dir.to_str().unwrap());
+        graph.files.insert("src/main.cpp".to_string(), make_entry("cpp", vec![]));
+        graph.files.insert("include/engine.h".to_string(), make_entry("cpp", vec![]));
+
+        let diagnostics = resolve_c_includes(&mut graph, &dir, &["include".to_string()]);
+
+        assert!(diagnostics.is_empty());
+        assert_eq!(
+            graph.files["src/main.cpp"].resolved_imports,
+            vec![("engine.h".to_string(), "include/engine.h".to_string())]
+        );
+        assert_eq!(graph.files["include/engine.h"].imported_by, vec!["src/main.cpp".to_string()]);
+    }
+
+    #[test]
+    fn test_resolve_c_includes_unresolved_quoted_include_is_an_error() {
+        let dir = c_include_temp_dir("quoted-unresolved");
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("src/main.cpp"), "#include \"missing.h\"\n").unwrap();
+
+        let mut graph = crate::graph::create_empty_graph("test", dir.to_str().unwrap());
+        graph.files.insert("src/main.cpp".to_string(), make_entry("cpp", vec![]));
+
+        let diagnostics = resolve_c_includes(&mut graph, &dir, &[]);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].path, "missing.h");
+        assert_eq!(diagnostics[0].including_file, "src/main.cpp");
+        assert_eq!(diagnostics[0].severity, crate::graph::IncludeSeverity::Error);
+    }
+
+    #[test]
+    fn test_resolve_c_includes_unresolved_system_include_is_informational() {
+        let dir = c_include_temp_dir("system-unresolved");
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("src/main.cpp"), "#include <stdio.h>\n").unwrap();
+
+        let mut graph = crate::graph::create_empty_graph("test", dir.to_str().unwrap());
+        graph.files.insert("src/main.cpp".to_string(), make_entry("cpp", vec![]));
+
+        let diagnostics = resolve_c_includes(&mut graph, &dir, &[]);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].path, "stdio.h");
+        assert_eq!(diagnostics[0].severity, crate::graph::IncludeSeverity::Info);
+    }
+
+    #[test]
+    fn test_default_c_search_paths_only_includes_existing_conventional_dirs() {
+        let dir = c_include_temp_dir("search-path-discovery");
+        std::fs::create_dir_all(dir.join("include")).unwrap();
+
+        let search_paths = default_c_search_paths(&dir);
+
+        assert_eq!(search_paths, vec!["include".to_string()]);
+    }
+
+    // ── resolve_calls ─────────────────────────────────────────────────────────
+
+    fn make_function(name: &str) -> crate::graph::FunctionInfo {
+        crate::graph::FunctionInfo {
+            name: name.to_string(),
+            signature: format!("{}()", name),
+            start_line: 1,
+            end_line: 2,
+            complexity: 1,
+        }
+    }
+
+    fn make_call(caller: &str, callee: &str) -> crate::graph::CallInfo {
+        crate::graph::CallInfo {
+            caller: caller.to_string(),
+            callee: callee.to_string(),
+            line: 5,
+            resolved: false,
+        }
+    }
+
+    #[test]
+    fn test_resolve_calls_marks_local_call_as_resolved() {
+        let mut graph = crate::graph::create_empty_graph("test", "/tmp/test");
+        let mut entry = make_entry("typescript", vec![]);
+        entry.functions = vec![make_function("helper")];
+        entry.calls = vec![make_call("main", "helper")];
+        graph.files.insert("src/main.ts".to_string(), entry);
+
+        resolve_calls(&mut graph);
+
+        assert!(graph.files["src/main.ts"].calls[0].resolved);
+    }
+
+    #[test]
+    fn test_resolve_calls_marks_call_into_resolved_import_as_resolved() {
+        let mut graph = crate::graph::create_empty_graph("test", "/tmp/test");
+        let mut main_entry = make_entry("typescript", vec![]);
+        main_entry.calls = vec![make_call("main", "parse")];
+        main_entry.resolved_imports = vec![("./parse".to_string(), "src/parse.ts".to_string())];
+        graph.files.insert("src/main.ts".to_string(), main_entry);
+
+        let mut parse_entry = make_entry("typescript", vec![]);
+        parse_entry.functions = vec![make_function("parse")];
+        graph.files.insert("src/parse.ts".to_string(), parse_entry);
+
+        resolve_calls(&mut graph);
+
+        assert!(graph.files["src/main.ts"].calls[0].resolved);
+    }
+
+    #[test]
+    fn test_resolve_calls_leaves_unknown_callee_unresolved() {
+        let mut graph = crate::graph::create_empty_graph("test", "/tmp/test");
+        let mut entry = make_entry("typescript", vec![]);
+        entry.calls = vec![make_call("main", "mystery")];
+        graph.files.insert("src/main.ts".to_string(), entry);
+
+        resolve_calls(&mut graph);
+
+        assert!(!graph.files["src/main.ts"].calls[0].resolved);
+    }
+
+    // ── AliasMap ────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_alias_map_resolves_exact_key() {
+        let alias_map = AliasMap::from_entries(vec![("react".to_string(), "vendor/react/index.js".to_string())]);
+        assert_eq!(alias_map.resolve("react"), Some("vendor/react/index.js".to_string()));
+        assert_eq!(alias_map.resolve("react-dom"), None);
+    }
+
+    #[test]
+    fn test_alias_map_resolves_wildcard_suffix() {
+        let alias_map = AliasMap::from_entries(vec![("@app/*".to_string(), "src/*".to_string())]);
+        assert_eq!(alias_map.resolve("@app/auth/login"), Some("src/auth/login".to_string()));
+        assert_eq!(alias_map.resolve("@other/auth/login"), None);
+    }
+
+    #[test]
+    fn test_alias_map_longest_prefix_wins() {
+        let alias_map = AliasMap::from_entries(vec![
+            ("@app/*".to_string(), "src/*".to_string()),
+            ("@app/auth/*".to_string(), "src/core/auth/*".to_string()),
+        ]);
+        assert_eq!(alias_map.resolve("@app/auth/login"), Some("src/core/auth/login".to_string()));
+        assert_eq!(alias_map.resolve("@app/utils/helpers"), Some("src/utils/helpers".to_string()));
+    }
+
+    #[test]
+    fn test_load_alias_map_parses_bare_object_form() {
+        let dir = std::env::temp_dir().join("codemap-alias-map-test-bare");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("import_map.json"), r#"{"@app/*": "src/*"}"#).unwrap();
+
+        let alias_map = load_alias_map(&dir);
+        assert_eq!(alias_map.resolve("@app/auth/login"), Some("src/auth/login".to_string()));
+    }
+
+    #[test]
+    fn test_load_alias_map_parses_imports_wrapper_and_array_target() {
+        let dir = std::env::temp_dir().join("codemap-alias-map-test-imports");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("import_map.json"), r#"{"imports": {"~/*": ["src/*", "lib/*"]}}"#).unwrap();
+
+        let alias_map = load_alias_map(&dir);
+        assert_eq!(alias_map.resolve("~/utils/helpers"), Some("src/utils/helpers".to_string()));
+    }
+
+    #[test]
+    fn test_load_alias_map_missing_file_returns_default() {
+        let dir = std::env::temp_dir().join("codemap-alias-map-test-missing");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let alias_map = load_alias_map(&dir);
+        assert_eq!(alias_map.resolve("anything"), None);
+    }
+
+    #[test]
+    fn test_resolve_file_imports_rewrites_aliased_import_before_relative_fallback() {
+        let mut graph = crate::graph::create_empty_graph("test", "/tmp/test");
+        graph.files.insert("src/app.ts".to_string(), make_entry("typescript", vec![("@app/auth/login", true)]));
+        graph.files.insert("src/auth/login.ts".to_string(), make_entry("typescript", vec![]));
+        let alias_map = AliasMap::from_entries(vec![("@app/*".to_string(), "src/*".to_string())]);
+
+        resolve_file_imports(&mut graph, &alias_map);
+
+        assert_eq!(
+            graph.files["src/app.ts"].resolved_imports,
+            vec![("@app/auth/login".to_string(), "src/auth/login.ts".to_string())]
+        );
+        assert_eq!(graph.files["src/auth/login.ts"].imported_by, vec!["src/app.ts".to_string()]);
+    }
+
+    // ── scan_project_incremental ──────────────────────────────────────────────
+
+    fn incremental_temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("codemap-scan-incremental-test-{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_scan_project_incremental_reuses_entry_for_unchanged_hash() {
+        let dir = incremental_temp_dir("unchanged");
+        std::fs::write(dir.join("main.rs"), "fn main() {}\n").unwrap();
+
+        let prev = scan_project(&dir, &[]).unwrap();
+        let next = scan_project_incremental(&dir, &prev, &[]).unwrap();
+
+        assert_eq!(next.files["main.rs"].hash, prev.files["main.rs"].hash);
+        assert_eq!(next.summary.total_files, 1);
+    }
+
+    #[test]
+    fn test_scan_project_incremental_reparses_file_whose_hash_changed() {
+        let dir = incremental_temp_dir("changed");
+        std::fs::write(dir.join("main.rs"), "fn main() {}\n").unwrap();
+        let prev = scan_project(&dir, &[]).unwrap();
+
+        std::fs::write(dir.join("main.rs"), "fn main() {}\nfn added() {}\n").unwrap();
+        let next = scan_project_incremental(&dir, &prev, &[]).unwrap();
+
+        assert_ne!(next.files["main.rs"].hash, prev.files["main.rs"].hash);
+        assert_eq!(next.files["main.rs"].functions.len(), 2);
+    }
+
+    #[test]
+    fn test_scan_project_incremental_drops_deleted_file() {
+        let dir = incremental_temp_dir("deleted");
+        std::fs::write(dir.join("a.rs"), "fn a() {}\n").unwrap();
+        std::fs::write(dir.join("b.rs"), "fn b() {}\n").unwrap();
+        let prev = scan_project(&dir, &[]).unwrap();
+        assert_eq!(prev.summary.total_files, 2);
+
+        std::fs::remove_file(dir.join("b.rs")).unwrap();
+        let next = scan_project_incremental(&dir, &prev, &[]).unwrap();
+
+        assert!(!next.files.contains_key("b.rs"));
+        assert_eq!(next.summary.total_files, 1);
+    }
+
+    #[test]
+    fn test_scan_project_incremental_picks_up_new_file() {
+        let dir = incremental_temp_dir("added");
+        std::fs::write(dir.join("a.rs"), "fn a() {}\n").unwrap();
+        let prev = scan_project(&dir, &[]).unwrap();
+
+        std::fs::write(dir.join("b.rs"), "fn b() {}\n").unwrap();
+        let next = scan_project_incremental(&dir, &prev, &[]).unwrap();
+
+        assert!(next.files.contains_key("b.rs"));
+        assert_eq!(next.summary.total_files, 2);
+    }
+
+    #[test]
+    fn test_scan_project_with_filter_applies_include_whitelist() {
+        let dir = incremental_temp_dir("filter-include");
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::create_dir_all(dir.join("tools")).unwrap();
+        std::fs::write(dir.join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(dir.join("tools/gen.rs"), "fn gen() {}\n").unwrap();
+
+        let filter = crate::traverser::ScanFilter::new(vec!["src/**".to_string()], vec![]);
+        let graph = scan_project_with_filter(&dir, &filter).unwrap();
+
+        assert!(graph.files.contains_key("src/main.rs"));
+        assert!(!graph.files.contains_key("tools/gen.rs"));
+        assert_eq!(graph.config.include_patterns, vec!["src/**".to_string()]);
+    }
 }
@@ -27,6 +27,9 @@ pub struct OverviewModule {
     pub depends_on: Vec<String>,
     #[serde(rename = "dependedBy")]
     pub depended_by: Vec<String>,
+    /// 第三方依赖（`ImportInfo.is_external` 为 true 的 import source），去重排序
+    #[serde(rename = "externalDependencies")]
+    pub external_dependencies: Vec<String>,
     pub stats: ModuleStats,
 }
 
@@ -41,6 +44,10 @@ pub struct Overview {
     pub modules: Vec<OverviewModule>,
     #[serde(rename = "entryPoints")]
     pub entry_points: Vec<String>,
+    /// 模块依赖图里的循环依赖组，见 [`crate::impact::detect_cycles`]。与
+    /// `summary.circularDependencies` 指向同一份数据，提在顶层是为了让只看
+    /// overview 结构、不深入 `summary` 的消费方也能直接发现架构问题
+    pub cycles: Vec<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,15 +62,31 @@ pub struct SliceFile {
     pub exports: Vec<String>,
     #[serde(rename = "isEntryPoint")]
     pub is_entry_point: bool,
+    #[serde(rename = "entryPointReason", skip_serializing_if = "Option::is_none", default)]
+    pub entry_point_reason: Option<String>,
     pub hash: String,
 }
 
+/// 一个通过 barrel re-export 抵达当前模块的符号，标注它真正声明在哪个模块——
+/// `export { login } from '../auth/login'` 或折叠之后的 `export * from './routes'`，
+/// 见 [`resolve_reexports`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReexportedSymbol {
+    pub name: String,
+    #[serde(rename = "fromModule")]
+    pub from_module: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModuleSlice {
     pub module: String,
     pub path: String,
     pub files: Vec<SliceFile>,
     pub exports: Vec<String>,
+    /// 本模块通过 barrel re-export 转手的符号，附带真正的来源模块；与 `exports`
+    /// （本模块文件里本地声明的符号）互不重叠，见 [`resolve_reexports`]
+    #[serde(default)]
+    pub reexports: Vec<ReexportedSymbol>,
     #[serde(rename = "dependsOn")]
     pub depends_on: Vec<String>,
     #[serde(rename = "dependedBy")]
@@ -78,6 +101,8 @@ pub struct DepInfo {
     #[serde(rename = "fileCount")]
     pub file_count: u32,
     pub stats: ModuleStats,
+    /// 距离起始模块的跳数：直接依赖为 1，依赖的依赖为 2，以此类推
+    pub depth: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -104,6 +129,7 @@ pub fn generate_overview(graph: &CodeGraph) -> Overview {
                 exports: dedup_sorted(all_exports),
                 depends_on: mod_data.depends_on.clone(),
                 depended_by: mod_data.depended_by.clone(),
+                external_dependencies: collect_external_dependencies(graph, mod_data),
                 stats,
             }
         })
@@ -116,6 +142,7 @@ pub fn generate_overview(graph: &CodeGraph) -> Overview {
         summary: graph.summary.clone(),
         modules,
         entry_points: graph.summary.entry_points.clone(),
+        cycles: graph.summary.circular_dependencies.clone(),
     }
 }
 
@@ -157,6 +184,7 @@ pub fn build_module_slice(graph: &CodeGraph, mod_name: &str, mod_data: &ModuleEn
                 imports: file_data.imports.clone(),
                 exports: file_data.exports.clone(),
                 is_entry_point: file_data.is_entry_point,
+                entry_point_reason: file_data.entry_point_reason.clone(),
                 hash: file_data.hash.clone(),
             });
         }
@@ -167,6 +195,7 @@ pub fn build_module_slice(graph: &CodeGraph, mod_name: &str, mod_data: &ModuleEn
         path: module_path(mod_data, mod_name),
         files,
         exports: dedup_sorted(all_exports),
+        reexports: resolve_reexports(graph, mod_name, mod_data),
         depends_on: mod_data.depends_on.clone(),
         depended_by: mod_data.depended_by.clone(),
         stats: ModuleStats {
@@ -179,9 +208,14 @@ pub fn build_module_slice(graph: &CodeGraph, mod_name: &str, mod_data: &ModuleEn
 }
 
 /// 获取模块切片并附带依赖信息（--with-deps）
+///
+/// `max_depth` 为 `None` 时只附带直接依赖（depth 1），与原行为一致；给定
+/// `Some(n)` 时沿 `depends_on` 做广度优先遍历，附带到第 n 层为止的完整传递闭包，
+/// 让消费这份切片的 LLM 能看到模块真正可达的全部依赖，而不只是第一层邻居。
 pub fn get_module_slice_with_deps(
     graph: &CodeGraph,
     module_name: &str,
+    max_depth: Option<u32>,
 ) -> anyhow::Result<ModuleSliceWithDeps> {
     let mod_data = graph
         .modules
@@ -189,34 +223,55 @@ pub fn get_module_slice_with_deps(
         .ok_or_else(|| anyhow::anyhow!("Module \"{}\" not found in graph", module_name))?;
 
     let slice = build_module_slice(graph, module_name, mod_data);
+    let max_depth = max_depth.unwrap_or(1);
 
-    let dependencies: Vec<DepInfo> = mod_data
+    let mut dependencies: Vec<DepInfo> = Vec::new();
+    let mut visited: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let mut queue: std::collections::VecDeque<(String, u32)> = mod_data
         .depends_on
         .iter()
-        .map(|dep_name| {
-            if let Some(dep_data) = graph.modules.get(dep_name) {
-                let (dep_exports, dep_stats) = collect_module_stats(graph, dep_data);
-                DepInfo {
-                    name: dep_name.clone(),
-                    exports: dedup_sorted(dep_exports),
-                    file_count: dep_data.files.len() as u32,
-                    stats: dep_stats,
-                }
-            } else {
-                DepInfo {
-                    name: dep_name.clone(),
-                    exports: vec![],
-                    file_count: 0,
-                    stats: ModuleStats {
-                        total_files: 0,
-                        total_functions: 0,
-                        total_classes: 0,
-                        total_lines: 0,
-                    },
+        .map(|name| (name.clone(), 1))
+        .collect();
+
+    while let Some((dep_name, depth)) = queue.pop_front() {
+        if !visited.insert(dep_name.clone()) {
+            continue;
+        }
+
+        if let Some(dep_data) = graph.modules.get(&dep_name) {
+            let (dep_exports, dep_stats) = collect_module_stats(graph, dep_data);
+            dependencies.push(DepInfo {
+                name: dep_name.clone(),
+                exports: dedup_sorted(dep_exports),
+                file_count: dep_data.files.len() as u32,
+                stats: dep_stats,
+                depth,
+            });
+
+            if depth < max_depth {
+                for child in &dep_data.depends_on {
+                    if !visited.contains(child) {
+                        queue.push_back((child.clone(), depth + 1));
+                    }
                 }
             }
-        })
-        .collect();
+        } else {
+            dependencies.push(DepInfo {
+                name: dep_name,
+                exports: vec![],
+                file_count: 0,
+                stats: ModuleStats {
+                    total_files: 0,
+                    total_functions: 0,
+                    total_classes: 0,
+                    total_lines: 0,
+                },
+                depth,
+            });
+        }
+    }
+
+    dependencies.sort_by(|a, b| a.depth.cmp(&b.depth).then_with(|| a.name.cmp(&b.name)));
 
     Ok(ModuleSliceWithDeps { slice, dependencies })
 }
@@ -224,6 +279,19 @@ pub fn get_module_slice_with_deps(
 /// 保存 overview 和各模块切片到 {output_dir}/slices/
 #[allow(dead_code)]
 pub fn save_slices(output_dir: &Path, graph: &CodeGraph) -> anyhow::Result<()> {
+    save_slices_with_progress(output_dir, graph, &mut crate::progress::NoopSink)
+}
+
+/// 保存 overview 和各模块切片到 {output_dir}/slices/，同时把进度事件交给 `sink`
+///
+/// 行为与 `save_slices` 完全一致，只是每保存完一个模块切片就调用一次
+/// `sink.emit(...)`；传入 `NoopSink` 等价于 `save_slices`。
+#[allow(dead_code)]
+pub fn save_slices_with_progress(
+    output_dir: &Path,
+    graph: &CodeGraph,
+    sink: &mut dyn crate::progress::ProgressSink,
+) -> anyhow::Result<()> {
     let slices_dir = output_dir.join("slices");
     std::fs::create_dir_all(&slices_dir)?;
 
@@ -237,13 +305,183 @@ pub fn save_slices(output_dir: &Path, graph: &CodeGraph) -> anyhow::Result<()> {
     for (mod_name, slice) in &slices {
         let slice_json = serde_json::to_string_pretty(slice)?;
         std::fs::write(slices_dir.join(format!("{}.json", mod_name)), slice_json)?;
+        sink.emit(crate::progress::ScanEvent::ModuleSliced {
+            module: mod_name.clone(),
+            files: slice.files.len() as u32,
+        });
     }
 
     Ok(())
 }
 
+/// `save_slices_incremental` 的执行结果：每个桶里是受影响的模块名
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SaveReport {
+    pub written: Vec<String>,
+    pub skipped: Vec<String>,
+    pub deleted: Vec<String>,
+}
+
+/// `slices/_manifest.json` 的内容：模块名 -> 该模块成员文件哈希的合并摘要
+type SliceManifest = std::collections::BTreeMap<String, String>;
+
+/// 对一个模块算出顺序无关的合并摘要，决定 `save_slices_incremental` 是否需要重写它的切片
+///
+/// 不能只看 `mod_data.files` 自己的哈希：`build_module_slice` 吐出的切片里还带着
+/// `dependsOn`/`dependedBy`/`reexports`，这三项都依赖别的模块。比如 D 新增一条对 A
+/// 的 import，`merge_graph_update` 会正确地把 D 加进 A 的 `depended_by`，但 A 自己
+/// 一个文件都没改，如果摘要只算 A 的文件哈希就永远不变，A 的切片会被判定为"未变"
+/// 而一直跳过重写，磁盘上的 `dependedBy` 就永久缺了 D。`reexports` 同理：barrel
+/// re-export 链路上任何一个中间模块变化，都可能改变本模块展开后的 reexports 列表，
+/// 而那些文件同样不在 `mod_data.files` 里。所以把已经算好的 `depends_on`/
+/// `depended_by`/[`resolve_reexports`] 结果也编码进摘要，跟文件哈希一起先排序再拼接，
+/// 保证顺序变化不会误触发重写
+fn module_digest(graph: &CodeGraph, mod_name: &str, mod_data: &ModuleEntry) -> String {
+    let mut hashes: Vec<&str> = mod_data
+        .files
+        .iter()
+        .filter_map(|f| graph.files.get(f))
+        .map(|f| f.hash.as_str())
+        .collect();
+    hashes.sort_unstable();
+
+    let mut depends_on = mod_data.depends_on.clone();
+    depends_on.sort_unstable();
+    let mut depended_by = mod_data.depended_by.clone();
+    depended_by.sort_unstable();
+    let reexports = resolve_reexports(graph, mod_name, mod_data);
+
+    let mut digest_input = hashes.join("\n");
+    digest_input.push('\n');
+    digest_input.push_str(&depends_on.join(","));
+    digest_input.push('\n');
+    digest_input.push_str(&depended_by.join(","));
+    digest_input.push('\n');
+    for rx in &reexports {
+        digest_input.push_str(&rx.name);
+        digest_input.push('=');
+        digest_input.push_str(&rx.from_module);
+        digest_input.push(';');
+    }
+
+    crate::graph::compute_file_hash(digest_input.as_bytes())
+}
+
+/// 增量保存 overview 和各模块切片到 `{output_dir}/slices/`
+///
+/// 维护一份 `slices/_manifest.json`（模块名 -> 合并哈希），每次运行只重写摘要
+/// 变化过的模块切片，跳过未变的模块，并删除图谱里已不存在的模块留下的旧切片
+/// 文件；`_overview.json` 本身较小且依赖全图统计，始终重写。大仓库上重复运行
+/// `save_slices` 代价很高，这个函数是为那种场景补上的增量版本。
+pub fn save_slices_incremental(output_dir: &Path, graph: &CodeGraph) -> anyhow::Result<SaveReport> {
+    let slices_dir = output_dir.join("slices");
+    std::fs::create_dir_all(&slices_dir)?;
+
+    let overview = generate_overview(graph);
+    let overview_json = serde_json::to_string_pretty(&overview)?;
+    std::fs::write(slices_dir.join("_overview.json"), overview_json)?;
+
+    let manifest_path = slices_dir.join("_manifest.json");
+    let old_manifest: SliceManifest = std::fs::read_to_string(&manifest_path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+
+    let mut report = SaveReport::default();
+    let mut new_manifest: SliceManifest = SliceManifest::new();
+
+    for (mod_name, mod_data) in &graph.modules {
+        let digest = module_digest(graph, mod_name, mod_data);
+        new_manifest.insert(mod_name.clone(), digest.clone());
+
+        if old_manifest.get(mod_name) == Some(&digest) {
+            report.skipped.push(mod_name.clone());
+            continue;
+        }
+
+        let slice = build_module_slice(graph, mod_name, mod_data);
+        let slice_json = serde_json::to_string_pretty(&slice)?;
+        std::fs::write(slices_dir.join(format!("{}.json", mod_name)), slice_json)?;
+        report.written.push(mod_name.clone());
+    }
+
+    for stale_name in old_manifest.keys().filter(|n| !graph.modules.contains_key(*n)) {
+        let _ = std::fs::remove_file(slices_dir.join(format!("{}.json", stale_name)));
+        report.deleted.push(stale_name.clone());
+    }
+
+    std::fs::write(&manifest_path, serde_json::to_string_pretty(&new_manifest)?)?;
+
+    report.written.sort();
+    report.skipped.sort();
+    report.deleted.sort();
+    Ok(report)
+}
+
 // ── 内部工具函数 ──────────────────────────────────────────────────────────────
 
+/// 解析一个模块里的 barrel re-export：具名 re-export（`export { login } from '../auth/login'`）
+/// 直接标注上目标文件所在的模块当作 `from_module`；`export * from './routes'` 这种整体
+/// re-export 把目标模块折叠开来——用 `target_module` 的全部本地声明符号当作这条 star
+/// re-export 实际曝出的符号，而且会继续跟随 `target_module` 自己的 re-export（多层 barrel，
+/// 比如 `index.ts` re-export `routes.ts`，`routes.ts` 又整体 re-export `handlers.ts`）。
+///
+/// 用 `VecDeque` 做广度优先遍历、`HashSet` 记已经展开过的模块 ID，防止 re-export 成环
+/// （`a` re-export `b`、`b` 又整体 re-export `a`）导致死循环。
+fn resolve_reexports(graph: &CodeGraph, mod_name: &str, mod_data: &ModuleEntry) -> Vec<ReexportedSymbol> {
+    let mut resolved: Vec<ReexportedSymbol> = Vec::new();
+    let mut visited: std::collections::HashSet<String> = std::collections::HashSet::new();
+    visited.insert(mod_name.to_string());
+    let mut queue: std::collections::VecDeque<String> = std::collections::VecDeque::new();
+
+    for file_path in &mod_data.files {
+        let Some(file_data) = graph.files.get(file_path) else { continue };
+        for rx in &file_data.resolved_reexports {
+            let Some(target_file) = graph.files.get(&rx.target_file) else { continue };
+            if rx.star {
+                queue.push_back(target_file.module.clone());
+            } else {
+                resolved.push(ReexportedSymbol {
+                    name: rx.name.clone(),
+                    from_module: target_file.module.clone(),
+                });
+            }
+        }
+    }
+
+    while let Some(target_mod) = queue.pop_front() {
+        if !visited.insert(target_mod.clone()) {
+            continue;
+        }
+        let Some(target_data) = graph.modules.get(&target_mod) else { continue };
+
+        for file_path in &target_data.files {
+            let Some(file_data) = graph.files.get(file_path) else { continue };
+            for name in &file_data.exports {
+                resolved.push(ReexportedSymbol {
+                    name: name.clone(),
+                    from_module: target_mod.clone(),
+                });
+            }
+            for rx in &file_data.resolved_reexports {
+                let Some(inner_target) = graph.files.get(&rx.target_file) else { continue };
+                if rx.star {
+                    queue.push_back(inner_target.module.clone());
+                } else {
+                    resolved.push(ReexportedSymbol {
+                        name: rx.name.clone(),
+                        from_module: inner_target.module.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    resolved.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.from_module.cmp(&b.from_module)));
+    resolved.dedup();
+    resolved
+}
+
 fn collect_module_stats(
     graph: &CodeGraph,
     mod_data: &ModuleEntry,
@@ -271,6 +509,23 @@ fn collect_module_stats(
     (all_exports, stats)
 }
 
+/// 收集一个模块下所有文件里标记为 external 的 import source，去重排序
+fn collect_external_dependencies(graph: &CodeGraph, mod_data: &ModuleEntry) -> Vec<String> {
+    let mut deps: Vec<String> = Vec::new();
+    for file_path in &mod_data.files {
+        if let Some(file_data) = graph.files.get(file_path) {
+            deps.extend(
+                file_data
+                    .imports
+                    .iter()
+                    .filter(|i| i.is_external)
+                    .map(|i| i.source.clone()),
+            );
+        }
+    }
+    dedup_sorted(deps)
+}
+
 fn module_path(mod_data: &ModuleEntry, mod_name: &str) -> String {
     if let Some(first_file) = mod_data.files.first() {
         // 取第一个文件的目录
@@ -308,12 +563,22 @@ mod tests {
                 module: "_root".to_string(),
                 hash: "sha256:abcdef123456".to_string(),
                 lines: 10,
+                code_lines: 8,
+                comment_lines: 0,
+                blank_lines: 2,
                 functions: vec![],
                 classes: vec![],
                 types: vec![],
                 imports: vec![],
                 exports: vec!["main".to_string()],
+                reexports: vec![],
+                resolved_reexports: vec![],
+                calls: vec![],
                 is_entry_point: true,
+                entry_point_reason: Some("filename".to_string()),
+                resolved_imports: vec![],
+                imported_by: vec![],
+                parse_diagnostics: vec![],
             },
         );
 
@@ -323,6 +588,9 @@ mod tests {
                 files: vec!["src/main.rs".to_string()],
                 depends_on: vec![],
                 depended_by: vec![],
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
             },
         );
 
@@ -342,6 +610,38 @@ mod tests {
         assert_eq!(overview.modules[0].file_count, 1);
     }
 
+    #[test]
+    fn test_generate_overview_exposes_cycles_from_summary() {
+        let mut graph = make_test_graph();
+        graph.summary.circular_dependencies = vec![vec!["_root".to_string(), "_root".to_string()]];
+        let overview = generate_overview(&graph);
+        assert_eq!(overview.cycles, graph.summary.circular_dependencies);
+    }
+
+    #[test]
+    fn test_generate_overview_collects_external_dependencies() {
+        let mut graph = make_test_graph();
+        graph.files.get_mut("src/main.rs").unwrap().imports = vec![
+            crate::graph::ImportInfo {
+                source: "./utils".to_string(),
+                symbols: vec![],
+                is_external: false,
+                dynamic: false,
+            },
+            crate::graph::ImportInfo {
+                source: "github.com/foo/bar".to_string(),
+                symbols: vec![],
+                is_external: true,
+                dynamic: false,
+            },
+        ];
+        let overview = generate_overview(&graph);
+        assert_eq!(
+            overview.modules[0].external_dependencies,
+            vec!["github.com/foo/bar".to_string()]
+        );
+    }
+
     #[test]
     fn test_build_module_slice() {
         let graph = make_test_graph();
@@ -354,13 +654,340 @@ mod tests {
         assert_eq!(slice.stats.total_lines, 10);
     }
 
+    #[test]
+    fn test_build_module_slice_resolves_named_reexport_to_origin_module() {
+        let mut graph = make_test_graph();
+        graph.files.insert(
+            "src/auth/index.ts".to_string(),
+            FileEntry {
+                language: "typescript".to_string(),
+                module: "auth".to_string(),
+                hash: "sha256:idx".to_string(),
+                lines: 1,
+                code_lines: 1,
+                comment_lines: 0,
+                blank_lines: 0,
+                functions: vec![],
+                classes: vec![],
+                types: vec![],
+                imports: vec![],
+                exports: vec![],
+                reexports: vec![crate::graph::ReexportInfo {
+                    name: "login".to_string(),
+                    source: "./login".to_string(),
+                    star: false,
+                }],
+                resolved_reexports: vec![crate::graph::ResolvedReexport {
+                    name: "login".to_string(),
+                    target_file: "src/auth/login.ts".to_string(),
+                    star: false,
+                }],
+                calls: vec![],
+                is_entry_point: false,
+                entry_point_reason: None,
+                resolved_imports: vec![],
+                imported_by: vec![],
+                parse_diagnostics: vec![],
+            },
+        );
+        graph.files.insert(
+            "src/auth/login.ts".to_string(),
+            FileEntry {
+                language: "typescript".to_string(),
+                module: "login_impl".to_string(),
+                hash: "sha256:login".to_string(),
+                lines: 1,
+                code_lines: 1,
+                comment_lines: 0,
+                blank_lines: 0,
+                functions: vec![],
+                classes: vec![],
+                types: vec![],
+                imports: vec![],
+                exports: vec!["login".to_string()],
+                reexports: vec![],
+                resolved_reexports: vec![],
+                calls: vec![],
+                is_entry_point: false,
+                entry_point_reason: None,
+                resolved_imports: vec![],
+                imported_by: vec![],
+                parse_diagnostics: vec![],
+            },
+        );
+        graph.modules.insert(
+            "auth".to_string(),
+            ModuleEntry {
+                files: vec!["src/auth/index.ts".to_string()],
+                depends_on: vec![],
+                depended_by: vec![],
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
+            },
+        );
+        graph.modules.insert(
+            "login_impl".to_string(),
+            ModuleEntry {
+                files: vec!["src/auth/login.ts".to_string()],
+                depends_on: vec![],
+                depended_by: vec![],
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
+            },
+        );
+
+        let mod_data = graph.modules.get("auth").unwrap();
+        let slice = build_module_slice(&graph, "auth", mod_data);
+        assert!(slice.exports.is_empty());
+        assert_eq!(
+            slice.reexports,
+            vec![ReexportedSymbol { name: "login".to_string(), from_module: "login_impl".to_string() }]
+        );
+    }
+
+    #[test]
+    fn test_build_module_slice_folds_star_reexport_and_breaks_cycles() {
+        let mut graph = make_test_graph();
+        graph.files.insert(
+            "src/barrel/index.ts".to_string(),
+            FileEntry {
+                language: "typescript".to_string(),
+                module: "barrel".to_string(),
+                hash: "sha256:barrel".to_string(),
+                lines: 1,
+                code_lines: 1,
+                comment_lines: 0,
+                blank_lines: 0,
+                functions: vec![],
+                classes: vec![],
+                types: vec![],
+                imports: vec![],
+                exports: vec![],
+                reexports: vec![crate::graph::ReexportInfo {
+                    name: String::new(),
+                    source: "./routes".to_string(),
+                    star: true,
+                }],
+                resolved_reexports: vec![crate::graph::ResolvedReexport {
+                    name: String::new(),
+                    target_file: "src/routes/routes.ts".to_string(),
+                    star: true,
+                }],
+                calls: vec![],
+                is_entry_point: false,
+                entry_point_reason: None,
+                resolved_imports: vec![],
+                imported_by: vec![],
+                parse_diagnostics: vec![],
+            },
+        );
+        graph.files.insert(
+            "src/routes/routes.ts".to_string(),
+            FileEntry {
+                language: "typescript".to_string(),
+                module: "routes".to_string(),
+                hash: "sha256:routes".to_string(),
+                lines: 1,
+                code_lines: 1,
+                comment_lines: 0,
+                blank_lines: 0,
+                functions: vec![],
+                classes: vec![],
+                types: vec![],
+                imports: vec![],
+                exports: vec!["getUsers".to_string(), "getPosts".to_string()],
+                // 整体 re-export 回 barrel 模块，校验 visited 能防止无限循环
+                reexports: vec![crate::graph::ReexportInfo {
+                    name: String::new(),
+                    source: "../barrel/index".to_string(),
+                    star: true,
+                }],
+                resolved_reexports: vec![crate::graph::ResolvedReexport {
+                    name: String::new(),
+                    target_file: "src/barrel/index.ts".to_string(),
+                    star: true,
+                }],
+                calls: vec![],
+                is_entry_point: false,
+                entry_point_reason: None,
+                resolved_imports: vec![],
+                imported_by: vec![],
+                parse_diagnostics: vec![],
+            },
+        );
+        graph.modules.insert(
+            "barrel".to_string(),
+            ModuleEntry {
+                files: vec!["src/barrel/index.ts".to_string()],
+                depends_on: vec![],
+                depended_by: vec![],
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
+            },
+        );
+        graph.modules.insert(
+            "routes".to_string(),
+            ModuleEntry {
+                files: vec!["src/routes/routes.ts".to_string()],
+                depends_on: vec![],
+                depended_by: vec![],
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
+            },
+        );
+
+        let mod_data = graph.modules.get("barrel").unwrap();
+        let slice = build_module_slice(&graph, "barrel", mod_data);
+        assert_eq!(
+            slice.reexports,
+            vec![
+                ReexportedSymbol { name: "getPosts".to_string(), from_module: "routes".to_string() },
+                ReexportedSymbol { name: "getUsers".to_string(), from_module: "routes".to_string() },
+            ]
+        );
+    }
+
     #[test]
     fn test_get_module_slice_with_deps_not_found() {
         let graph = make_test_graph();
-        let result = get_module_slice_with_deps(&graph, "nonexistent");
+        let result = get_module_slice_with_deps(&graph, "nonexistent", None);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_get_module_slice_with_deps_transitive_closure() {
+        let mut graph = make_test_graph();
+        // _root -> a -> b -> a（环），校验 visited 能防止无限循环
+        graph.modules.get_mut("_root").unwrap().depends_on = vec!["a".to_string()];
+        graph.modules.insert(
+            "a".to_string(),
+            ModuleEntry {
+                files: vec![],
+                depends_on: vec!["b".to_string()],
+                depended_by: vec!["_root".to_string()],
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
+            },
+        );
+        graph.modules.insert(
+            "b".to_string(),
+            ModuleEntry {
+                files: vec![],
+                depends_on: vec!["a".to_string()],
+                depended_by: vec!["a".to_string()],
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
+            },
+        );
+
+        let result = get_module_slice_with_deps(&graph, "_root", Some(5)).unwrap();
+        assert_eq!(result.dependencies.len(), 2);
+        assert_eq!(result.dependencies[0].name, "a");
+        assert_eq!(result.dependencies[0].depth, 1);
+        assert_eq!(result.dependencies[1].name, "b");
+        assert_eq!(result.dependencies[1].depth, 2);
+    }
+
+    #[test]
+    fn test_get_module_slice_with_deps_default_depth_is_direct_only() {
+        let mut graph = make_test_graph();
+        graph.modules.get_mut("_root").unwrap().depends_on = vec!["a".to_string()];
+        graph.modules.insert(
+            "a".to_string(),
+            ModuleEntry {
+                files: vec![],
+                depends_on: vec!["b".to_string()],
+                depended_by: vec!["_root".to_string()],
+                code_lines: 0,
+                comment_lines: 0,
+                blank_lines: 0,
+            },
+        );
+
+        let result = get_module_slice_with_deps(&graph, "_root", None).unwrap();
+        assert_eq!(result.dependencies.len(), 1);
+        assert_eq!(result.dependencies[0].name, "a");
+        assert_eq!(result.dependencies[0].depth, 1);
+    }
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("codemap-slicer-test-{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_save_slices_incremental_skips_unchanged_modules() {
+        let dir = temp_dir("skip-unchanged");
+        let graph = make_test_graph();
+
+        let first = save_slices_incremental(&dir, &graph).unwrap();
+        assert_eq!(first.written, vec!["_root".to_string()]);
+        assert!(first.skipped.is_empty());
+
+        let second = save_slices_incremental(&dir, &graph).unwrap();
+        assert!(second.written.is_empty());
+        assert_eq!(second.skipped, vec!["_root".to_string()]);
+    }
+
+    #[test]
+    fn test_save_slices_incremental_rewrites_changed_module_and_deletes_removed() {
+        let dir = temp_dir("rewrite-and-delete");
+        let mut graph = make_test_graph();
+        save_slices_incremental(&dir, &graph).unwrap();
+
+        // 文件哈希变了 -> 摘要变了，应该重写
+        graph.files.get_mut("src/main.rs").unwrap().hash = "sha256:changed".to_string();
+        let report = save_slices_incremental(&dir, &graph).unwrap();
+        assert_eq!(report.written, vec!["_root".to_string()]);
+
+        // 模块整个从图里消失 -> 旧切片文件应该被删掉
+        graph.modules.remove("_root");
+        let report = save_slices_incremental(&dir, &graph).unwrap();
+        assert_eq!(report.deleted, vec!["_root".to_string()]);
+        assert!(!dir.join("slices/_root.json").exists());
+    }
+
+    #[test]
+    fn test_module_digest_is_order_independent() {
+        let mut graph = make_test_graph();
+        graph.files.insert(
+            "src/other.rs".to_string(),
+            graph.files.get("src/main.rs").unwrap().clone(),
+        );
+        graph.files.get_mut("src/other.rs").unwrap().hash = "sha256:other".to_string();
+
+        let mut forward = graph.modules.get("_root").unwrap().clone();
+        forward.files = vec!["src/main.rs".to_string(), "src/other.rs".to_string()];
+        let mut reversed = forward.clone();
+        reversed.files = vec!["src/other.rs".to_string(), "src/main.rs".to_string()];
+
+        assert_eq!(
+            module_digest(&graph, "_root", &forward),
+            module_digest(&graph, "_root", &reversed)
+        );
+    }
+
+    #[test]
+    fn test_module_digest_changes_when_depended_by_gains_a_module_with_no_file_changes() {
+        let graph = make_test_graph();
+        let mod_data = graph.modules.get("_root").unwrap().clone();
+        let before = module_digest(&graph, "_root", &mod_data);
+
+        let mut mod_data = mod_data;
+        mod_data.depended_by.push("other".to_string());
+        let after = module_digest(&graph, "_root", &mod_data);
+
+        assert_ne!(before, after);
+    }
+
     #[test]
     fn test_dedup_sorted() {
         let v = vec!["b".to_string(), "a".to_string(), "b".to_string()];
@@ -0,0 +1,67 @@
+//! 增量更新用的源码快照缓存
+//!
+//! `codegraph update` 每次运行都是一个全新进程，没有像 `ParseCache` 那样常驻内存、
+//! 能跨两次调用直接复用的 `Tree`。要让 `differ::update_graph_incremental` 用上
+//! tree-sitter 真正的增量重解析（`Tree::edit` + `Parser::parse(new, Some(&old_tree))`），
+//! 就得先有"上一次的源码"本身可用——而文件一旦变更，磁盘上已经是新内容了，旧的那份
+//! 无从找回。这里把每个解析过的文件的源码字节原样存一份在 `.codemap/sources/` 下
+//! （镜像其相对路径），供下一次 update 读回来当作 diff 的基线；文件被删除时一并清理。
+use std::path::{Path, PathBuf};
+
+fn cache_dir(codemap_dir: &Path) -> PathBuf {
+    codemap_dir.join("sources")
+}
+
+/// 读取上一次保存的 `rel_path` 源码快照。返回 `None` 时（从未缓存过，或者
+/// `.codemap/sources/` 还不存在）调用方应当退回全量解析，而不是强行走增量路径。
+pub fn load(codemap_dir: &Path, rel_path: &str) -> Option<Vec<u8>> {
+    std::fs::read(cache_dir(codemap_dir).join(rel_path)).ok()
+}
+
+/// 把这次解析用的源码字节存下来，供下一次 update 当作增量重解析的基线
+pub fn save(codemap_dir: &Path, rel_path: &str, content: &[u8]) -> anyhow::Result<()> {
+    let path = cache_dir(codemap_dir).join(rel_path);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+/// 文件被删除时清理它的源码快照，避免 `.codemap/sources/` 里堆积指向已删除文件的条目
+pub fn remove(codemap_dir: &Path, rel_path: &str) {
+    let _ = std::fs::remove_file(cache_dir(codemap_dir).join(rel_path));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("codemap-source-cache-test-{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_load_missing_returns_none() {
+        let dir = temp_dir("missing");
+        assert!(load(&dir, "src/main.rs").is_none());
+    }
+
+    #[test]
+    fn test_save_then_load_roundtrips_nested_path() {
+        let dir = temp_dir("roundtrip");
+        save(&dir, "src/nested/mod.rs", b"fn a() {}").unwrap();
+        assert_eq!(load(&dir, "src/nested/mod.rs"), Some(b"fn a() {}".to_vec()));
+    }
+
+    #[test]
+    fn test_remove_deletes_cached_snapshot() {
+        let dir = temp_dir("remove");
+        save(&dir, "src/main.rs", b"fn main() {}").unwrap();
+        remove(&dir, "src/main.rs");
+        assert!(load(&dir, "src/main.rs").is_none());
+    }
+}
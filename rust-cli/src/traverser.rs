@@ -1,3 +1,5 @@
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::overrides::{Override, OverrideBuilder};
 use ignore::WalkBuilder;
 use std::path::{Path, PathBuf};
 
@@ -83,32 +85,227 @@ pub fn effective_language(path: &Path, base: Language, project_has_cpp: bool) ->
     }
 }
 
-/// 遍历目录，返回所有支持语言的源文件路径
+/// 编译额外的排除模式为一个 gitignore 风格的匹配器，用于兜底的逐文件复查
+///
+/// `extra_exclude` 中的每一项都按 `.gitignore` 的语法解释，因此除了 `vendor` 这样的
+/// 普通目录名外，也支持 `**/*.test.ts`、`vendor/**` 这类 glob 模式。模式非法时跳过
+/// 并提示，不影响其余模式生效。
+fn build_exclude_matcher(root_dir: &Path, extra_exclude: &[String]) -> Option<Gitignore> {
+    if extra_exclude.is_empty() {
+        return None;
+    }
+
+    let mut builder = GitignoreBuilder::new(root_dir);
+    for pattern in extra_exclude {
+        if let Err(e) = builder.add_line(None, pattern) {
+            eprintln!("Warning: ignoring invalid exclude pattern '{}': {}", pattern, e);
+        }
+    }
+    builder.build().ok()
+}
+
+/// 从 `extra_exclude` 里剔除不可能命中当前 `root_dir` 的模式
+///
+/// 一个模式的字面前缀（第一个 glob 元字符 `* ? [ {` 之前的完整目录段）若在磁盘上
+/// 根本不存在，这条模式在本次扫描里就不可能匹配到任何东西，没必要再编译、再在每个
+/// 目录项上跑一遍——用户扫描某个子目录、但排除列表里混有其它子树的模式时尤其常见。
+fn relevant_excludes<'a>(root_dir: &Path, extra_exclude: &'a [String]) -> Vec<&'a String> {
+    extra_exclude
+        .iter()
+        .filter(|pattern| {
+            let literal_prefix = literal_dir_prefix(pattern);
+            literal_prefix.is_empty() || root_dir.join(literal_prefix).exists()
+        })
+        .collect()
+}
+
+/// 取模式里第一个 glob 元字符之前、且以完整目录段结尾的字面前缀；
+/// 完全不含 glob 元字符的模式（如 `vendor/`）整体就是字面前缀
+fn literal_dir_prefix(pattern: &str) -> &str {
+    let pattern = pattern.trim_start_matches('!').trim_end_matches('/');
+    match pattern.find(['*', '?', '[', '{']) {
+        None => pattern,
+        Some(cut) => match pattern[..cut].rfind('/') {
+            Some(slash) => &pattern[..slash],
+            None => "",
+        },
+    }
+}
+
+/// 把排除模式编译成 `Override`，交给 `WalkBuilder` 在遍历时直接参与目录剪枝
+///
+/// `ignore` crate 的 `Override`（即 ripgrep `-g/--glob` 用的那套）语义和 `.gitignore`
+/// 相反：不带 `!` 的模式表示"只保留匹配项"（白名单），所以这里把普通的排除模式统一
+/// 加上 `!` 前缀，让它们纯粹当作黑名单用——命中的目录在遍历时直接被跳过，不会再下探
+/// 进去，而不是等文件都读出来之后再逐个丢弃。`extra_exclude` 里以 `!` 开头的模式按
+/// `.gitignore` 的直觉走，表示"把前面规则排除掉的这部分重新找回来"，对应到 `Override`
+/// 的白名单语义就是去掉 `!`、原样保留——`OverrideBuilder` 按添加顺序"后加入的覆盖先
+/// 加入的"判定，所以必须排在默认排除和普通排除之后加入才能生效。
+fn build_prune_overrides(root_dir: &Path, extra_exclude: &[String]) -> Option<Override> {
+    let mut builder = OverrideBuilder::new(root_dir);
+    let mut any = false;
+
+    for name in DEFAULT_EXCLUDE {
+        if builder.add(&format!("!**/{}", name)).is_ok() {
+            any = true;
+        }
+    }
+
+    for pattern in relevant_excludes(root_dir, extra_exclude) {
+        let translated = match pattern.strip_prefix('!') {
+            Some(reincluded) => reincluded.to_string(),
+            None => format!("!{}", pattern),
+        };
+        match builder.add(&translated) {
+            Ok(_) => any = true,
+            Err(e) => eprintln!("Warning: ignoring invalid exclude pattern '{}': {}", pattern, e),
+        }
+    }
+
+    if !any {
+        return None;
+    }
+    builder.build().ok()
+}
+
+/// `scan`/`update` 的 include/exclude 过滤条件
+///
+/// `include` 为空表示不设白名单——所有未被排除的受支持语言文件都收录，与历史行为
+/// 一致。非空时只有命中至少一条 include glob 的文件才会被收录，`exclude` 仍然优先
+/// （一个文件同时命中 include 和 exclude 时按排除处理）。两个列表里的模式都按相对于
+/// 扫描根目录解释，可以带 `**`/`*`/`?`/`{..}` 这类标准 glob 元字符。
+#[derive(Debug, Clone, Default)]
+pub struct ScanFilter {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+impl ScanFilter {
+    pub fn new(include: Vec<String>, exclude: Vec<String>) -> Self {
+        Self { include, exclude }
+    }
+}
+
+/// 遍历目录，返回所有支持语言的源文件路径（无 include 白名单的 `ScanFilter` 快捷方式）
 pub fn traverse_files(root_dir: &Path, extra_exclude: &[String]) -> Vec<PathBuf> {
-    let mut files = Vec::new();
+    traverse_files_filtered(root_dir, &ScanFilter::new(vec![], extra_exclude.to_vec()))
+}
+
+/// 把 `include` 编译成一个 Gitignore 风格的匹配器，复用 `matched()` 判断"这个相对路径
+/// 命中了哪条 include 模式"；语义上跟 `build_exclude_matcher` 反过来用——命中才留下，
+/// 而不是命中就丢弃
+fn build_include_matcher(root_dir: &Path, include: &[String]) -> Option<Gitignore> {
+    if include.is_empty() {
+        return None;
+    }
 
-    let walker = WalkBuilder::new(root_dir)
-        .hidden(false)
-        .git_ignore(true)
-        .git_global(true)
-        .git_exclude(true)
-        .build();
+    let mut builder = GitignoreBuilder::new(root_dir);
+    for pattern in include {
+        if let Err(e) = builder.add_line(None, pattern) {
+            eprintln!("Warning: ignoring invalid include pattern '{}': {}", pattern, e);
+        }
+    }
+    builder.build().ok()
+}
+
+/// 把每条 include 模式拆成"字面前缀目录" + 其余 glob 部分，只返回这些字面前缀目录
+/// （去重、丢弃磁盘上不存在的），作为遍历时实际要下探的根；`include` 为空，或者其中
+/// 任意一条模式没有字面前缀（如 `**/*.rs`），则退化为整个 `root_dir`——这种模式本来
+/// 就可能匹配根下任何位置，没办法再收窄下探范围。
+fn include_base_dirs(root_dir: &Path, include: &[String]) -> Vec<PathBuf> {
+    if include.is_empty() {
+        return vec![root_dir.to_path_buf()];
+    }
+
+    let mut bases: Vec<PathBuf> = Vec::new();
+    for pattern in include {
+        let prefix = literal_dir_prefix(pattern);
+        if prefix.is_empty() {
+            return vec![root_dir.to_path_buf()];
+        }
+        let base = root_dir.join(prefix);
+        if base.exists() && !bases.contains(&base) {
+            bases.push(base);
+        }
+    }
 
-    for entry in walker.flatten() {
-        let path = entry.path().to_path_buf();
+    if bases.is_empty() {
+        vec![root_dir.to_path_buf()]
+    } else {
+        dedup_nested_bases(bases)
+    }
+}
 
-        if !path.is_file() {
-            continue;
+/// 丢弃被别的 base 目录包住的 base：一条 include 模式的字面前缀目录如果是另一条的
+/// 祖先（或者完全相同），遍历祖先目录自然就会覆盖到后代目录，没必要再单独起一次
+/// `WalkBuilder` 重复下探同一棵子树
+fn dedup_nested_bases(mut bases: Vec<PathBuf>) -> Vec<PathBuf> {
+    bases.sort_by_key(|b| b.components().count());
+    let mut kept: Vec<PathBuf> = Vec::new();
+    for base in bases {
+        if !kept.iter().any(|k: &PathBuf| base.starts_with(k)) {
+            kept.push(base);
         }
+    }
+    kept
+}
+
+/// 遍历目录，返回所有支持语言、满足 `filter` 的源文件路径
+///
+/// 除了 tree-sitter 能解析的扩展名过滤外，这里还叠加几层排除/收录：
+/// - 先用 `include_base_dirs` 把每条 include 模式拆成字面前缀目录，只在这些目录
+///   （或没有 include 时的整个 `root_dir`）下起 `WalkBuilder`，避免白白遍历跟任何
+///   include 模式都不沾边的子树；
+/// - `ignore::WalkBuilder` 在遍历时沿目录层级加载并应用 `.gitignore`/`.ignore`/
+///   `.git/info/exclude`（含全局 gitignore），与常见代码遍历工具行为一致；
+/// - 默认排除目录和 `filter.exclude` 编译出的 `Override` 注册给 `WalkBuilder` 本身，
+///   命中的目录在遍历时就被剪掉整棵子树，不会再下探；
+/// - `exclude_matcher`/`include_matcher` 做逐文件复查：前者是兜底（防止 `Override`
+///   编译失败时排除规则完全失效），后者是 include 白名单本身的精确匹配（字面前缀
+///   只负责粗粒度剪枝，`src/**/*.rs` 这样的完整模式仍需要逐文件核对）。
+pub fn traverse_files_filtered(root_dir: &Path, filter: &ScanFilter) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let exclude_matcher = build_exclude_matcher(root_dir, &filter.exclude);
+    let include_matcher = build_include_matcher(root_dir, &filter.include);
+    let mut seen: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
 
-        // 检查是否在默认排除目录中
-        if is_excluded(&path, root_dir, extra_exclude) {
-            continue;
+    for base_dir in include_base_dirs(root_dir, &filter.include) {
+        let mut walk_builder = WalkBuilder::new(&base_dir);
+        walk_builder.hidden(false).git_ignore(true).git_global(true).git_exclude(true);
+        // Override 绑定着 root_dir 的 glob 编译结果，每个 base_dir 各自建一份，
+        // 避免依赖 `ignore::overrides::Override` 是否实现 Clone
+        if let Some(overrides) = build_prune_overrides(root_dir, &filter.exclude) {
+            walk_builder.overrides(overrides);
         }
+        let walker = walk_builder.build();
+
+        for entry in walker.flatten() {
+            let path = entry.path().to_path_buf();
+
+            if !path.is_file() || !seen.insert(path.clone()) {
+                continue;
+            }
+
+            // 检查是否在默认排除目录中，或命中 exclude 的 glob 模式
+            if is_excluded(&path, root_dir, exclude_matcher.as_ref()) {
+                continue;
+            }
+
+            // 有 include 白名单时，只留下命中它的文件
+            if let Some(matcher) = &include_matcher {
+                let rel = match path.strip_prefix(root_dir) {
+                    Ok(r) => r,
+                    Err(_) => continue,
+                };
+                if !matcher.matched(rel, false).is_ignore() {
+                    continue;
+                }
+            }
 
-        // 只保留支持语言的文件
-        if detect_language(&path).is_some() {
-            files.push(path);
+            // 只保留支持语言的文件
+            if detect_language(&path).is_some() {
+                files.push(path);
+            }
         }
     }
 
@@ -116,24 +313,30 @@ pub fn traverse_files(root_dir: &Path, extra_exclude: &[String]) -> Vec<PathBuf>
     files
 }
 
-fn is_excluded(path: &Path, root: &Path, extra_exclude: &[String]) -> bool {
+fn is_excluded(path: &Path, root: &Path, exclude_matcher: Option<&Gitignore>) -> bool {
     let rel = match path.strip_prefix(root) {
         Ok(r) => r,
         Err(_) => return false,
     };
 
-    // 检查路径各组件是否命中默认排除列表
+    // 先问 exclude_matcher 有没有给出明确结论。`!pattern` 形式的显式取消忽略
+    // （`Match::Whitelist`）必须在下面的默认目录名检查之前判断，否则 `build/`
+    // 这种默认排除目录一旦命中就直接短路返回，`!build/keep/**` 永远没有机会
+    // 把它的某个角落重新找回来
+    if let Some(matcher) = exclude_matcher {
+        match matcher.matched(rel, false) {
+            ignore::Match::Whitelist(_) => return false,
+            ignore::Match::Ignore(_) => return true,
+            ignore::Match::None => {}
+        }
+    }
+
+    // 检查路径各组件是否命中默认排除目录
     for component in rel.components() {
         let name = component.as_os_str().to_string_lossy();
         if DEFAULT_EXCLUDE.contains(&name.as_ref()) {
             return true;
         }
-        // 检查额外排除模式（简单前缀/名称匹配）
-        for pattern in extra_exclude {
-            if name.as_ref() == pattern.as_str() {
-                return true;
-            }
-        }
     }
 
     false
@@ -191,4 +394,158 @@ mod tests {
         let no_cpp: Vec<PathBuf> = vec![PathBuf::from("a.c"), PathBuf::from("b.h")];
         assert!(!has_cpp_source_files(&no_cpp));
     }
+
+    #[test]
+    fn test_literal_dir_prefix() {
+        assert_eq!(literal_dir_prefix("src/legacy/**"), "src/legacy");
+        assert_eq!(literal_dir_prefix("vendor/"), "vendor");
+        assert_eq!(literal_dir_prefix("**/*.test.ts"), "");
+        assert_eq!(literal_dir_prefix("!src/legacy/**"), "src/legacy");
+    }
+
+    #[test]
+    fn test_relevant_excludes_skips_patterns_for_nonexistent_subtrees() {
+        let dir = make_tree();
+        let patterns = vec!["src/**".to_string(), "does-not-exist/**".to_string()];
+        let relevant = relevant_excludes(&dir, &patterns);
+        assert_eq!(relevant, vec![&"src/**".to_string()]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    /// 在临时目录下搭一个小型项目树，验证 traverse_files 同时遵守默认排除目录、
+    /// .gitignore 以及 extra_exclude 中的 glob 模式
+    fn make_tree() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "codegraph_traverser_test_{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::create_dir_all(dir.join("node_modules")).unwrap();
+        std::fs::create_dir_all(dir.join("vendor/lib")).unwrap();
+        std::fs::write(dir.join(".gitignore"), "generated/\n").unwrap();
+        std::fs::create_dir_all(dir.join("generated")).unwrap();
+        std::fs::write(dir.join("src/main.rs"), "fn main() {}").unwrap();
+        std::fs::write(dir.join("src/main.test.rs"), "fn t() {}").unwrap();
+        std::fs::write(dir.join("node_modules/dep.js"), "// dep").unwrap();
+        std::fs::write(dir.join("vendor/lib/util.c"), "int x;").unwrap();
+        std::fs::write(dir.join("generated/codegen.rs"), "fn g() {}").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_traverse_files_honors_default_and_gitignore_excludes() {
+        let dir = make_tree();
+        let files = traverse_files(&dir, &[]);
+        let rel: Vec<String> = files
+            .iter()
+            .map(|f| f.strip_prefix(&dir).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect();
+
+        assert!(rel.contains(&"src/main.rs".to_string()));
+        assert!(rel.contains(&"src/main.test.rs".to_string()));
+        assert!(!rel.iter().any(|p| p.starts_with("node_modules/")));
+        assert!(!rel.iter().any(|p| p.starts_with("vendor/")));
+        assert!(!rel.iter().any(|p| p.starts_with("generated/")));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_traverse_files_filtered_applies_include_whitelist() {
+        let dir = make_tree();
+        let filter = ScanFilter::new(vec!["src/**".to_string()], vec![]);
+        let files = traverse_files_filtered(&dir, &filter);
+        let rel: Vec<String> = files
+            .iter()
+            .map(|f| f.strip_prefix(&dir).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect();
+
+        assert!(rel.contains(&"src/main.rs".to_string()));
+        assert!(rel.contains(&"src/main.test.rs".to_string()));
+        // 不在 include 列表里的子树即使本来没被排除，也不应该出现
+        assert!(!rel.iter().any(|p| !p.starts_with("src/")));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_traverse_files_filtered_exclude_wins_over_include() {
+        let dir = make_tree();
+        let filter = ScanFilter::new(vec!["src/**".to_string()], vec!["**/*.test.rs".to_string()]);
+        let files = traverse_files_filtered(&dir, &filter);
+        let rel: Vec<String> = files
+            .iter()
+            .map(|f| f.strip_prefix(&dir).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect();
+
+        assert!(rel.contains(&"src/main.rs".to_string()));
+        assert!(!rel.contains(&"src/main.test.rs".to_string()));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_include_base_dirs_falls_back_to_root_for_wildcard_prefix() {
+        let dir = make_tree();
+        let bases = include_base_dirs(&dir, &["**/*.rs".to_string()]);
+        assert_eq!(bases, vec![dir.clone()]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_include_base_dirs_uses_literal_prefix() {
+        let dir = make_tree();
+        let bases = include_base_dirs(&dir, &["src/**".to_string()]);
+        assert_eq!(bases, vec![dir.join("src")]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_include_base_dirs_dedups_nested_bases() {
+        let dir = make_tree();
+        std::fs::create_dir_all(dir.join("src/legacy")).unwrap();
+
+        // src/legacy/** 的 base 目录是 src/** 的 base 目录的子目录，遍历 src
+        // 自然会覆盖到 src/legacy，不应该再单独起一次 WalkBuilder
+        let bases = include_base_dirs(&dir, &["src/**".to_string(), "src/legacy/**".to_string()]);
+        assert_eq!(bases, vec![dir.join("src")]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_traverse_files_applies_extra_exclude_globs() {
+        let dir = make_tree();
+        let files = traverse_files(&dir, &["**/*.test.rs".to_string()]);
+        let rel: Vec<String> = files
+            .iter()
+            .map(|f| f.strip_prefix(&dir).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect();
+
+        assert!(rel.contains(&"src/main.rs".to_string()));
+        assert!(!rel.contains(&"src/main.test.rs".to_string()));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_traverse_files_reincludes_path_under_default_excluded_dir() {
+        let dir = make_tree();
+        std::fs::create_dir_all(dir.join("vendor/keep")).unwrap();
+        std::fs::write(dir.join("vendor/keep/shim.c"), "int shim;").unwrap();
+
+        // vendor/ 整体落在 DEFAULT_EXCLUDE 里，但显式 `!vendor/keep/**` 应该把
+        // vendor/keep 这一个角落重新找回来，不影响 vendor 下其余文件仍被排除
+        let files = traverse_files(&dir, &["!vendor/keep/**".to_string()]);
+        let rel: Vec<String> = files
+            .iter()
+            .map(|f| f.strip_prefix(&dir).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect();
+
+        assert!(rel.contains(&"vendor/keep/shim.c".to_string()));
+        assert!(!rel.iter().any(|p| p == "vendor/lib/util.c"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
@@ -0,0 +1,160 @@
+/// 数据驱动的 fixture 一致性校验
+///
+/// 把原先散落在 `tests/fixture_compat.rs` 里的手写 assert 收敛成一份 golden JSON
+/// 期望清单：每个 fixture 记录期望的 functions/imports/exports/classes 名单，跑一遍
+/// `get_adapter(lang)` + 四个 `extract_*` 后按字段做集合 diff。事件模型仿照 deno
+/// 测试运行器：先发一条 `Plan`，每个 fixture 跑之前发 `Wait`，跑完发 `Result`。
+use crate::languages::get_adapter;
+use crate::traverser::Language;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// 一个 fixture 的 golden 期望：一种语言 + 一个文件 + 四类符号名单
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FixtureExpectation {
+    pub name: String,
+    pub language: String,
+    pub path: String,
+    #[serde(default)]
+    pub functions: Vec<String>,
+    #[serde(default)]
+    pub imports: Vec<String>,
+    #[serde(default)]
+    pub exports: Vec<String>,
+    #[serde(default)]
+    pub classes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExpectationManifest {
+    #[serde(rename = "fixtureBase")]
+    pub fixture_base: String,
+    pub fixtures: Vec<FixtureExpectation>,
+}
+
+/// 从磁盘加载 golden 期望清单
+pub fn load_manifest(path: &Path) -> anyhow::Result<ExpectationManifest> {
+    let data = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&data)?)
+}
+
+/// 某个字段（functions/imports/exports/classes）上缺失或多出的符号
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldDiff {
+    pub field: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum Outcome {
+    Ok,
+    Ignored,
+    Failed { diff: Vec<FieldDiff> },
+}
+
+/// deno 测试运行器风格的事件流：跑之前广播一次 `Plan`，然后每个 fixture 各发一对
+/// `Wait`/`Result`
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum VerifyEvent {
+    Plan {
+        pending: usize,
+        filtered: usize,
+        only: bool,
+    },
+    Wait {
+        name: String,
+    },
+    Result {
+        name: String,
+        #[serde(rename = "durationMs")]
+        duration_ms: u64,
+        outcome: Outcome,
+    },
+}
+
+/// 解析 manifest 里的语言字符串（"typescript"/"cpp" 等）到 [`Language`]；也被
+/// `codegraph coverage` 的 `--language` 覆盖参数复用
+pub fn parse_language(name: &str) -> Option<Language> {
+    match name {
+        "typescript" => Some(Language::TypeScript),
+        "javascript" => Some(Language::JavaScript),
+        "python" => Some(Language::Python),
+        "go" => Some(Language::Go),
+        "rust" => Some(Language::Rust),
+        "java" => Some(Language::Java),
+        "c" => Some(Language::C),
+        "cpp" => Some(Language::Cpp),
+        _ => None,
+    }
+}
+
+fn diff_field(field: &str, expected: &[String], actual: &[String]) -> Option<FieldDiff> {
+    let expected_set: BTreeSet<&str> = expected.iter().map(String::as_str).collect();
+    let actual_set: BTreeSet<&str> = actual.iter().map(String::as_str).collect();
+    let missing: Vec<String> = expected_set.difference(&actual_set).map(|s| s.to_string()).collect();
+    let extra: Vec<String> = actual_set.difference(&expected_set).map(|s| s.to_string()).collect();
+    if missing.is_empty() && extra.is_empty() {
+        None
+    } else {
+        Some(FieldDiff { field: field.to_string(), missing, extra })
+    }
+}
+
+/// 跑单个 fixture：解析 + 与期望做字段级 diff，返回 [`Outcome`]（不含计时，计时交给调用方）
+pub fn run_fixture(base_dir: &Path, expectation: &FixtureExpectation) -> Outcome {
+    let Some(lang) = parse_language(&expectation.language) else {
+        return Outcome::Failed {
+            diff: vec![FieldDiff { field: "language".into(), missing: vec![expectation.language.clone()], extra: vec![] }],
+        };
+    };
+
+    let path = base_dir.join(&expectation.path);
+    let source = match std::fs::read(&path) {
+        Ok(s) => s,
+        Err(e) => {
+            return Outcome::Failed {
+                diff: vec![FieldDiff { field: "fixture".into(), missing: vec![format!("{}: {}", path.display(), e)], extra: vec![] }],
+            };
+        }
+    };
+
+    let adapter = get_adapter(lang);
+    let mut parser = tree_sitter::Parser::new();
+    if parser.set_language(&adapter.language()).is_err() {
+        return Outcome::Failed {
+            diff: vec![FieldDiff { field: "language".into(), missing: vec![expectation.language.clone()], extra: vec![] }],
+        };
+    }
+    let Some(tree) = parser.parse(&source, None) else {
+        return Outcome::Failed {
+            diff: vec![FieldDiff { field: "parse".into(), missing: vec![path.display().to_string()], extra: vec![] }],
+        };
+    };
+
+    let functions: Vec<String> = adapter.extract_functions(&tree, &source).into_iter().map(|f| f.name).collect();
+    let imports: Vec<String> = adapter.extract_imports(&tree, &source).into_iter().map(|i| i.source).collect();
+    let exports: Vec<String> = adapter.extract_exports(&tree, &source).into_iter().map(|e| e.name).collect();
+    let classes: Vec<String> = adapter.extract_classes(&tree, &source).into_iter().map(|c| c.name).collect();
+
+    let diffs: Vec<FieldDiff> = [
+        diff_field("functions", &expectation.functions, &functions),
+        diff_field("imports", &expectation.imports, &imports),
+        diff_field("exports", &expectation.exports, &exports),
+        diff_field("classes", &expectation.classes, &classes),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if diffs.is_empty() {
+        Outcome::Ok
+    } else {
+        Outcome::Failed { diff: diffs }
+    }
+}
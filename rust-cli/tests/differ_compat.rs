@@ -11,11 +11,15 @@ fn make_file_entry(module: &str, hash: &str, functions_count: usize, classes_cou
         module: module.to_string(),
         hash: hash.to_string(),
         lines: 10,
+        code_lines: 8,
+        comment_lines: 0,
+        blank_lines: 2,
         functions: (0..functions_count).map(|i| codegraph::graph::FunctionInfo {
             name: format!("fn{}", i),
             signature: format!("fn{}()", i),
             start_line: 1,
             end_line: 2,
+            complexity: 1,
         }).collect(),
         classes: (0..classes_count).map(|i| codegraph::graph::ClassInfo {
             name: format!("Class{}", i),
@@ -62,9 +66,13 @@ fn make_graph() -> CodeGraph {
             total_files: 3,
             total_functions: 2,
             total_classes: 0,
+            total_code_lines: 0,
+            total_comment_lines: 0,
+            total_blank_lines: 0,
             languages: [("typescript".to_string(), 3u32)].into_iter().collect(),
             modules: vec!["api".to_string(), "auth".to_string(), "old".to_string()],
             entry_points: vec![],
+            complexity_hotspots: vec![],
         },
         modules,
         files,